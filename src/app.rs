@@ -8,14 +8,16 @@ use once_cell::sync::{Lazy, OnceCell};
 
 use crate::{
     context::{AppCtx, FrameCtx},
+    core::color::history::PickSource,
     types::error::{append_global_error, ERROR_STACK},
     ui::{
         components::{colors::*, *},
         panels::{
             color_picker_panel::ColorPickerPanel, cryptography_panel::CryptographyPanel,
             datetime_panel::DateTimePanel, encoding_panel::EncodingPanel,
-            error_display::ErrorDisplay, generators_panel::GeneratorsPanel, image_panel::ImagePanel,
-            jwt_panel::JwtPanel, regex_panel::RegexPanel, top_panel::TopPanel,
+            error_display::ErrorDisplay, generators_panel::GeneratorsPanel,
+            image_panel::ImagePanel, jwt_panel::JwtPanel, regex_panel::RegexPanel,
+            top_panel::TopPanel,
         },
         traits::{UiComponent, UiPanel, UiWindow},
         windows::settings::SettingsWindow,
@@ -123,6 +125,13 @@ impl eframe::App for App {
             ctx.app.current_selected_color = ctx.app.picker.current_color;
         }
     }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        if let Some(app_ctx) = CONTEXT.get().and_then(|ctx| ctx.read().ok()) {
+            app_ctx.save_palettes(storage);
+            app_ctx.save_history(storage);
+        }
+    }
 }
 
 impl App {
@@ -164,6 +173,7 @@ impl App {
             }
 
             ctx.app.load_palettes(context.storage);
+            ctx.app.load_history(context.storage);
         }
 
         let mut fonts = egui::FontDefinitions::default();
@@ -287,19 +297,10 @@ impl App {
         // Check for backtick/grave key (`) to select color
         if ctx.egui.input(|i| i.key_pressed(egui::Key::Backtick)) {
             let picked_color = ctx.app.cursor_pick_color;
-            ctx.app.picker.current_color = picked_color;
+            ctx.app.picker.set_cur_color(picked_color);
 
             // Add to picking history (avoid duplicates of the same color)
-            if ctx.app.color_picking_history.is_empty()
-                || ctx.app.color_picking_history.last() != Some(&picked_color)
-            {
-                ctx.app.color_picking_history.push(picked_color);
-
-                // Keep history limited to last 20 colors
-                if ctx.app.color_picking_history.len() > 20 {
-                    ctx.app.color_picking_history.remove(0);
-                }
-            }
+            ctx.app.add_to_history(picked_color, PickSource::Screen);
 
             ctx.app.color_picking_enabled = false;
         }
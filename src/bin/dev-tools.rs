@@ -0,0 +1,1139 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use dev_tools_rs::core::{
+    color::{
+        delta_e::DeltaE,
+        import::import_palettes,
+        names::{ColorNameDatabase, ColorNameSet},
+        Color,
+    },
+    crypto::{
+        asymmetric::rsa::{
+            generate_rsa_keypair, RsaEncryptionPadding, RsaHash, RsaSignaturePadding,
+        },
+        stream::CipherJob,
+        symmetric::aes::AesKeySize,
+        CipherMode, CryptoAlgorithm, CryptoOperation, CryptographyProcessor, InputEncoding,
+        OutputEncoding, Padding,
+    },
+    datetime::{DateTimeFormat, DateTimeProcessor},
+    encoding::{EncodingProcessor, EncodingType},
+    generators::{GeneratorProcessor, GeneratorType},
+    image::{
+        CompressionOptions, CropRect, FilterType, ImageFormatType, ImageProcessor, ResizeOptions,
+    },
+    jwt::{self, JwtEncoderDecoder},
+    regex::RegexProcessor,
+};
+
+/// Headless access to the dev-tools-rs processors for scripts and CI.
+///
+/// Every command reads its input from a file when one is given and from stdin
+/// otherwise, and writes the result to stdout.
+#[derive(Parser)]
+#[command(name = "dev-tools", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Encode text as Base64, Base32 or URL encoding
+    Encode(EncodingArgs),
+    /// Decode Base64, Base32 or URL encoded text
+    Decode(EncodingArgs),
+    /// Encode, decode and verify JSON Web Tokens
+    #[command(subcommand)]
+    Jwt(JwtCommand),
+    /// Symmetric and asymmetric cryptography
+    Crypto(CryptoArgs),
+    /// Generate UUIDs, ULIDs, Nano IDs and passwords
+    Gen(GenArgs),
+    /// Convert between Unix timestamps and formatted dates
+    #[command(subcommand)]
+    Time(TimeCommand),
+    /// Match a regular expression against text
+    Regex(RegexArgs),
+    /// Transform and convert images
+    #[command(subcommand)]
+    Image(ImageCommand),
+    /// Look up colors
+    #[command(subcommand)]
+    Color(ColorCommand),
+}
+
+#[derive(Args)]
+struct EncodingArgs {
+    /// Encoding scheme
+    #[arg(short = 't', long = "type", value_enum, default_value_t = CliEncoding::Base64)]
+    encoding: CliEncoding,
+    /// Escape line breaks before encoding / unescape them after decoding
+    #[arg(long)]
+    line_breaks: bool,
+    /// Input file, stdin when omitted or `-`
+    input: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliEncoding {
+    Base64,
+    Base64Url,
+    Base64Mime,
+    Base32,
+    Url,
+}
+
+impl From<CliEncoding> for EncodingType {
+    fn from(encoding: CliEncoding) -> Self {
+        match encoding {
+            CliEncoding::Base64 => EncodingType::Base64,
+            CliEncoding::Base64Url => EncodingType::Base64Url,
+            CliEncoding::Base64Mime => EncodingType::Base64Mime,
+            CliEncoding::Base32 => EncodingType::Base32,
+            CliEncoding::Url => EncodingType::UrlEncoding,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum JwtCommand {
+    /// Print the header and claims of a token without verifying it
+    Decode {
+        /// File containing the token, stdin when omitted or `-`
+        input: Option<PathBuf>,
+    },
+    /// Sign a JSON claims document
+    Encode {
+        #[command(flatten)]
+        key: JwtKeyArgs,
+        /// File containing the claims JSON, stdin when omitted or `-`
+        input: Option<PathBuf>,
+    },
+    /// Verify the signature of a token
+    Verify {
+        #[command(flatten)]
+        key: JwtKeyArgs,
+        /// File containing the token, stdin when omitted or `-`
+        input: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct JwtKeyArgs {
+    /// Signing algorithm
+    #[arg(short, long, value_enum, default_value_t = CliJwtAlgorithm::Hs256)]
+    algorithm: CliJwtAlgorithm,
+    /// HMAC secret
+    #[arg(short, long)]
+    secret: Option<String>,
+    /// PEM file holding the RSA private key used for signing
+    #[arg(long)]
+    private_key: Option<PathBuf>,
+    /// PEM file holding the RSA public key used for verification
+    #[arg(long)]
+    public_key: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliJwtAlgorithm {
+    Hs256,
+    Hs384,
+    Hs512,
+    Rs256,
+    Rs384,
+    Rs512,
+}
+
+impl From<CliJwtAlgorithm> for jwt::Algorithm {
+    fn from(algorithm: CliJwtAlgorithm) -> Self {
+        match algorithm {
+            CliJwtAlgorithm::Hs256 => jwt::Algorithm::HS256,
+            CliJwtAlgorithm::Hs384 => jwt::Algorithm::HS384,
+            CliJwtAlgorithm::Hs512 => jwt::Algorithm::HS512,
+            CliJwtAlgorithm::Rs256 => jwt::Algorithm::RS256,
+            CliJwtAlgorithm::Rs384 => jwt::Algorithm::RS384,
+            CliJwtAlgorithm::Rs512 => jwt::Algorithm::RS512,
+        }
+    }
+}
+
+#[derive(Args)]
+struct CryptoArgs {
+    /// Operation to perform
+    #[arg(value_enum)]
+    operation: CliCryptoOperation,
+    /// Cipher algorithm
+    #[arg(short, long, value_enum, default_value_t = CliCryptoAlgorithm::Aes)]
+    algorithm: CliCryptoAlgorithm,
+    /// Block cipher mode for symmetric algorithms
+    #[arg(short, long, value_enum, default_value_t = CliCipherMode::Cbc)]
+    mode: CliCipherMode,
+    /// Padding for ECB and CBC mode
+    #[arg(short, long, value_enum, default_value_t = CliPadding::Pkcs7)]
+    padding: CliPadding,
+    /// Symmetric key
+    #[arg(short, long)]
+    key: Option<String>,
+    /// Encoding of the symmetric key, also used for generated keys
+    #[arg(long, value_enum, default_value_t = CliInputEncoding::Utf8)]
+    key_encoding: CliInputEncoding,
+    /// AES key size in bits
+    #[arg(long, value_enum, default_value_t = CliAesKeySize::Aes128)]
+    key_size: CliAesKeySize,
+    /// Initialization vector for every mode but ECB or nonce for AEAD algorithms
+    #[arg(long)]
+    iv: Option<String>,
+    /// Encoding of the IV or nonce, also used for generated ones
+    #[arg(long, value_enum, default_value_t = CliInputEncoding::Utf8)]
+    iv_encoding: CliInputEncoding,
+    /// Associated data authenticated by AEAD algorithms
+    #[arg(long)]
+    aad: Option<String>,
+    /// Authentication tag to verify when it is not appended to the ciphertext
+    #[arg(long)]
+    tag: Option<String>,
+    /// RSA key size in bits used by `keygen`
+    #[arg(long, default_value_t = 2048)]
+    rsa_bits: usize,
+    /// PEM file holding the RSA public key
+    #[arg(long)]
+    public_key: Option<PathBuf>,
+    /// PEM file holding the RSA private key
+    #[arg(long)]
+    private_key: Option<PathBuf>,
+    /// Signature to verify, encoded as selected with --encoding
+    #[arg(long)]
+    signature: Option<String>,
+    /// RSA padding, OAEP to encrypt or PSS to sign
+    #[arg(long, value_enum, default_value_t = CliRsaPadding::Pkcs1)]
+    rsa_padding: CliRsaPadding,
+    /// Hash of RSA signatures and OAEP
+    #[arg(long, value_enum, default_value_t = CliRsaHash::Sha256)]
+    hash: CliRsaHash,
+    /// Label of RSA-OAEP
+    #[arg(long)]
+    oaep_label: Option<String>,
+    /// Salt length of RSA-PSS in bytes, defaults to the hash length
+    #[arg(long)]
+    salt_length: Option<usize>,
+    /// Encoding of encrypted output and signatures
+    #[arg(short, long, value_enum, default_value_t = CliOutputEncoding::Hex)]
+    encoding: CliOutputEncoding,
+    /// Encoding of the input, defaults to UTF-8 for plaintext and hex for ciphertext
+    #[arg(long, value_enum)]
+    input_encoding: Option<CliInputEncoding>,
+    /// Write the raw encrypted or decrypted bytes to this file. The input is read as raw bytes in
+    /// chunks, so files of any size and binary files can be processed with symmetric algorithms
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Input file, stdin when omitted or `-`
+    input: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CliCryptoOperation {
+    Encrypt,
+    Decrypt,
+    Sign,
+    Verify,
+    /// Generate a random key (symmetric) or key pair (RSA)
+    Keygen,
+    /// Generate a random initialization vector
+    Iv,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliCryptoAlgorithm {
+    Aes,
+    Des,
+    #[value(name = "3des")]
+    TripleDes,
+    AesGcm,
+    AesGcmSiv,
+    AesCcm,
+    #[value(name = "aes-ccm8")]
+    AesCcm8,
+    #[value(name = "chacha20-poly1305")]
+    ChaCha20Poly1305,
+    #[value(name = "xchacha20-poly1305")]
+    XChaCha20Poly1305,
+    Rsa,
+}
+
+impl From<CliCryptoAlgorithm> for CryptoAlgorithm {
+    fn from(algorithm: CliCryptoAlgorithm) -> Self {
+        match algorithm {
+            CliCryptoAlgorithm::Aes => CryptoAlgorithm::AES,
+            CliCryptoAlgorithm::Des => CryptoAlgorithm::DES,
+            CliCryptoAlgorithm::TripleDes => CryptoAlgorithm::TripleDES,
+            CliCryptoAlgorithm::AesGcm => CryptoAlgorithm::AesGcm,
+            CliCryptoAlgorithm::AesGcmSiv => CryptoAlgorithm::AesGcmSiv,
+            CliCryptoAlgorithm::AesCcm => CryptoAlgorithm::AesCcm,
+            CliCryptoAlgorithm::AesCcm8 => CryptoAlgorithm::AesCcm8,
+            CliCryptoAlgorithm::ChaCha20Poly1305 => CryptoAlgorithm::ChaCha20Poly1305,
+            CliCryptoAlgorithm::XChaCha20Poly1305 => CryptoAlgorithm::XChaCha20Poly1305,
+            CliCryptoAlgorithm::Rsa => CryptoAlgorithm::RSA,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliAesKeySize {
+    #[value(name = "128")]
+    Aes128,
+    #[value(name = "192")]
+    Aes192,
+    #[value(name = "256")]
+    Aes256,
+}
+
+impl From<CliAesKeySize> for AesKeySize {
+    fn from(key_size: CliAesKeySize) -> Self {
+        match key_size {
+            CliAesKeySize::Aes128 => AesKeySize::Aes128,
+            CliAesKeySize::Aes192 => AesKeySize::Aes192,
+            CliAesKeySize::Aes256 => AesKeySize::Aes256,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliCipherMode {
+    Ecb,
+    Cbc,
+    Ctr,
+    Cfb,
+    Cfb8,
+    Ofb,
+}
+
+impl From<CliCipherMode> for CipherMode {
+    fn from(mode: CliCipherMode) -> Self {
+        match mode {
+            CliCipherMode::Ecb => CipherMode::ECB,
+            CliCipherMode::Cbc => CipherMode::CBC,
+            CliCipherMode::Ctr => CipherMode::CTR,
+            CliCipherMode::Cfb => CipherMode::CFB,
+            CliCipherMode::Cfb8 => CipherMode::CFB8,
+            CliCipherMode::Ofb => CipherMode::OFB,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliPadding {
+    Pkcs7,
+    Zero,
+    #[value(name = "iso10126")]
+    Iso10126,
+    #[value(name = "ansix923")]
+    AnsiX923,
+    None,
+}
+
+impl From<CliPadding> for Padding {
+    fn from(padding: CliPadding) -> Self {
+        match padding {
+            CliPadding::Pkcs7 => Padding::Pkcs7,
+            CliPadding::Zero => Padding::Zero,
+            CliPadding::Iso10126 => Padding::Iso10126,
+            CliPadding::AnsiX923 => Padding::AnsiX923,
+            CliPadding::None => Padding::NoPadding,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliInputEncoding {
+    Utf8,
+    Hex,
+    Base64,
+}
+
+impl From<CliInputEncoding> for InputEncoding {
+    fn from(encoding: CliInputEncoding) -> Self {
+        match encoding {
+            CliInputEncoding::Utf8 => InputEncoding::Utf8,
+            CliInputEncoding::Hex => InputEncoding::Hex,
+            CliInputEncoding::Base64 => InputEncoding::Base64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliOutputEncoding {
+    Hex,
+    Base64,
+}
+
+impl From<CliOutputEncoding> for OutputEncoding {
+    fn from(encoding: CliOutputEncoding) -> Self {
+        match encoding {
+            CliOutputEncoding::Hex => OutputEncoding::Hex,
+            CliOutputEncoding::Base64 => OutputEncoding::Base64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliRsaPadding {
+    Pkcs1,
+    Oaep,
+    Pss,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliRsaHash {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl From<CliRsaHash> for RsaHash {
+    fn from(hash: CliRsaHash) -> Self {
+        match hash {
+            CliRsaHash::Sha1 => RsaHash::Sha1,
+            CliRsaHash::Sha256 => RsaHash::Sha256,
+            CliRsaHash::Sha384 => RsaHash::Sha384,
+            CliRsaHash::Sha512 => RsaHash::Sha512,
+        }
+    }
+}
+
+#[derive(Args)]
+struct GenArgs {
+    /// Kind of value to generate
+    #[arg(value_enum)]
+    kind: CliGeneratorType,
+    /// Number of values to generate
+    #[arg(short = 'n', long, default_value_t = 1)]
+    count: usize,
+    /// Length of passwords and Nano IDs
+    #[arg(short, long)]
+    length: Option<usize>,
+    /// Custom Nano ID alphabet
+    #[arg(long)]
+    alphabet: Option<String>,
+    /// Leave uppercase letters out of passwords
+    #[arg(long)]
+    no_uppercase: bool,
+    /// Leave lowercase letters out of passwords
+    #[arg(long)]
+    no_lowercase: bool,
+    /// Leave digits out of passwords
+    #[arg(long)]
+    no_numbers: bool,
+    /// Include symbols in passwords
+    #[arg(long)]
+    symbols: bool,
+    /// Exclude ambiguous characters (0O1lI) from passwords
+    #[arg(long)]
+    exclude_ambiguous: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliGeneratorType {
+    Uuid,
+    Ulid,
+    Nanoid,
+    Password,
+}
+
+impl From<CliGeneratorType> for GeneratorType {
+    fn from(kind: CliGeneratorType) -> Self {
+        match kind {
+            CliGeneratorType::Uuid => GeneratorType::Uuid,
+            CliGeneratorType::Ulid => GeneratorType::Ulid,
+            CliGeneratorType::Nanoid => GeneratorType::NanoId,
+            CliGeneratorType::Password => GeneratorType::Password,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum TimeCommand {
+    /// Print the current Unix timestamp
+    Now,
+    /// Format a Unix timestamp (seconds or milliseconds)
+    Format {
+        #[command(flatten)]
+        format: TimeFormatArgs,
+        /// Timestamp, stdin when omitted
+        timestamp: Option<String>,
+    },
+    /// Parse a formatted date into a Unix timestamp
+    Parse {
+        #[command(flatten)]
+        format: TimeFormatArgs,
+        /// Date string, stdin when omitted
+        date: Option<String>,
+    },
+}
+
+#[derive(Args)]
+struct TimeFormatArgs {
+    /// Output/input format
+    #[arg(short, long, value_enum, default_value_t = CliDateTimeFormat::Iso8601)]
+    format: CliDateTimeFormat,
+    /// strftime-style pattern used with `--format custom`
+    #[arg(short, long, default_value = "%Y-%m-%d %H:%M:%S")]
+    pattern: String,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliDateTimeFormat {
+    Iso8601,
+    Rfc2822,
+    Rfc3339,
+    Custom,
+}
+
+#[derive(Args)]
+struct RegexArgs {
+    /// Regular expression
+    pattern: String,
+    /// Text file to search, stdin when omitted or `-`
+    input: Option<PathBuf>,
+    /// Case insensitive matching
+    #[arg(short = 'i', long)]
+    case_insensitive: bool,
+    /// `^` and `$` match at line boundaries
+    #[arg(short = 'm', long)]
+    multiline: bool,
+    /// `.` also matches newlines
+    #[arg(short = 's', long)]
+    dot_matches_newline: bool,
+    /// Print the full match result as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Subcommand)]
+enum ImageCommand {
+    /// Print dimensions, format and color type
+    Info {
+        /// Image file
+        input: PathBuf,
+    },
+    /// Apply transformations and write the result
+    Convert(ImageConvertArgs),
+}
+
+#[derive(Args)]
+struct ImageConvertArgs {
+    /// Source image
+    input: PathBuf,
+    /// Destination image, its extension selects the format unless `--format` is given
+    output: PathBuf,
+    /// Output format
+    #[arg(short, long, value_enum)]
+    format: Option<CliImageFormat>,
+    /// JPEG quality (0-100)
+    #[arg(short, long)]
+    quality: Option<u8>,
+    /// Crop rectangle as `x,y,width,height`, applied first
+    #[arg(long)]
+    crop: Option<String>,
+    /// Target size as `WIDTHxHEIGHT`, `WIDTHx` or `xHEIGHT`
+    #[arg(long)]
+    resize: Option<String>,
+    /// Stretch instead of keeping the aspect ratio when both sides are given
+    #[arg(long)]
+    ignore_aspect_ratio: bool,
+    /// Clockwise rotation in degrees
+    #[arg(long, value_parser = ["90", "180", "270"])]
+    rotate: Option<String>,
+    /// Flip horizontally
+    #[arg(long)]
+    flip_horizontal: bool,
+    /// Flip vertically
+    #[arg(long)]
+    flip_vertical: bool,
+    /// Convert to grayscale
+    #[arg(long)]
+    grayscale: bool,
+    /// Gaussian blur sigma
+    #[arg(long)]
+    blur: Option<f32>,
+    /// Brightness offset
+    #[arg(long, allow_hyphen_values = true)]
+    brightness: Option<i32>,
+    /// Contrast adjustment
+    #[arg(long, allow_hyphen_values = true)]
+    contrast: Option<f32>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliImageFormat {
+    Jpeg,
+    Png,
+    Gif,
+    Webp,
+    Tiff,
+    Bmp,
+    Ico,
+}
+
+impl From<CliImageFormat> for ImageFormatType {
+    fn from(format: CliImageFormat) -> Self {
+        match format {
+            CliImageFormat::Jpeg => ImageFormatType::Jpeg,
+            CliImageFormat::Png => ImageFormatType::Png,
+            CliImageFormat::Gif => ImageFormatType::Gif,
+            CliImageFormat::Webp => ImageFormatType::WebP,
+            CliImageFormat::Tiff => ImageFormatType::Tiff,
+            CliImageFormat::Bmp => ImageFormatType::Bmp,
+            CliImageFormat::Ico => ImageFormatType::Ico,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum ColorCommand {
+    /// Find the named colors closest to a color
+    Name(ColorNameArgs),
+}
+
+#[derive(Args)]
+struct ColorNameArgs {
+    /// Any CSS color like `#3b82f6` or `rgb(59 130 246)`, stdin when omitted
+    color: Option<String>,
+    /// Color difference metric
+    #[arg(short, long, value_enum, default_value_t = CliDeltaE::Ciede2000)]
+    metric: CliDeltaE,
+    /// Number of matches to print
+    #[arg(short = 'n', long, default_value_t = 1)]
+    count: usize,
+    /// Built-in sets to search, all when omitted
+    #[arg(short, long = "set", value_enum)]
+    sets: Vec<CliColorNameSet>,
+    /// Palette files to search as well (GIMP, ASE, ACO, Paint.NET, Sketch or CSS variables)
+    #[arg(short, long = "palette")]
+    palettes: Vec<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliDeltaE {
+    Cie76,
+    Ciede2000,
+    Oklab,
+}
+
+impl From<CliDeltaE> for DeltaE {
+    fn from(metric: CliDeltaE) -> Self {
+        match metric {
+            CliDeltaE::Cie76 => DeltaE::Cie76,
+            CliDeltaE::Ciede2000 => DeltaE::Ciede2000,
+            CliDeltaE::Oklab => DeltaE::Oklab,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CliColorNameSet {
+    Css,
+    X11,
+    Tailwind,
+}
+
+impl From<CliColorNameSet> for ColorNameSet {
+    fn from(set: CliColorNameSet) -> Self {
+        match set {
+            CliColorNameSet::Css => ColorNameSet::Css,
+            CliColorNameSet::X11 => ColorNameSet::X11,
+            CliColorNameSet::Tailwind => ColorNameSet::Tailwind,
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Encode(args) => run_encode(args),
+        Command::Decode(args) => run_decode(args),
+        Command::Jwt(command) => run_jwt(command),
+        Command::Crypto(args) => run_crypto(args),
+        Command::Gen(args) => run_gen(args),
+        Command::Time(command) => run_time(command),
+        Command::Regex(args) => run_regex(args),
+        Command::Image(command) => run_image(command),
+        Command::Color(command) => run_color(command),
+    };
+
+    match result {
+        Ok(output) => {
+            let mut stdout = io::stdout().lock();
+            let _ = writeln!(stdout, "{output}");
+        }
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Read the whole input from `path`, or from stdin when no path (or `-`) is given.
+fn read_input(path: Option<&Path>) -> Result<String> {
+    match path {
+        Some(path) if path != Path::new("-") => fs::read_to_string(path)
+            .with_context(|| format!("Failed to read input from {:?}", path)),
+        _ => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .context("Failed to read input from stdin")?;
+            Ok(input)
+        }
+    }
+}
+
+/// Use an inline argument when given, otherwise the trimmed contents of stdin.
+fn arg_or_stdin(arg: Option<String>) -> Result<String> {
+    match arg {
+        Some(arg) => Ok(arg),
+        None => Ok(read_input(None)?.trim().to_string()),
+    }
+}
+
+fn read_key_file(path: Option<&PathBuf>) -> Result<Option<String>> {
+    path.map(|path| {
+        fs::read_to_string(path).with_context(|| format!("Failed to read key from {:?}", path))
+    })
+    .transpose()
+}
+
+fn run_encode(args: EncodingArgs) -> Result<String> {
+    let mut processor = EncodingProcessor::new();
+    processor.encoding_type = args.encoding.into();
+    processor.handle_line_breaks = args.line_breaks;
+    processor.decoded_text = read_input(args.input.as_deref())?;
+
+    processor.encode()?;
+    Ok(processor.encoded_text)
+}
+
+fn run_decode(args: EncodingArgs) -> Result<String> {
+    let mut processor = EncodingProcessor::new();
+    processor.encoding_type = args.encoding.into();
+    processor.handle_line_breaks = args.line_breaks;
+    processor.encoded_text = read_input(args.input.as_deref())?.trim().to_string();
+
+    processor.decode()?;
+    Ok(processor.decoded_text)
+}
+
+fn jwt_processor(key: JwtKeyArgs) -> Result<JwtEncoderDecoder> {
+    let mut processor = JwtEncoderDecoder::new();
+    processor.algorithm = key.algorithm.into();
+    processor.secret = key.secret.unwrap_or_default();
+    processor.private_key = read_key_file(key.private_key.as_ref())?.unwrap_or_default();
+    processor.public_key = read_key_file(key.public_key.as_ref())?.unwrap_or_default();
+    Ok(processor)
+}
+
+fn run_jwt(command: JwtCommand) -> Result<String> {
+    match command {
+        JwtCommand::Decode { input } => {
+            let mut processor = JwtEncoderDecoder::new();
+            processor.encoded = read_input(input.as_deref())?.trim().to_string();
+
+            let header = processor.get_header()?;
+            let header: serde_json::Value = serde_json::from_str(&header)?;
+            processor.algorithm = match header["alg"].as_str() {
+                Some("HS384") => jwt::Algorithm::HS384,
+                Some("HS512") => jwt::Algorithm::HS512,
+                Some("RS256") => jwt::Algorithm::RS256,
+                Some("RS384") => jwt::Algorithm::RS384,
+                Some("RS512") => jwt::Algorithm::RS512,
+                _ => jwt::Algorithm::HS256,
+            };
+            processor.decode()?;
+
+            Ok(format!(
+                "{}\n{}",
+                serde_json::to_string_pretty(&header)?,
+                processor.decoded
+            ))
+        }
+        JwtCommand::Encode { key, input } => {
+            let mut processor = jwt_processor(key)?;
+            processor.decoded = read_input(input.as_deref())?;
+
+            processor.encode()?;
+            Ok(processor.encoded)
+        }
+        JwtCommand::Verify { key, input } => {
+            let mut processor = jwt_processor(key)?;
+            processor.encoded = read_input(input.as_deref())?.trim().to_string();
+
+            processor.verify()?;
+            Ok("Signature valid".to_string())
+        }
+    }
+}
+
+fn run_crypto(args: CryptoArgs) -> Result<String> {
+    let mut processor = CryptographyProcessor::new();
+    let input = &mut processor.input;
+    input.algorithm = args.algorithm.into();
+    input.mode = Some(args.mode.into());
+    input.padding = args.padding.into();
+    input.aes_key_size = Some(args.key_size.into());
+    input.key = args.key.unwrap_or_default();
+    input.iv = args.iv;
+    input.associated_data = args.aad;
+    input.tag = args.tag;
+    input.public_key = read_key_file(args.public_key.as_ref())?;
+    input.private_key = read_key_file(args.private_key.as_ref())?;
+    input.signature = args.signature;
+    input.rsa_options.hash = args.hash.into();
+    input.rsa_options.oaep_label = args.oaep_label;
+    input.rsa_options.pss_salt_length = args.salt_length;
+    input.encoding = args.encoding.into();
+    input.input_encodings.key = args.key_encoding.into();
+    input.input_encodings.iv = args.iv_encoding.into();
+
+    match args.operation {
+        CliCryptoOperation::Keygen => {
+            if input.algorithm == CryptoAlgorithm::RSA {
+                // The processor generates RSA keys on a background thread for the UI,
+                // a CLI can simply block.
+                let (public_key, private_key) = generate_rsa_keypair(args.rsa_bits)?;
+                return Ok(format!("{}{}", private_key, public_key.trim_end()));
+            }
+            processor.generate_random_key()?;
+            return Ok(processor.input.key);
+        }
+        CliCryptoOperation::Iv => {
+            processor.input.mode = Some(CipherMode::CBC);
+            processor.generate_random_iv()?;
+            return processor
+                .input
+                .iv
+                .ok_or_else(|| anyhow!("IV not generated for this algorithm"));
+        }
+        _ => {}
+    }
+
+    input.operation = match args.operation {
+        CliCryptoOperation::Encrypt => CryptoOperation::Encrypt,
+        CliCryptoOperation::Decrypt => CryptoOperation::Decrypt,
+        CliCryptoOperation::Sign => CryptoOperation::Sign,
+        CliCryptoOperation::Verify => CryptoOperation::Verify,
+        CliCryptoOperation::Keygen | CliCryptoOperation::Iv => unreachable!(),
+    };
+    // encrypting always appends the tag, a detached one is only read back to decrypt
+    if input.tag.is_some() && input.operation != CryptoOperation::Decrypt {
+        bail!("--tag is only used to decrypt, encryption appends the tag to the ciphertext");
+    }
+    input.detached_tag = input.tag.is_some();
+    let options = &mut input.rsa_options;
+    match (args.rsa_padding, &input.operation) {
+        (CliRsaPadding::Pkcs1, _) => {}
+        (CliRsaPadding::Oaep, CryptoOperation::Encrypt | CryptoOperation::Decrypt) => {
+            options.encryption_padding = RsaEncryptionPadding::Oaep;
+        }
+        (CliRsaPadding::Pss, CryptoOperation::Sign | CryptoOperation::Verify) => {
+            options.signature_padding = RsaSignaturePadding::Pss;
+        }
+        _ => bail!("OAEP padding is used to encrypt and decrypt, PSS to sign and verify"),
+    }
+    if let Some(encoding) = args.input_encoding {
+        match input.operation {
+            CryptoOperation::Decrypt => input.input_encodings.ciphertext = encoding.into(),
+            _ => input.input_encodings.plaintext = encoding.into(),
+        }
+    }
+    if let Some(output) = args.output {
+        // a detached tag is decoded like the ciphertext would be
+        let job = CipherJob::from_input(&processor.input)?;
+        let written = match args.input.as_deref() {
+            Some(path) if path != Path::new("-") => job.process_file(path, &output, |_, _| {})?,
+            _ => {
+                let file = File::create(&output)
+                    .with_context(|| format!("Failed to create {:?}", output))?;
+                let mut writer = BufWriter::new(file);
+                let written = job.process(&mut io::stdin().lock(), &mut writer)?;
+                writer.flush()?;
+                written
+            }
+        };
+        return Ok(format!("Wrote {written} bytes to {}", output.display()));
+    }
+
+    let text = read_input(args.input.as_deref())?;
+    input.input_text = match input.operation {
+        CryptoOperation::Decrypt => text.trim().to_string(),
+        _ => text,
+    };
+
+    processor.process()
+}
+
+fn run_gen(args: GenArgs) -> Result<String> {
+    let mut processor = GeneratorProcessor::new();
+    processor.generator_type = args.kind.into();
+    processor.generated_count = args.count;
+    if let Some(length) = args.length {
+        processor.password_length = length;
+        processor.nano_id_length = length;
+    }
+    if let Some(alphabet) = args.alphabet {
+        processor.nano_id_alphabet = alphabet;
+        processor.use_custom_alphabet = true;
+    }
+    processor.include_uppercase = !args.no_uppercase;
+    processor.include_lowercase = !args.no_lowercase;
+    processor.include_numbers = !args.no_numbers;
+    processor.include_symbols = args.symbols;
+    processor.exclude_ambiguous = args.exclude_ambiguous;
+
+    processor.generate().map_err(|e| anyhow!(e.to_string()))?;
+    Ok(processor.output)
+}
+
+fn datetime_processor(args: TimeFormatArgs) -> DateTimeProcessor {
+    let mut processor = DateTimeProcessor::new();
+    processor.custom_format = args.pattern.clone();
+    processor.selected_format = match args.format {
+        CliDateTimeFormat::Iso8601 => DateTimeFormat::Iso8601,
+        CliDateTimeFormat::Rfc2822 => DateTimeFormat::Rfc2822,
+        CliDateTimeFormat::Rfc3339 => DateTimeFormat::Rfc3339,
+        CliDateTimeFormat::Custom => DateTimeFormat::Custom(args.pattern),
+    };
+    processor
+}
+
+fn run_time(command: TimeCommand) -> Result<String> {
+    match command {
+        TimeCommand::Now => Ok(DateTimeProcessor::new().get_cached_timestamp().to_string()),
+        TimeCommand::Format { format, timestamp } => {
+            let mut processor = datetime_processor(format);
+            processor.timestamp_input = arg_or_stdin(timestamp)?;
+
+            processor.timestamp_to_formatted();
+            if !processor.error_message.is_empty() {
+                bail!(processor.error_message);
+            }
+            Ok(processor.formatted_result)
+        }
+        TimeCommand::Parse { format, date } => {
+            let mut processor = datetime_processor(format);
+            processor.formatted_input = arg_or_stdin(date)?;
+
+            processor.formatted_to_timestamp();
+            if !processor.error_message.is_empty() {
+                bail!(processor.error_message);
+            }
+            Ok(processor.timestamp_result)
+        }
+    }
+}
+
+fn run_regex(args: RegexArgs) -> Result<String> {
+    let mut processor = RegexProcessor::new();
+    processor.pattern = args.pattern;
+    processor.text = read_input(args.input.as_deref())?;
+    processor.case_insensitive = args.case_insensitive;
+    processor.multiline = args.multiline;
+    processor.dot_matches_newline = args.dot_matches_newline;
+
+    processor.process()?;
+    let result = processor
+        .result
+        .ok_or_else(|| anyhow!("Regex produced no result"))?;
+
+    if args.json {
+        return Ok(serde_json::to_string_pretty(&result)?);
+    }
+
+    Ok(result
+        .matches
+        .iter()
+        .map(|m| {
+            let mut line = format!("{}..{}\t{}", m.start, m.end, m.text);
+            for group in &m.groups {
+                line.push('\t');
+                line.push_str(group);
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn run_image(command: ImageCommand) -> Result<String> {
+    match command {
+        ImageCommand::Info { input } => {
+            let mut processor = ImageProcessor::new();
+            processor.load_from_file(&input)?;
+            let info = processor
+                .image_info()
+                .ok_or_else(|| anyhow!("No image information available"))?;
+
+            let mut lines = vec![
+                format!("Dimensions: {}x{}", info.width, info.height),
+                format!("Color type: {}", info.color_type),
+            ];
+            if let Some(format) = info.format {
+                lines.push(format!("Format: {}", format.display_name()));
+            }
+            if let Some(file_size) = info.file_size {
+                lines.push(format!("File size: {} bytes", file_size));
+            }
+            Ok(lines.join("\n"))
+        }
+        ImageCommand::Convert(args) => run_image_convert(args),
+    }
+}
+
+fn run_color(command: ColorCommand) -> Result<String> {
+    match command {
+        ColorCommand::Name(args) => run_color_name(args),
+    }
+}
+
+fn run_color_name(args: ColorNameArgs) -> Result<String> {
+    let color = Color::from_css(&arg_or_stdin(args.color)?)?;
+
+    let mut db = if args.sets.is_empty() {
+        ColorNameDatabase::builtin()
+    } else {
+        let mut db = ColorNameDatabase::default();
+        for set in args.sets {
+            db.add_set(set.into());
+        }
+        db
+    };
+    for path in &args.palettes {
+        for palette in import_palettes(path)? {
+            db.add_palette(&palette);
+        }
+    }
+
+    let matches = db.nearest_n(&color, args.metric.into(), args.count);
+    if matches.is_empty() {
+        bail!("No named colors to search");
+    }
+    Ok(matches
+        .iter()
+        .map(|found| {
+            format!(
+                "{}\t{}\t{}\t{:.2}",
+                found.name,
+                found.source,
+                found.color.as_hex(),
+                found.distance
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn parse_crop(crop: &str) -> Result<CropRect> {
+    let parts = crop
+        .split(',')
+        .map(|part| part.trim().parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| anyhow!("Crop must be given as x,y,width,height"))?;
+    match parts[..] {
+        [x, y, width, height] => Ok(CropRect::new(x, y, width, height)),
+        _ => bail!("Crop must be given as x,y,width,height"),
+    }
+}
+
+fn parse_resize(resize: &str, maintain_aspect_ratio: bool) -> Result<ResizeOptions> {
+    let (width, height) = resize
+        .split_once(['x', 'X'])
+        .ok_or_else(|| anyhow!("Resize must be given as WIDTHxHEIGHT, WIDTHx or xHEIGHT"))?;
+    let parse_side = |side: &str| -> Result<Option<u32>> {
+        if side.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(side.parse().map_err(|_| {
+                anyhow!("Invalid resize dimension: {}", side)
+            })?))
+        }
+    };
+
+    Ok(ResizeOptions {
+        width: parse_side(width)?,
+        height: parse_side(height)?,
+        maintain_aspect_ratio,
+        filter: FilterType::default(),
+    })
+}
+
+fn run_image_convert(args: ImageConvertArgs) -> Result<String> {
+    let mut processor = ImageProcessor::new();
+    processor.load_from_file(&args.input)?;
+
+    if let Some(crop) = &args.crop {
+        processor.crop(&parse_crop(crop)?)?;
+    }
+    if let Some(resize) = &args.resize {
+        processor.resize(&parse_resize(resize, !args.ignore_aspect_ratio)?)?;
+    }
+    match args.rotate.as_deref() {
+        Some("90") => processor.rotate_90()?,
+        Some("180") => processor.rotate_180()?,
+        Some("270") => processor.rotate_270()?,
+        _ => {}
+    }
+    if args.flip_horizontal {
+        processor.flip_horizontal()?;
+    }
+    if args.flip_vertical {
+        processor.flip_vertical()?;
+    }
+    if args.grayscale {
+        processor.to_grayscale()?;
+    }
+    if let Some(sigma) = args.blur {
+        processor.blur(sigma)?;
+    }
+    if let Some(value) = args.brightness {
+        processor.adjust_brightness(value)?;
+    }
+    if let Some(contrast) = args.contrast {
+        processor.adjust_contrast(contrast)?;
+    }
+
+    let format = match args.format {
+        Some(format) => format.into(),
+        None => dev_tools_rs::core::image::format_from_extension(&args.output)
+            .and_then(ImageFormatType::from_image_format)
+            .ok_or_else(|| anyhow!("Cannot infer image format from {:?}", args.output))?,
+    };
+
+    match args.quality {
+        Some(quality) => processor.compress_and_save(
+            &args.output,
+            &CompressionOptions {
+                quality: quality.min(100),
+                format,
+            },
+        )?,
+        None => processor.save_as(&args.output, format)?,
+    }
+
+    let image = processor
+        .current_image()
+        .ok_or_else(|| anyhow!("No image loaded"))?;
+    Ok(format!(
+        "Wrote {}x{} {} to {}",
+        image.width(),
+        image.height(),
+        format.display_name(),
+        args.output.display()
+    ))
+}
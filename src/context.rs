@@ -7,7 +7,11 @@ use serde::{Deserialize, Serialize};
 use crate::{
     app::{CentralPanelTab, DARK_VISUALS, LIGHT_VISUALS},
     core::{
-        color::{palettes::Palettes, Color, ColorFormat},
+        color::{
+            history::{ColorHistory, PickSource},
+            palettes::Palettes,
+            Color, ColorFormat, NamedPalette,
+        },
         crypto::CryptographyProcessor,
         datetime::DateTimeProcessor,
         encoding::EncodingProcessor,
@@ -15,7 +19,7 @@ use crate::{
         jwt::JwtEncoderDecoder,
         regex::RegexProcessor,
     },
-    settings::{ColorDisplayFmtEnum, Settings},
+    settings::Settings,
     types::error::append_global_error,
     ui::components::color_picker::ColorPicker,
     utils::{
@@ -50,7 +54,7 @@ pub struct AppCtx {
 
     pub zoom_window_dragged: bool,
     pub color_picking_enabled: bool,
-    pub color_picking_history: Vec<Color>,
+    pub color_picking_history: ColorHistory,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -88,7 +92,7 @@ impl Default for AppCtx {
             central_panel_tab: CentralPanelTab::DateTime,
             zoom_window_dragged: false,
             color_picking_enabled: false,
-            color_picking_history: Vec::new(),
+            color_picking_history: ColorHistory::default(),
         }
     }
 }
@@ -107,14 +111,7 @@ impl AppCtx {
 
     /// Current color display format
     pub fn display_format(&self) -> ColorFormat {
-        match self.settings.color_display_format {
-            ColorDisplayFmtEnum::Hex => ColorFormat::Hex,
-            ColorDisplayFmtEnum::HexUppercase => ColorFormat::HexUpercase,
-            ColorDisplayFmtEnum::CssRgb => ColorFormat::CssRgb,
-            ColorDisplayFmtEnum::CssHsl => ColorFormat::CssHsl {
-                degree_symbol: true,
-            },
-        }
+        self.settings.color_display_format.color_format(true)
     }
 
     /// Format a color as a string using display color format from settings
@@ -124,22 +121,24 @@ impl AppCtx {
 
     /// Format a color as a string using clipboard color format from settings
     pub fn clipboard_color(&self, color: &Color) -> String {
-        let format = match self
+        let format = self
             .settings
             .color_clipboard_format
             .as_ref()
             .unwrap_or(&self.settings.color_display_format)
-        {
-            ColorDisplayFmtEnum::Hex => ColorFormat::Hex,
-            ColorDisplayFmtEnum::HexUppercase => ColorFormat::HexUpercase,
-            ColorDisplayFmtEnum::CssRgb => ColorFormat::CssRgb,
-            ColorDisplayFmtEnum::CssHsl => ColorFormat::CssHsl {
-                degree_symbol: false,
-            },
-        };
+            .color_format(false);
         color.display(format)
     }
 
+    /// Format a palette as a string using palette clipboard format from settings
+    pub fn clipboard_palette(&self, palette: &NamedPalette) -> String {
+        palette.display(
+            &self.settings.palette_clipboard_format,
+            self.settings.rgb_working_space,
+            self.settings.illuminant,
+        )
+    }
+
     /// Load palettes from appropriate location based on the target arch
     pub fn load_palettes(&mut self, _storage: Option<&dyn Storage>) {
         if self.settings.cache_colors {
@@ -181,6 +180,54 @@ impl AppCtx {
         }
     }
 
+    pub fn load_history(&mut self, _storage: Option<&dyn Storage>) {
+        if self.settings.cache_colors {
+            #[cfg(target_arch = "wasm32")]
+            if let Some(storage) = _storage {
+                match ColorHistory::load_from_storage(storage) {
+                    Ok(history) => self.color_picking_history = history,
+                    Err(e) => append_global_error(format!("failed to load color history, {e:?}")),
+                }
+            }
+
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(path) = Palettes::dir(APP_NAME) {
+                match ColorHistory::load_or_default(path.join(ColorHistory::FILE_NAME)) {
+                    Ok(history) => self.color_picking_history = history,
+                    Err(e) => append_global_error(format!("failed to load color history, {e:?}")),
+                }
+            }
+            self.color_picking_history
+                .truncate(self.settings.color_history_limit);
+        }
+    }
+
+    /// Save the color picking history next to the palettes
+    pub fn save_history(&self, _storage: &mut dyn Storage) {
+        if !self.settings.cache_colors {
+            return;
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Err(e) = self.color_picking_history.save_to_storage(_storage) {
+            append_global_error(format!("failed to save color history, {e:?}"));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(dir) = Palettes::dir(APP_NAME) {
+            if let Err(e) = self
+                .color_picking_history
+                .save(dir.join(ColorHistory::FILE_NAME))
+            {
+                append_global_error(format!("failed to save color history, {e:?}"));
+            }
+        }
+    }
+
+    /// Records a picked color in the history keeping it within the configured limit
+    pub fn add_to_history(&mut self, color: Color, source: PickSource) {
+        self.color_picking_history
+            .push(color, source, self.settings.color_history_limit);
+    }
+
     /// Adds a color to the currently selected palette
     pub fn add_color(&mut self, color: Color) {
         if !self.palettes.current_mut().palette.add(color) {
@@ -8,12 +8,14 @@ use serde::{Deserialize, Serialize};
 
 use super::{Color, Hsl, Hsv, Rgb};
 
-#[derive(Clone, Copy, Default, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
 pub struct Cmyk {
     c: f32,
     m: f32,
     y: f32,
     k: f32,
+    #[serde(default = "super::opaque")]
+    a: f32,
 }
 
 impl Cmyk {
@@ -22,7 +24,13 @@ impl Cmyk {
         let m = if m.is_nan() { 0. } else { m };
         let y = if y.is_nan() { 0. } else { y };
         let k = if k.is_nan() { 0. } else { k };
-        Self { c, m, y, k }
+        Self { c, m, y, k, a: 1. }
+    }
+
+    /// Returns this color with Alpha set to `alpha` in the range 0.0 ..= 1.0
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.a = super::clamp_alpha(alpha);
+        self
     }
 
     #[inline(always)]
@@ -49,6 +57,12 @@ impl Cmyk {
         self.k
     }
 
+    #[inline(always)]
+    /// Returns Alpha value in the range of 0.0 ..= 1.0
+    pub fn alpha(&self) -> f32 {
+        self.a
+    }
+
     /// Returns Cyan value in the range of 0.0 ..= 100.0
     pub fn c_scaled(&self) -> f32 {
         self.c * 100.
@@ -118,6 +132,10 @@ impl From<Color> for Cmyk {
             Color::Hsv(c) => Rgb::from(c).into(),
             Color::Hsl(c) => Rgb::from(c).into(),
             Color::Color32(c) => Rgb::from(c).into(),
+            Color::Lab(c) => Rgb::from(c).into(),
+            Color::Lch(c) => Rgb::from(c).into(),
+            Color::Oklab(c) => Rgb::from(c).into(),
+            Color::Oklch(c) => Rgb::from(c).into(),
         }
     }
 }
@@ -150,13 +168,13 @@ impl From<Rgb> for Cmyk {
         let k = 1. - rgb.iter().copied().fold(f32::NAN, f32::max);
 
         if math::eq_f32(k, 1.) {
-            return Cmyk::new(0., 0., 0., k);
+            return Cmyk::new(0., 0., 0., k).with_alpha(color.alpha());
         }
 
         let c = (1. - r - k) / (1. - k);
         let m = (1. - g - k) / (1. - k);
         let y = (1. - b - k) / (1. - k);
 
-        Cmyk::new(c, m, y, k)
+        Cmyk::new(c, m, y, k).with_alpha(color.alpha())
     }
 }
@@ -0,0 +1,244 @@
+//! WCAG 2.1 and APCA contrast between a foreground (text) and a background color.
+//!
+//! A translucent foreground is composited over the background before measuring, the alpha of the
+//! background itself is ignored.
+
+use super::{Color, Hsl, Rgb};
+
+/// Minimum WCAG contrast ratio of normal text for level AA.
+pub const AA_NORMAL: f32 = 4.5;
+/// Minimum WCAG contrast ratio of large text for level AA.
+pub const AA_LARGE: f32 = 3.;
+/// Minimum WCAG contrast ratio of normal text for level AAA.
+pub const AAA_NORMAL: f32 = 7.;
+/// Minimum WCAG contrast ratio of large text for level AAA.
+pub const AAA_LARGE: f32 = 4.5;
+
+/// Returns the WCAG 2.1 relative luminance of `color` in the range of 0.0 ..= 1.0
+pub fn relative_luminance(color: &Color) -> f32 {
+    let rgb = color.rgb().inverse_srgb_compand();
+    0.2126 * rgb.r() + 0.7152 * rgb.g() + 0.0722 * rgb.b()
+}
+
+/// Returns the WCAG 2.1 contrast ratio of `fg` over `bg` in the range of 1.0 ..= 21.0
+pub fn contrast_ratio(fg: &Color, bg: &Color) -> f32 {
+    let fg = relative_luminance(&composite(fg, bg));
+    let bg = relative_luminance(bg);
+    let (lighter, darker) = if fg > bg { (fg, bg) } else { (bg, fg) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Returns the APCA (0.0.98G-4g) lightness contrast Lc of text `fg` on `bg`.
+///
+/// The value is roughly in the range of -108.0 ..= 106.0, positive for dark text on a light
+/// background and negative for light text on a dark background.
+pub fn apca_contrast(fg: &Color, bg: &Color) -> f32 {
+    const BLACK_THRESHOLD: f32 = 0.022;
+    const BLACK_CLAMP: f32 = 1.414;
+    const SCALE: f32 = 1.14;
+    const OFFSET: f32 = 0.027;
+    const LOW_CLIP: f32 = 0.1;
+
+    fn screen_luminance(color: &Color) -> f32 {
+        let rgb = color.rgb().inverse_gamma_compand(2.4);
+        let y = 0.2126729 * rgb.r() + 0.7151522 * rgb.g() + 0.0721750 * rgb.b();
+        if y < BLACK_THRESHOLD {
+            y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+        } else {
+            y
+        }
+    }
+
+    let text = screen_luminance(&composite(fg, bg));
+    let background = screen_luminance(bg);
+
+    if (background - text).abs() < 0.0005 {
+        return 0.;
+    }
+
+    let lc = if background > text {
+        let sapc = (background.powf(0.56) - text.powf(0.57)) * SCALE;
+        if sapc < LOW_CLIP {
+            0.
+        } else {
+            sapc - OFFSET
+        }
+    } else {
+        let sapc = (background.powf(0.65) - text.powf(0.62)) * SCALE;
+        if sapc > -LOW_CLIP {
+            0.
+        } else {
+            sapc + OFFSET
+        }
+    };
+
+    lc * 100.
+}
+
+/// Returns `fg` composited over an opaque `bg`.
+fn composite(fg: &Color, bg: &Color) -> Color {
+    let alpha = fg.alpha();
+    if alpha >= 1. {
+        return *fg;
+    }
+    let fg = fg.rgb();
+    let bg = bg.rgb();
+    Rgb::new(
+        fg.r() * alpha + bg.r() * (1. - alpha),
+        fg.g() * alpha + bg.g() * (1. - alpha),
+        fg.b() * alpha + bg.b() * (1. - alpha),
+    )
+    .into()
+}
+
+//####################################################################################################
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contrast {
+    pub ratio: f32,
+    pub apca: f32,
+}
+
+impl Contrast {
+    pub fn new(fg: &Color, bg: &Color) -> Self {
+        Self {
+            ratio: contrast_ratio(fg, bg),
+            apca: apca_contrast(fg, bg),
+        }
+    }
+
+    pub fn aa_normal(&self) -> bool {
+        self.ratio >= AA_NORMAL
+    }
+
+    pub fn aa_large(&self) -> bool {
+        self.ratio >= AA_LARGE
+    }
+
+    pub fn aaa_normal(&self) -> bool {
+        self.ratio >= AAA_NORMAL
+    }
+
+    pub fn aaa_large(&self) -> bool {
+        self.ratio >= AAA_LARGE
+    }
+}
+
+/// Returns the color closest to `fg` in HSL lightness that reaches a WCAG contrast ratio of at
+/// least `min_ratio` over `bg`, keeping the hue, saturation and alpha of `fg`.
+///
+/// Returns `fg` itself if it already passes and `None` if neither direction can reach the ratio.
+pub fn nearest_passing(fg: &Color, bg: &Color, min_ratio: f32) -> Option<Color> {
+    if contrast_ratio(fg, bg) >= min_ratio {
+        return Some(*fg);
+    }
+
+    let hsl = fg.hsl();
+    let with_light =
+        |l: f32| -> Color { Hsl::new(hsl.h(), hsl.s(), l).with_alpha(hsl.alpha()).into() };
+    let passes = |l: f32| contrast_ratio(&with_light(l), bg) >= min_ratio;
+
+    // luminance grows monotonically with lightness for a fixed hue and saturation, so the
+    // boundary in each direction can be found with a binary search
+    let search = |target: f32| -> Option<f32> {
+        if !passes(target) {
+            return None;
+        }
+        let (mut failing, mut passing) = (hsl.l(), target);
+        for _ in 0..24 {
+            let mid = (failing + passing) / 2.;
+            if passes(mid) {
+                passing = mid;
+            } else {
+                failing = mid;
+            }
+        }
+        Some(passing)
+    };
+
+    let light = match (search(0.), search(1.)) {
+        (Some(darker), Some(lighter)) => {
+            if hsl.l() - darker <= lighter - hsl.l() {
+                darker
+            } else {
+                lighter
+            }
+        }
+        (Some(l), None) | (None, Some(l)) => l,
+        (None, None) => return None,
+    };
+
+    Some(with_light(light))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eq_f32(lhs: f32, rhs: f32) -> bool {
+        (lhs - rhs).abs() < 1e-4
+    }
+
+    #[test]
+    fn computes_wcag_contrast() {
+        let black = Color::black();
+        let white = Color::white();
+        assert!(eq_f32(relative_luminance(&white), 1.));
+        assert!(eq_f32(relative_luminance(&black), 0.));
+        assert!(eq_f32(contrast_ratio(&black, &white), 21.));
+        assert!(eq_f32(contrast_ratio(&white, &black), 21.));
+        assert!(eq_f32(contrast_ratio(&white, &white), 1.));
+
+        let gray = Color::from(Rgb::new_scaled(0x76, 0x76, 0x76));
+        let contrast = Contrast::new(&gray, &white);
+        assert!((contrast.ratio - 4.54).abs() < 0.01, "{}", contrast.ratio);
+        assert!(contrast.aa_normal());
+        assert!(contrast.aa_large());
+        assert!(!contrast.aaa_normal());
+        assert!(contrast.aaa_large());
+
+        // half transparent black over white is a mid gray
+        let translucent = black.with_alpha(0.5);
+        let ratio = contrast_ratio(&translucent, &white);
+        assert!((ratio - 3.98).abs() < 0.01, "{ratio}");
+    }
+
+    #[test]
+    fn computes_apca_contrast() {
+        macro_rules! test_case {
+            ($fg:literal on $bg:literal => $want:literal) => {
+                let fg = Color::from_hex($fg).unwrap();
+                let bg = Color::from_hex($bg).unwrap();
+                let lc = apca_contrast(&fg, &bg);
+                assert!((lc - $want).abs() < 0.1, "{} on {}: {lc}", $fg, $bg);
+            };
+        }
+
+        test_case!("000000" on "ffffff" => 106.04);
+        test_case!("ffffff" on "000000" => -107.88);
+        test_case!("888888" on "ffffff" => 63.06);
+        test_case!("ffffff" on "888888" => -68.54);
+        test_case!("777777" on "777777" => 0.);
+    }
+
+    #[test]
+    fn finds_nearest_passing_color() {
+        let white = Color::white();
+        let gray = Color::from(Rgb::new_scaled(0x99, 0x99, 0x99));
+
+        let passing = nearest_passing(&gray, &white, AA_NORMAL).unwrap();
+        let ratio = contrast_ratio(&passing, &white);
+        assert!((AA_NORMAL..AA_NORMAL + 0.05).contains(&ratio), "{ratio}");
+        assert!(passing.hsl().l() < gray.hsl().l());
+
+        let black = Color::black();
+        let passing = nearest_passing(&gray, &black, AAA_NORMAL).unwrap();
+        assert!(eq_f32(passing.hsl().l(), gray.hsl().l()));
+
+        let mid = Color::from(Rgb::new_scaled(0x76, 0x76, 0x76));
+        let passing = nearest_passing(&mid, &mid, AA_NORMAL).unwrap();
+        assert!(contrast_ratio(&passing, &mid) >= AA_NORMAL);
+
+        assert_eq!(nearest_passing(&gray, &gray, 22.), None);
+    }
+}
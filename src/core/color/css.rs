@@ -0,0 +1,384 @@
+use super::{
+    hex_chars_to_u8, Color, Hsl, Hsv, Illuminant, Lab, LchAb, Oklab, Oklch, Rgb, RgbWorkingSpace,
+    Xyz, U8_MAX,
+};
+
+use anyhow::{anyhow, bail, Result};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{alpha1, char, multispace0, multispace1},
+    combinator::{all_consuming, map, opt, success, value},
+    number::complete::float,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+
+/// The CSS named colors sorted by name.
+#[rustfmt::skip]
+pub(super) const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff), ("antiquewhite", 0xfaebd7), ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4), ("azure", 0xf0ffff), ("beige", 0xf5f5dc), ("bisque", 0xffe4c4),
+    ("black", 0x000000), ("blanchedalmond", 0xffebcd), ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2), ("brown", 0xa52a2a), ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0), ("chartreuse", 0x7fff00), ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50), ("cornflowerblue", 0x6495ed), ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c), ("cyan", 0x00ffff), ("darkblue", 0x00008b), ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b), ("darkgray", 0xa9a9a9), ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9), ("darkkhaki", 0xbdb76b), ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f), ("darkorange", 0xff8c00), ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000), ("darksalmon", 0xe9967a), ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b), ("darkslategray", 0x2f4f4f), ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1), ("darkviolet", 0x9400d3), ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff), ("dimgray", 0x696969), ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff), ("firebrick", 0xb22222), ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22), ("fuchsia", 0xff00ff), ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff), ("gold", 0xffd700), ("goldenrod", 0xdaa520), ("gray", 0x808080),
+    ("green", 0x008000), ("greenyellow", 0xadff2f), ("grey", 0x808080), ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4), ("indianred", 0xcd5c5c), ("indigo", 0x4b0082), ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c), ("lavender", 0xe6e6fa), ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00), ("lemonchiffon", 0xfffacd), ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080), ("lightcyan", 0xe0ffff), ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3), ("lightgreen", 0x90ee90), ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1), ("lightsalmon", 0xffa07a), ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de), ("lightyellow", 0xffffe0), ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32), ("linen", 0xfaf0e6), ("magenta", 0xff00ff), ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa), ("mediumblue", 0x0000cd), ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db), ("mediumseagreen", 0x3cb371), ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a), ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585), ("midnightblue", 0x191970), ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1), ("moccasin", 0xffe4b5), ("navajowhite", 0xffdead),
+    ("navy", 0x000080), ("oldlace", 0xfdf5e6), ("olive", 0x808000), ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500), ("orangered", 0xff4500), ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa), ("palegreen", 0x98fb98), ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093), ("papayawhip", 0xffefd5), ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f), ("pink", 0xffc0cb), ("plum", 0xdda0dd), ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080), ("rebeccapurple", 0x663399), ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f), ("royalblue", 0x4169e1), ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072), ("sandybrown", 0xf4a460), ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee), ("sienna", 0xa0522d), ("silver", 0xc0c0c0), ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd), ("slategray", 0x708090), ("slategrey", 0x708090),
+    ("snow", 0xfffafa), ("springgreen", 0x00ff7f), ("steelblue", 0x4682b4), ("tan", 0xd2b48c),
+    ("teal", 0x008080), ("thistle", 0xd8bfd8), ("tomato", 0xff6347), ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee), ("wheat", 0xf5deb3), ("white", 0xffffff), ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00), ("yellowgreen", 0x9acd32),
+];
+
+/// Returns the CSS named color `name`, the lookup is case sensitive.
+pub fn named_color(name: &str) -> Option<Color> {
+    NAMED_COLORS
+        .binary_search_by(|(it, _)| it.cmp(&name))
+        .ok()
+        .map(|idx| {
+            let [_, r, g, b] = NAMED_COLORS[idx].1.to_be_bytes();
+            Rgb::new_scaled(r, g, b).into()
+        })
+}
+
+/// Parses a CSS color value like `#ff000080`, `rgb(255 0 0 / 50%)`, `oklch(62.8% 0.26 29.2)` or
+/// `rebeccapurple`. Function and color names are case insensitive.
+pub fn parse_color(text: &str) -> Result<Color> {
+    let text = text.trim().to_ascii_lowercase();
+
+    if let Some(hex) = text.strip_prefix('#') {
+        return parse_hex_color(hex);
+    }
+    if text == "transparent" {
+        return Ok(Color::black().with_alpha(0.));
+    }
+    if let Some(color) = named_color(&text) {
+        return Ok(color);
+    }
+
+    let (_, function) =
+        all_consuming(parse_function)(&text).map_err(|_| anyhow!("unrecognized color `{text}`"))?;
+    function.into_color()
+}
+
+fn parse_hex_color(hex: &str) -> Result<Color> {
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        bail!("invalid hex color `#{hex}`");
+    }
+
+    let channels: Vec<u8> = match hex.len() {
+        3 | 4 => hex.bytes().map(|b| hex_chars_to_u8((b, b))).collect(),
+        6 | 8 => hex
+            .as_bytes()
+            .chunks(2)
+            .map(|b| hex_chars_to_u8((b[0], b[1])))
+            .collect(),
+        _ => bail!("hex color `#{hex}` must have 3, 4, 6 or 8 digits"),
+    };
+
+    let alpha = channels.get(3).map(|&a| a as f32 / U8_MAX).unwrap_or(1.);
+    Ok(Rgb::new_scaled(channels[0], channels[1], channels[2])
+        .with_alpha(alpha)
+        .into())
+}
+
+//####################################################################################################
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Component {
+    Number(f32),
+    Percentage(f32),
+    /// Angle in degrees
+    Angle(f32),
+    None,
+}
+
+impl Component {
+    /// Returns a number, resolving percentages relative to `reference`.
+    fn number(self, reference: f32) -> Result<f32> {
+        match self {
+            Component::Number(n) => Ok(n),
+            Component::Percentage(p) => Ok(p / 100. * reference),
+            Component::None => Ok(0.),
+            Component::Angle(_) => bail!("unexpected angle"),
+        }
+    }
+
+    /// Returns a hue normalized to the range of 0.0 ..= 360.0
+    fn hue(self) -> Result<f32> {
+        match self {
+            Component::Number(n) | Component::Angle(n) => Ok(n.rem_euclid(360.)),
+            Component::None => Ok(0.),
+            Component::Percentage(_) => bail!("hue can't be a percentage"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Function<'a> {
+    name: &'a str,
+    color_space: Option<&'a str>,
+    components: [Component; 3],
+    alpha: Option<Component>,
+}
+
+impl Function<'_> {
+    fn into_color(self) -> Result<Color> {
+        let [c1, c2, c3] = self.components;
+        let alpha = match self.alpha {
+            Some(alpha) => alpha.number(1.)?,
+            None => 1.,
+        };
+
+        let color: Color = match (self.name, self.color_space) {
+            ("rgb" | "rgba", None) => Rgb::new(
+                c1.number(U8_MAX)? / U8_MAX,
+                c2.number(U8_MAX)? / U8_MAX,
+                c3.number(U8_MAX)? / U8_MAX,
+            )
+            .into(),
+            ("hsl" | "hsla", None) => Hsl::new(
+                c1.hue()? / 360.,
+                (c2.number(100.)? / 100.).clamp(0., 1.),
+                (c3.number(100.)? / 100.).clamp(0., 1.),
+            )
+            .into(),
+            ("hwb", None) => {
+                let h = c1.hue()? / 360.;
+                let mut w = (c2.number(100.)? / 100.).clamp(0., 1.);
+                let mut b = (c3.number(100.)? / 100.).clamp(0., 1.);
+                if w + b >= 1. {
+                    let sum = w + b;
+                    w /= sum;
+                    b /= sum;
+                }
+                let v = 1. - b;
+                let s = if v == 0. { 0. } else { 1. - w / v };
+                Hsv::new(h, s, v).into()
+            }
+            ("lab", None) => Lab::new(
+                c1.number(100.)?.clamp(0., 100.),
+                c2.number(125.)?,
+                c3.number(125.)?,
+            )
+            .into(),
+            ("lch", None) => LchAb::new(
+                c1.number(100.)?.clamp(0., 100.),
+                c2.number(150.)?.max(0.),
+                c3.hue()?,
+            )
+            .into(),
+            ("oklab", None) => Oklab::new(
+                c1.number(1.)?.clamp(0., 1.),
+                c2.number(0.4)?,
+                c3.number(0.4)?,
+            )
+            .into(),
+            ("oklch", None) => Oklch::new(
+                c1.number(1.)?.clamp(0., 1.),
+                c2.number(0.4)?.max(0.),
+                c3.hue()?,
+            )
+            .into(),
+            ("color", Some(space)) => {
+                let rgb = Rgb::new(c1.number(1.)?, c2.number(1.)?, c3.number(1.)?);
+                match space {
+                    "srgb" => rgb.into(),
+                    "display-p3" => Xyz::from_rgb(rgb, RgbWorkingSpace::DisplayP3, Illuminant::D65)
+                        .to_rgb(RgbWorkingSpace::SRGB, Illuminant::D65)
+                        .into(),
+                    _ => bail!("unsupported color space `{space}`"),
+                }
+            }
+            (name, _) => bail!("unsupported color function `{name}`"),
+        };
+
+        Ok(color.with_alpha(alpha))
+    }
+}
+
+fn parse_dimension(i: &str) -> IResult<&str, Component> {
+    let (i, n) = float(i)?;
+    alt((
+        value(Component::Percentage(n), char('%')),
+        value(Component::Angle(n), tag("deg")),
+        value(Component::Angle(n * 0.9), tag("grad")),
+        value(Component::Angle(n.to_degrees()), tag("rad")),
+        value(Component::Angle(n * 360.), tag("turn")),
+        success(Component::Number(n)),
+    ))(i)
+}
+
+fn parse_component(i: &str) -> IResult<&str, Component> {
+    alt((value(Component::None, tag("none")), parse_dimension))(i)
+}
+
+type Arguments = ([Component; 3], Option<Component>);
+
+/// Parses the legacy comma separated syntax, e.g. `255, 0, 0, 0.5`.
+fn parse_legacy_arguments(i: &str) -> IResult<&str, Arguments> {
+    let separator = || delimited(multispace0, char(','), multispace0);
+    map(
+        tuple((
+            parse_component,
+            preceded(separator(), parse_component),
+            preceded(separator(), parse_component),
+            opt(preceded(separator(), parse_component)),
+        )),
+        |(c1, c2, c3, alpha)| ([c1, c2, c3], alpha),
+    )(i)
+}
+
+/// Parses the space separated syntax, e.g. `255 0 0 / 50%`.
+fn parse_modern_arguments(i: &str) -> IResult<&str, Arguments> {
+    map(
+        tuple((
+            parse_component,
+            preceded(multispace1, parse_component),
+            preceded(multispace1, parse_component),
+            opt(preceded(
+                delimited(multispace0, char('/'), multispace0),
+                parse_component,
+            )),
+        )),
+        |(c1, c2, c3, alpha)| ([c1, c2, c3], alpha),
+    )(i)
+}
+
+fn parse_function(i: &str) -> IResult<&str, Function<'_>> {
+    let (i, name) = terminated(alpha1, pair(char('('), multispace0))(i)?;
+    let (i, color_space, (components, alpha)) = if name == "color" {
+        let (i, color_space) = terminated(
+            take_while1(|c: char| c.is_ascii_alphanumeric() || c == '-'),
+            multispace1,
+        )(i)?;
+        let (i, arguments) = parse_modern_arguments(i)?;
+        (i, Some(color_space), arguments)
+    } else {
+        let (i, arguments) = alt((parse_legacy_arguments, parse_modern_arguments))(i)?;
+        (i, None, arguments)
+    };
+    let (i, _) = pair(multispace0, char(')'))(i)?;
+
+    Ok((
+        i,
+        Function {
+            name,
+            color_space,
+            components,
+            alpha,
+        },
+    ))
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use super::{named_color, parse_color, NAMED_COLORS};
+
+    #[test]
+    fn parses_css_colors() {
+        macro_rules! test_case {
+            ($text:literal, $hex:literal) => {
+                let color = parse_color($text).unwrap_or_else(|e| panic!("{}: {e}", $text));
+                assert_eq!(color.as_hex(), $hex, "{}", $text);
+            };
+        }
+
+        test_case!("#f00", "#ff0000");
+        test_case!("#F00A", "#ff0000aa");
+        test_case!("#781e50", "#781e50");
+        test_case!("#781E5080", "#781e5080");
+        test_case!("rgb(120, 30, 80)", "#781e50");
+        test_case!("rgba(120,30,80,0.5)", "#781e5080");
+        test_case!("rgb(120 30 80)", "#781e50");
+        test_case!("rgb(120 30 80 / 50%)", "#781e5080");
+        test_case!("RGB(100%, 0%, 50%)", "#ff0080");
+        test_case!("rgb(none 255 none)", "#00ff00");
+        test_case!("hsl(120, 100%, 50%)", "#00ff00");
+        test_case!("hsla(0.5turn 100% 25% / 0.3)", "#0080804d");
+        test_case!("hsl(-120deg 100 50)", "#0000ff");
+        test_case!("hwb(0 0% 0%)", "#ff0000");
+        test_case!("hwb(120 20% 40%)", "#339933");
+        test_case!("hwb(0 60% 60%)", "#808080");
+        test_case!("lab(54.29% 80.8 69.89)", "#ff0000");
+        test_case!("lab(46.28 -47.55 48.59)", "#008000");
+        test_case!("lch(54.29 106.84 40.85)", "#ff0000");
+        test_case!("oklch(62.8% 0.2577 29.23)", "#ff0000");
+        test_case!("oklch(0.452 0.313 264.05deg / 1)", "#0000ff");
+        test_case!("oklab(0.628 0.2249 0.1258)", "#ff0000");
+        test_case!("color(display-p3 1 0 0)", "#ff0000");
+        test_case!("color(display-p3 0.9176 0.2003 0.1386)", "#ff0000");
+        test_case!("color(srgb 50% 0 1)", "#8000ff");
+        test_case!("  RebeccaPurple ", "#663399");
+        test_case!("transparent", "#00000000");
+        test_case!("lab(54.29% 80.8 69.89 / 0)", "#ff000000");
+        test_case!("white", "#ffffff");
+    }
+
+    #[test]
+    fn rejects_invalid_colors() {
+        for text in [
+            "",
+            "#ff000",
+            "#gg0000",
+            "rgb(1, 2)",
+            "rgb(1 2 3",
+            "rgb(1, 2 3)",
+            "rgb(10deg 0 0)",
+            "hsl(10% 50% 50%)",
+            "cmyk(1 0 0 0)",
+            "color(rec2020 1 0 0)",
+            "notacolor",
+        ] {
+            assert!(parse_color(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn named_colors() {
+        assert_eq!(NAMED_COLORS.len(), 148);
+        assert!(NAMED_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+        for (name, _) in NAMED_COLORS {
+            assert!(named_color(name).is_some(), "{name}");
+        }
+        assert_eq!(named_color("tomato").unwrap().as_hex(), "#ff6347");
+        assert!(named_color("transparent").is_none());
+    }
+}
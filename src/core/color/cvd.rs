@@ -0,0 +1,176 @@
+//! Color vision deficiency simulation.
+//!
+//! The red-green and blue-yellow dichromacies use the full severity matrices of Machado, Oliveira
+//! and Fernandes (2009), which agree closely with the Viénot/Brettel projections while being a
+//! single linear transform. Achromatopsia maps every color to its relative luminance. All of them
+//! operate on linear sRGB.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    palette::{Palette, PaletteEntry},
+    Color, Rgb, U8_MAX,
+};
+use crate::utils::math::{Matrix1x3, Matrix3};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ColorVisionDeficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+    Achromatopsia,
+}
+
+/// Linear sRGB value of every 8-bit sRGB channel value.
+static LINEAR_SRGB: Lazy<[f32; 256]> = Lazy::new(|| {
+    let mut lut = [0.; 256];
+    for (i, value) in lut.iter_mut().enumerate() {
+        let v = i as f32 / U8_MAX;
+        *value = Rgb::new(v, v, v).inverse_srgb_compand().r();
+    }
+    lut
+});
+
+impl ColorVisionDeficiency {
+    pub fn all() -> [Self; 4] {
+        [
+            Self::Protanopia,
+            Self::Deuteranopia,
+            Self::Tritanopia,
+            Self::Achromatopsia,
+        ]
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Protanopia => "Protanopia",
+            Self::Deuteranopia => "Deuteranopia",
+            Self::Tritanopia => "Tritanopia",
+            Self::Achromatopsia => "Achromatopsia",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Protanopia => "No red cones",
+            Self::Deuteranopia => "No green cones",
+            Self::Tritanopia => "No blue cones",
+            Self::Achromatopsia => "No color vision",
+        }
+    }
+
+    /// Returns the simulation matrix applied to linear sRGB.
+    #[rustfmt::skip]
+    pub fn matrix(self) -> Matrix3 {
+        match self {
+            Self::Protanopia => [
+                [ 0.152286, 1.052583, -0.204868],
+                [ 0.114503, 0.786281,  0.099216],
+                [-0.003882,-0.048116,  1.051998],
+            ],
+            Self::Deuteranopia => [
+                [ 0.367322, 0.860646, -0.227968],
+                [ 0.280085, 0.672501,  0.047413],
+                [-0.011820, 0.042940,  0.968881],
+            ],
+            Self::Tritanopia => [
+                [ 1.255528,-0.076749, -0.178779],
+                [-0.078411, 0.930809,  0.147602],
+                [ 0.004733, 0.691367,  0.303900],
+            ],
+            Self::Achromatopsia => [
+                [0.2126, 0.7152, 0.0722],
+                [0.2126, 0.7152, 0.0722],
+                [0.2126, 0.7152, 0.0722],
+            ],
+        }
+        .into()
+    }
+
+    /// Returns `color` as seen with this deficiency, the alpha is kept as is.
+    pub fn simulate(self, color: &Color) -> Color {
+        let linear = Matrix1x3::from(color.rgb().inverse_srgb_compand());
+        Rgb::from(self.matrix() * linear)
+            .srgb_compand()
+            .with_alpha(color.alpha())
+            .into()
+    }
+
+    pub fn simulate_palette(self, palette: &Palette) -> Palette {
+        palette
+            .entries()
+            .map(|entry| PaletteEntry {
+                color: self.simulate(&entry.color),
+                name: entry.name.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the 8-bit sRGB channels `rgb` as seen with this deficiency.
+    pub fn simulate_srgb8(self, rgb: [u8; 3]) -> [u8; 3] {
+        let linear = Matrix1x3::from(rgb.map(|c| LINEAR_SRGB[c as usize]));
+        let simulated = Rgb::from(self.matrix() * linear).srgb_compand();
+        [
+            simulated.r_scaled().round() as u8,
+            simulated.g_scaled().round() as u8,
+            simulated.b_scaled().round() as u8,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_neutral_colors() {
+        let grays = [0x00, 0x40, 0x80, 0xc0, 0xff];
+        for deficiency in ColorVisionDeficiency::all() {
+            for gray in grays {
+                let [r, g, b] = deficiency.simulate_srgb8([gray, gray, gray]);
+                for channel in [r, g, b] {
+                    assert!(
+                        channel.abs_diff(gray) <= 1,
+                        "{deficiency:?} {gray} => {r} {g} {b}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn simulates_deficiencies() {
+        macro_rules! test_case {
+            ($deficiency:ident, $hex:literal => $want:literal) => {
+                let color = Color::from_hex($hex).unwrap();
+                let simulated = ColorVisionDeficiency::$deficiency.simulate(&color);
+                assert_eq!(simulated.as_hex(), $want, "{:?}", stringify!($deficiency));
+                let rgb = color.as_rgb_triplet_scaled();
+                let [r, g, b] =
+                    ColorVisionDeficiency::$deficiency.simulate_srgb8([rgb.0, rgb.1, rgb.2]);
+                assert_eq!(Color::from(Rgb::new_scaled(r, g, b)).as_hex(), $want);
+            };
+        }
+
+        test_case!(Protanopia, "ff0000" => "#6d5f00");
+        test_case!(Deuteranopia, "ff0000" => "#a39000");
+        test_case!(Tritanopia, "0000ff" => "#006b96");
+        test_case!(Achromatopsia, "ff0000" => "#7f7f7f");
+    }
+
+    #[test]
+    fn simulates_palettes() {
+        let palette: Palette = [
+            Color::from_hex("ff0000").unwrap().with_alpha(0.5),
+            Color::white(),
+        ]
+        .into_iter()
+        .collect();
+        let simulated = ColorVisionDeficiency::Achromatopsia.simulate_palette(&palette);
+        let colors: Vec<_> = simulated.iter().collect();
+        assert_eq!(colors.len(), 2);
+        assert_eq!(colors[0].alpha(), 0.5);
+        assert_eq!(colors[1].as_hex(), "#ffffff");
+    }
+}
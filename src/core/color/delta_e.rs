@@ -0,0 +1,164 @@
+//! Color difference metrics.
+//!
+//! CIE76 and CIEDE2000 work on CIELAB relative to D50 like CSS does. Oklab distances are
+//! multiplied by 100 so that all metrics are on a similar scale where about 1.0 is a just
+//! noticeable difference.
+
+use serde::{Deserialize, Serialize};
+
+use super::{Color, Lab};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DeltaE {
+    Cie76,
+    #[default]
+    Ciede2000,
+    Oklab,
+}
+
+impl DeltaE {
+    pub fn all() -> [Self; 3] {
+        [Self::Cie76, Self::Ciede2000, Self::Oklab]
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Cie76 => "CIE76",
+            Self::Ciede2000 => "CIEDE2000",
+            Self::Oklab => "Oklab",
+        }
+    }
+
+    /// Returns the difference between `a` and `b`, alpha is ignored.
+    pub fn distance(self, a: &Color, b: &Color) -> f32 {
+        match self {
+            Self::Cie76 => cie76(&Lab::from(a.rgb()), &Lab::from(b.rgb())),
+            Self::Ciede2000 => ciede2000(&Lab::from(a.rgb()), &Lab::from(b.rgb())),
+            Self::Oklab => {
+                let (a, b) = (a.oklab(), b.oklab());
+                euclidean([a.l(), a.a(), a.b()], [b.l(), b.a(), b.b()]) * 100.
+            }
+        }
+    }
+}
+
+fn euclidean(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f32>()
+        .sqrt()
+}
+
+pub fn cie76(a: &Lab, b: &Lab) -> f32 {
+    euclidean([a.l(), a.a(), a.b()], [b.l(), b.a(), b.b()])
+}
+
+/// CIEDE2000 with the parametric factors kL, kC and kH set to 1.
+pub fn ciede2000(lab1: &Lab, lab2: &Lab) -> f32 {
+    // computed in f64 as the formula is sensitive to rounding around the hue discontinuities
+    let (l1, a1, b1) = (lab1.l() as f64, lab1.a() as f64, lab1.b() as f64);
+    let (l2, a2, b2) = (lab2.l() as f64, lab2.a() as f64, lab2.b() as f64);
+
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+    let c_mean = (c1 + c2) / 2.;
+    let c_mean7 = c_mean.powi(7);
+    let g = 0.5 * (1. - (c_mean7 / (c_mean7 + 25f64.powi(7))).sqrt());
+
+    let a1 = a1 * (1. + g);
+    let a2 = a2 * (1. + g);
+    let c1 = a1.hypot(b1);
+    let c2 = a2.hypot(b2);
+    let hue = |a: f64, b: f64| {
+        if a == 0. && b == 0. {
+            0.
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.)
+        }
+    };
+    let h1 = hue(a1, b1);
+    let h2 = hue(a2, b2);
+
+    let delta_l = l2 - l1;
+    let delta_c = c2 - c1;
+    let delta_h = if c1 * c2 == 0. {
+        0.
+    } else if (h2 - h1).abs() <= 180. {
+        h2 - h1
+    } else if h2 - h1 > 180. {
+        h2 - h1 - 360.
+    } else {
+        h2 - h1 + 360.
+    };
+    let delta_h = 2. * (c1 * c2).sqrt() * (delta_h / 2.).to_radians().sin();
+
+    let l_mean = (l1 + l2) / 2.;
+    let c_mean = (c1 + c2) / 2.;
+    let h_mean = if c1 * c2 == 0. {
+        h1 + h2
+    } else if (h1 - h2).abs() <= 180. {
+        (h1 + h2) / 2.
+    } else if h1 + h2 < 360. {
+        (h1 + h2 + 360.) / 2.
+    } else {
+        (h1 + h2 - 360.) / 2.
+    };
+
+    let t = 1. - 0.17 * (h_mean - 30.).to_radians().cos()
+        + 0.24 * (2. * h_mean).to_radians().cos()
+        + 0.32 * (3. * h_mean + 6.).to_radians().cos()
+        - 0.20 * (4. * h_mean - 63.).to_radians().cos();
+    let delta_theta = 30. * (-((h_mean - 275.) / 25.).powi(2)).exp();
+    let c_mean7 = c_mean.powi(7);
+    let r_c = 2. * (c_mean7 / (c_mean7 + 25f64.powi(7))).sqrt();
+    let s_l = 1. + 0.015 * (l_mean - 50.).powi(2) / (20. + (l_mean - 50.).powi(2)).sqrt();
+    let s_c = 1. + 0.045 * c_mean;
+    let s_h = 1. + 0.015 * c_mean * t;
+    let r_t = -(2. * delta_theta).to_radians().sin() * r_c;
+
+    let l = delta_l / s_l;
+    let c = delta_c / s_c;
+    let h = delta_h / s_h;
+    (l * l + c * c + h * h + r_t * c * h).sqrt() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_ciede2000() {
+        // reference pairs from Sharma, Wu and Dalal (2005)
+        macro_rules! test_case {
+            ($l1:literal, $a1:literal, $b1:literal; $l2:literal, $a2:literal, $b2:literal => $want:literal) => {
+                let lab1 = Lab::new($l1, $a1, $b1);
+                let lab2 = Lab::new($l2, $a2, $b2);
+                let delta = ciede2000(&lab1, &lab2);
+                assert!((delta - $want).abs() < 1e-3, "{delta} != {}", $want);
+                let delta = ciede2000(&lab2, &lab1);
+                assert!((delta - $want).abs() < 1e-3, "{delta} != {}", $want);
+            };
+        }
+
+        test_case!(50., 2.6772, -79.7751; 50., 0., -82.7485 => 2.0425);
+        test_case!(50., 0., 0.; 50., -1., 2. => 2.3669);
+        test_case!(50., 2.5, 0.; 50., 0., -2.5 => 4.3065);
+        test_case!(50., 2.5, 0.; 73., 25., -18. => 27.1492);
+        test_case!(50., 2.5, 0.; 61., -5., 29. => 22.8977);
+        test_case!(60.2574, -34.0099, 36.2677; 60.4626, -34.1751, 39.4387 => 1.2644);
+        test_case!(22.7233, 20.0904, -46.694; 23.0331, 14.973, -42.5619 => 2.0373);
+    }
+
+    #[test]
+    fn computes_distances() {
+        let black = Color::black();
+        let white = Color::white();
+        for metric in DeltaE::all() {
+            assert_eq!(metric.distance(&black, &black), 0., "{metric:?}");
+            let distance = metric.distance(&black, &white);
+            assert!((distance - 100.).abs() < 0.1, "{metric:?} {distance}");
+        }
+        assert!((cie76(&Lab::new(50., 0., 0.), &Lab::new(50., 3., 4.)) - 5.).abs() < 1e-5);
+    }
+}
@@ -1,4 +1,7 @@
-use super::{palette::Palette, Cmyk, Color, Hsl, Hsv};
+use super::{
+    palette::Palette, Cmyk, Color, Hsl, Hsv, Illuminant, Lab, LchAb, LchUv, Luv, RgbWorkingSpace,
+    Xyy,
+};
 
 use anyhow::{Error, Result};
 use nom::{
@@ -12,7 +15,10 @@ use nom::{
     Err, IResult, Parser,
 };
 use serde::{Deserialize, Serialize};
-use std::{fmt::Write, num::ParseIntError};
+use std::{
+    fmt::{self, Write},
+    num::ParseIntError,
+};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CustomPaletteFormat {
@@ -22,17 +28,43 @@ pub struct CustomPaletteFormat {
 }
 
 impl CustomPaletteFormat {
-    pub fn format_palette(&self, palette: &Palette) -> Result<String> {
+    pub fn format_palette(
+        &self,
+        palette: &Palette,
+        ws: RgbWorkingSpace,
+        illuminant: Illuminant,
+    ) -> Result<String> {
         let mut s = self.prefix.clone();
         let entry_format = CustomColorFormat::parse(&self.entry_format)?;
         for entry in palette.iter() {
-            s.push_str(&entry_format.format_color(entry)?);
+            s.push_str(&entry_format.format_color(entry, ws, illuminant)?);
         }
         s.push_str(&self.suffix);
         Ok(s)
     }
+
+    /// Returns the errors found in the entry format, see [`CustomColorFormat::validate`].
+    pub fn validate(&self) -> Vec<FormatError> {
+        CustomColorFormat::validate(&self.entry_format)
+    }
+}
+
+/// A problem found in a custom format by [`CustomColorFormat::validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatError {
+    /// Offset of the offending character counted in characters, not bytes.
+    pub position: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at column {}", self.message, self.position + 1)
+    }
 }
 
+impl std::error::Error for FormatError {}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct CustomColorFormat<'a>(Vec<FormatToken<'a>>);
 
@@ -44,13 +76,58 @@ impl<'a> CustomColorFormat<'a> {
         }
     }
 
-    pub fn format_color(&self, color: &Color) -> Result<String> {
+    /// Checks every color field of `text`. Parsing never fails as anything that isn't a valid
+    /// field is kept as text, so this is used to point out fields that would end up printed
+    /// verbatim. A `{` only starts a field when it is closed by `}` before the next `{`, which
+    /// keeps braces used as text like `{{ {r} }}` valid. A `{` followed by a color symbol that is
+    /// never closed is reported as a missing `}`.
+    pub fn validate(text: &str) -> Vec<FormatError> {
+        let mut errors = vec![];
+        let mut rest = text;
+        while let Some(start) = rest.find('{') {
+            let field = &rest[start..];
+            let inner = &field[1..];
+            match (inner.find('}'), inner.find('{')) {
+                (Some(close), open) if open.map(|open| close < open).unwrap_or(true) => {
+                    if let Err(Err::Error(e) | Err::Failure(e)) = parse_color_field(field) {
+                        errors.push(field_error(text, inner, e));
+                    }
+                    rest = &inner[close + 1..];
+                }
+                _ => {
+                    if let Some(unclosed) = unclosed_field_end(inner) {
+                        errors.push(FormatError {
+                            position: text[..text.len() - unclosed.len()].chars().count(),
+                            message: "expected `}`".into(),
+                        });
+                    }
+                    rest = inner;
+                }
+            }
+        }
+        errors
+    }
+
+    /// Formats `color` using this format. CIE symbols are computed by treating the color as
+    /// encoded in the working space `ws`, relative to the `illuminant` reference white.
+    pub fn format_color(
+        &self,
+        color: &Color,
+        ws: RgbWorkingSpace,
+        illuminant: Illuminant,
+    ) -> Result<String> {
         use ColorSymbol::*;
 
         let rgb = color.rgb();
         let cmyk = Cmyk::from(rgb);
         let hsl = Hsl::from(rgb);
         let hsv = Hsv::from(rgb);
+        let xyz = color.xyz(ws, illuminant);
+        let xyy = Xyy::from_xyz(xyz, illuminant);
+        let lab = Lab::from_xyz(xyz, illuminant);
+        let lch_ab = LchAb::from(lab);
+        let luv = Luv::from_xyz(xyz, illuminant);
+        let lch_uv = LchUv::from(luv);
 
         let mut s = String::new();
 
@@ -61,16 +138,17 @@ impl<'a> CustomColorFormat<'a> {
                     symbol,
                     digit_format,
                 }) => match symbol {
-                    Red | Green | Blue | Cyan | Magenta | Yellow | Key | Cyan100 | Magenta100
-                    | Yellow100 | Key100 | HSLHue | HSLSaturation | HSLLight | HSVHue
-                    | HSVSaturation | HSVValue | LabL | LabA | LabB | LCHabL | LCHabC | LCHabH
-                    | LuvL | LuvU | LuvV | LCHuvL | LCHuvC | LCHuvH | xyYx | xyYy | xyYY | XYZx
-                    | XYZy | XYZz | HSLHue360 | HSLSaturation100 | HSLLight100 | HSVHue360
-                    | HSVSaturation100 | HSVValue100 => {
+                    Red | Green | Blue | Alpha | Cyan | Magenta | Yellow | Key | Cyan100
+                    | Magenta100 | Yellow100 | Key100 | HSLHue | HSLSaturation | HSLLight
+                    | HSVHue | HSVSaturation | HSVValue | LabL | LabA | LabB | LCHabL | LCHabC
+                    | LCHabH | LuvL | LuvU | LuvV | LCHuvL | LCHuvC | LCHuvH | xyYx | xyYy
+                    | xyYY | XYZx | XYZy | XYZz | HSLHue360 | HSLSaturation100 | HSLLight100
+                    | HSVHue360 | HSVSaturation100 | HSVValue100 => {
                         let num = match symbol {
                             Red => rgb.r(),
                             Green => rgb.g(),
                             Blue => rgb.b(),
+                            Alpha => rgb.alpha(),
 
                             Cyan => cmyk.c(),
                             Magenta => cmyk.m(),
@@ -98,21 +176,48 @@ impl<'a> CustomColorFormat<'a> {
                             HSVSaturation100 => hsv.s_scaled(),
                             HSVValue100 => hsv.v_scaled(),
 
-                            _ => unreachable!(),
+                            LabL => lab.l(),
+                            LabA => lab.a(),
+                            LabB => lab.b(),
+
+                            LCHabL => lch_ab.l(),
+                            LCHabC => lch_ab.c(),
+                            LCHabH => lch_ab.h(),
+
+                            LuvL => luv.l(),
+                            LuvU => luv.u(),
+                            LuvV => luv.v(),
+
+                            LCHuvL => lch_uv.l(),
+                            LCHuvC => lch_uv.c(),
+                            LCHuvH => lch_uv.h(),
+
+                            xyYx => xyy.x(),
+                            xyYy => xyy.y(),
+                            xyYY => xyy.Y(),
+
+                            XYZx => xyz.x(),
+                            XYZy => xyz.y(),
+                            XYZz => xyz.z(),
+
+                            Red255 | Green255 | Blue255 | Alpha255 => unreachable!(),
                         };
 
+                        // Integer formats share the sign so that -20 and 20 differ in hex too
+                        let int = num as i32;
+                        let sign = if int < 0 { "-" } else { "" };
                         match digit_format {
                             Some(DigitFormat::Decimal) => {
-                                write!(&mut s, "{}", num.abs() as u32)?;
+                                write!(&mut s, "{}", int)?;
                             }
                             Some(DigitFormat::Hex) => {
-                                write!(&mut s, "{:x}", num.abs() as u32)?;
+                                write!(&mut s, "{sign}{:x}", int.unsigned_abs())?;
                             }
                             Some(DigitFormat::UppercaseHex) => {
-                                write!(&mut s, "{:X}", num.abs() as u32)?;
+                                write!(&mut s, "{sign}{:X}", int.unsigned_abs())?;
                             }
                             Some(DigitFormat::Octal) => {
-                                write!(&mut s, "{:o}", num.abs() as u32)?;
+                                write!(&mut s, "{sign}{:o}", int.unsigned_abs())?;
                             }
                             Some(DigitFormat::Float { precision }) => {
                                 write!(&mut s, "{:.*}", *precision as usize, num)?;
@@ -122,11 +227,12 @@ impl<'a> CustomColorFormat<'a> {
                             }
                         }
                     }
-                    Red255 | Green255 | Blue255 => {
+                    Red255 | Green255 | Blue255 | Alpha255 => {
                         let num = match symbol {
                             Red255 => rgb.r_scaled(),
                             Green255 => rgb.g_scaled(),
                             Blue255 => rgb.b_scaled(),
+                            Alpha255 => rgb.alpha_scaled().round(),
                             _ => unreachable!(),
                         } as u32;
 
@@ -175,6 +281,52 @@ impl FromExternalError<&str, ParseIntError> for ColorParseError<&str> {
     }
 }
 
+/// Describes why `field` failed to parse, `field` is the part of `text` right after the `{`.
+/// Returns the input following the color symbol and digit format at the start of `field` if
+/// nothing but the end of the input or the next `{` comes after them, where a `}` was expected.
+fn unclosed_field_end(field: &str) -> Option<&str> {
+    let (rest, _) = preceded(
+        space0::<_, ColorParseError<&str>>,
+        tuple((parse_color_symbol, opt(parse_digit_format), space0)),
+    )(field)
+    .ok()?;
+    (rest.is_empty() || rest.starts_with('{')).then_some(rest)
+}
+
+fn field_error(text: &str, field: &str, err: ColorParseError<&str>) -> FormatError {
+    let position = |rest: &str| text[..text.len() - rest.len()].chars().count();
+    let symbol = field.trim_start_matches([' ', '\t']);
+    let failed_at = match err {
+        ColorParseError::Nom(rest, _) => rest,
+        _ => symbol,
+    };
+
+    if failed_at.starts_with(':') {
+        FormatError {
+            position: position(failed_at),
+            message: "invalid digit format, expected one of :d, :x, :X, :o or :.<precision>".into(),
+        }
+    } else if failed_at.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+        let end = symbol
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(symbol.len());
+        FormatError {
+            position: position(symbol),
+            message: format!("unknown color symbol `{}`", &symbol[..end]),
+        }
+    } else if failed_at.len() == symbol.len() {
+        FormatError {
+            position: position(symbol),
+            message: "expected a color symbol".into(),
+        }
+    } else {
+        FormatError {
+            position: position(failed_at),
+            message: "expected `}`".into(),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum FormatToken<'a> {
     Color(ColorField),
@@ -209,10 +361,12 @@ enum ColorSymbol {
     Red,
     Green,
     Blue,
+    Alpha,
 
     Red255,
     Green255,
     Blue255,
+    Alpha255,
 
     Cyan,
     Magenta,
@@ -270,9 +424,11 @@ fn parse_rgb_symbol(i: &str) -> IResult<&str, ColorSymbol, ColorParseError<&str>
         tag("r255").map(|_| ColorSymbol::Red255),
         tag("g255").map(|_| ColorSymbol::Green255),
         tag("b255").map(|_| ColorSymbol::Blue255),
+        tag("a255").map(|_| ColorSymbol::Alpha255),
         char('r').map(|_| ColorSymbol::Red),
         char('g').map(|_| ColorSymbol::Green),
         char('b').map(|_| ColorSymbol::Blue),
+        char('a').map(|_| ColorSymbol::Alpha),
     ))(i)
 }
 
@@ -458,9 +614,12 @@ fn parse_color_format(i: &str) -> IResult<&str, CustomColorFormat, ColorParseErr
 
 #[cfg(test)]
 mod tests {
-    use crate::core::color::Rgb;
+    use crate::core::color::{Illuminant, Rgb, RgbWorkingSpace};
 
-    use super::{Color, ColorField, ColorSymbol, CustomColorFormat, DigitFormat, FormatToken};
+    use super::{
+        Color, ColorField, ColorSymbol, CustomColorFormat, CustomPaletteFormat, DigitFormat,
+        FormatToken,
+    };
     macro_rules! field {
         ($sym:tt) => {
             FormatToken::Color(ColorField {
@@ -492,9 +651,14 @@ mod tests {
     fn formats_custom_color_string() {
         macro_rules! test_case {
             ($fmt:literal => $want:literal, $color:expr) => {
+                test_case!($fmt => $want, $color, SRGB, D65);
+            };
+            ($fmt:literal => $want:literal, $color:expr, $ws:ident, $illuminant:ident) => {
                 let color_format = CustomColorFormat::parse($fmt).unwrap();
                 let color = $color;
-                let formatted = color_format.format_color(&color).unwrap();
+                let formatted = color_format
+                    .format_color(&color, RgbWorkingSpace::$ws, Illuminant::$illuminant)
+                    .unwrap();
                 assert_eq!(formatted, $want);
             };
         }
@@ -510,10 +674,66 @@ mod tests {
             "r:0o{r255:o} g:0x{g255:X} b:0x{b255:x}" => "r:0o177 g:0x7F b:0x7f",
             Color::Rgb(Rgb::new(0.5, 0.5, 0.5))
         );
+        test_case!(
+            "rgba({r255}, {g255}, {b255}, {a:.2})" => "rgba(120, 30, 80, 0.50)",
+            Color::Rgb(Rgb::new_scaled(120, 30, 80).with_alpha(0.5))
+        );
+        test_case!(
+            "{a} {a255} {a255:x}" => "1.0 255 ff",
+            Color::Rgb(Rgb::new_scaled(120, 30, 80))
+        );
+        test_case!(
+            "{a255}" => "128",
+            Color::Color32(eframe::egui::Color32::from_rgba_unmultiplied(120, 30, 80, 128))
+        );
         test_case!(
             "{hsv_h360:d} {hsv_s100:X} {hsv_v100:x}" => "326 4B 2f",
             Color::Rgb(Rgb::new_scaled(120, 30, 80))
         );
+        test_case!(
+            "{lab_l:.2} {lab_a:.2} {lab_b:.2}" => "53.24 80.09 67.20",
+            Color::Rgb(Rgb::new(1., 0., 0.))
+        );
+        test_case!(
+            "{lab_l:d} {lab_a:d} {lab_b:d}" => "32 79 -107",
+            Color::Rgb(Rgb::new(0., 0., 1.))
+        );
+        test_case!(
+            "{lab_a:x} {lab_b:x} {lab_b:X} {lab_b:o}" => "4f -6b -6B -153",
+            Color::Rgb(Rgb::new(0., 0., 1.))
+        );
+        test_case!(
+            "lch({lch_ab_l:.1} {lch_ab_c:.1} {lch_ab_h:.1})" => "lch(53.2 104.6 40.0)",
+            Color::Rgb(Rgb::new(1., 0., 0.))
+        );
+        test_case!(
+            "{luv_l:.1} {luv_u:.1} {luv_v:.1}" => "53.2 175.0 37.8",
+            Color::Rgb(Rgb::new(1., 0., 0.))
+        );
+        test_case!(
+            "{lch_uv_l:.2} {lch_uv_c:.2} {lch_uv_h:.2}" => "53.24 179.04 12.17",
+            Color::Rgb(Rgb::new(1., 0., 0.))
+        );
+        test_case!(
+            "{xyy_x:.4} {xyy_y:.4} {xyy_Y:.4}" => "0.6400 0.3300 0.2127",
+            Color::Rgb(Rgb::new(1., 0., 0.))
+        );
+        test_case!(
+            "{xyz_x:.4} {xyz_y:.4} {xyz_z:.4}" => "0.9505 1.0000 1.0888",
+            Color::Rgb(Rgb::new(1., 1., 1.))
+        );
+        test_case!(
+            "{xyz_x:.4} {xyz_y:.4} {xyz_z:.4}" => "0.9642 1.0000 0.8252",
+            Color::Rgb(Rgb::new(1., 1., 1.)),
+            SRGB,
+            D50
+        );
+        test_case!(
+            "{lab_l:.1} {lab_a:.1} {lab_b:.1}" => "54.3 80.8 69.9",
+            Color::Rgb(Rgb::new(1., 0., 0.)),
+            SRGB,
+            D50
+        );
     }
 
     #[test]
@@ -575,4 +795,43 @@ mod tests {
             .into()
         );
     }
+
+    #[test]
+    fn validates_color_format() {
+        macro_rules! test_case {
+            ($fmt:literal => [$(($pos:literal, $msg:literal)),*]) => {
+                let errors: Vec<_> = CustomColorFormat::validate($fmt)
+                    .into_iter()
+                    .map(|e| (e.position, e.message))
+                    .collect();
+                let want: Vec<(usize, String)> = vec![$(($pos, $msg.to_string())),*];
+                assert_eq!(errors, want, "{}", $fmt);
+            };
+        }
+
+        test_case!("rgb({r255}, {g255:x}, { b:.2 })" => []);
+        test_case!("{hsv_h} {{ {hsv_s} }} {hsv_v}" => []);
+        test_case!("{r" => [(2, "expected `}`")]);
+        test_case!("{hsl_h:.1 {r}" => [(10, "expected `}`")]);
+        test_case!("{ {r}" => []);
+        test_case!("{}" => [(1, "expected a color symbol")]);
+        test_case!("a {red} {  foo_1:x}" => [
+            (3, "unknown color symbol `red`"),
+            (11, "unknown color symbol `foo_1`")
+        ]);
+        test_case!("é{r:z}" => [
+            (3, "invalid digit format, expected one of :d, :x, :X, :o or :.<precision>")
+        ]);
+        test_case!("{g255 ;}" => [(6, "expected `}`")]);
+
+        let format = CustomPaletteFormat {
+            prefix: "[".into(),
+            entry_format: "{lab_q}, ".into(),
+            suffix: "]".into(),
+        };
+        assert_eq!(
+            format.validate()[0].to_string(),
+            "unknown color symbol `lab_q` at column 2"
+        );
+    }
 }
@@ -0,0 +1,226 @@
+//! Color harmonies computed by rotating the hue in Oklch.
+//!
+//! Unlike the HSV based [`Color::complementary`] and friends, rotating the Oklch hue keeps the
+//! perceived lightness and chroma of every member close to the base color. Members that fall out
+//! of the sRGB gamut have their chroma lowered.
+
+use serde::{Deserialize, Serialize};
+
+use super::{cvd::ColorVisionDeficiency, ramp::into_gamut, Color};
+
+/// Oklab distance below which two colors are considered hard to tell apart.
+pub const MIN_DISTINGUISHABLE: f32 = 0.04;
+
+/// Lightness offsets of the members of a monochromatic harmony.
+const MONOCHROMATIC_STEPS: [f32; 4] = [-0.24, -0.12, 0.12, 0.24];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ColorHarmony {
+    #[default]
+    Complementary,
+    Triadic,
+    Tetradic,
+    Analogous,
+    SplitComplementary,
+    Square,
+    Monochromatic,
+    Compound,
+}
+
+impl ColorHarmony {
+    pub fn all() -> [Self; 8] {
+        [
+            Self::Complementary,
+            Self::Triadic,
+            Self::Tetradic,
+            Self::Analogous,
+            Self::SplitComplementary,
+            Self::Square,
+            Self::Monochromatic,
+            Self::Compound,
+        ]
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Complementary => "Complementary",
+            Self::Triadic => "Triadic",
+            Self::Tetradic => "Tetradic",
+            Self::Analogous => "Analogous",
+            Self::SplitComplementary => "Split complementary",
+            Self::Square => "Square",
+            Self::Monochromatic => "Monochromatic",
+            Self::Compound => "Compound",
+        }
+    }
+
+    /// Whether the members of this harmony depend on an angle.
+    pub fn has_angle(self) -> bool {
+        matches!(
+            self,
+            Self::Analogous | Self::SplitComplementary | Self::Compound
+        )
+    }
+
+    /// Returns the angle in degrees this harmony is usually built with.
+    pub fn default_angle(self) -> f32 {
+        30.
+    }
+
+    /// Returns the base color followed by the other members of this harmony. `angle` is the hue
+    /// distance in degrees used by harmonies for which [`has_angle`](Self::has_angle) is true.
+    pub fn colors(self, base: &Color, angle: f32) -> Vec<Color> {
+        let oklch = base.oklch();
+        let alpha = base.alpha();
+        let member = |l: f32, hue_offset: f32| -> Color {
+            into_gamut(l, oklch.c(), (oklch.h() + hue_offset).rem_euclid(360.))
+                .with_alpha(alpha)
+                .into()
+        };
+        let rotated = |hue_offset: f32| member(oklch.l(), hue_offset);
+
+        let mut colors = vec![*base];
+        match self {
+            Self::Complementary => colors.push(rotated(180.)),
+            Self::Triadic => colors.extend([rotated(120.), rotated(240.)]),
+            Self::Tetradic => colors.extend([rotated(60.), rotated(180.), rotated(240.)]),
+            Self::Analogous => colors.extend([rotated(-angle), rotated(angle)]),
+            Self::SplitComplementary => {
+                colors.extend([rotated(180. - angle), rotated(180. + angle)])
+            }
+            Self::Square => colors.extend([rotated(90.), rotated(180.), rotated(270.)]),
+            Self::Monochromatic => colors.extend(
+                MONOCHROMATIC_STEPS
+                    .iter()
+                    .map(|offset| member((oklch.l() + offset).clamp(0.05, 0.98), 0.)),
+            ),
+            Self::Compound => colors.extend([rotated(angle), rotated(180. - angle), rotated(180.)]),
+        }
+        colors
+    }
+}
+
+/// Returns `color` with its Oklch hue rotated by `degrees`, keeping lightness, chroma and alpha.
+pub fn rotate_hue(color: &Color, degrees: f32) -> Color {
+    let oklch = color.oklch();
+    into_gamut(oklch.l(), oklch.c(), (oklch.h() + degrees).rem_euclid(360.))
+        .with_alpha(color.alpha())
+        .into()
+}
+
+/// A pair of colors that become hard to tell apart for people with a color vision deficiency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub deficiency: ColorVisionDeficiency,
+    pub first: usize,
+    pub second: usize,
+}
+
+/// Returns every pair of `colors` that collapses under one of the dichromacies. Pairs that are
+/// already indistinguishable with normal vision are not reported.
+///
+/// Achromatopsia is left out as only luminance differences help there, which hue based harmonies
+/// deliberately avoid.
+pub fn color_blind_conflicts(colors: &[Color]) -> Vec<Conflict> {
+    let distance = |a: &Color, b: &Color| {
+        let (a, b) = (a.oklab(), b.oklab());
+        ((a.l() - b.l()).powi(2) + (a.a() - b.a()).powi(2) + (a.b() - b.b()).powi(2)).sqrt()
+    };
+
+    let mut conflicts = vec![];
+    for deficiency in ColorVisionDeficiency::all() {
+        if deficiency == ColorVisionDeficiency::Achromatopsia {
+            continue;
+        }
+        let simulated: Vec<_> = colors.iter().map(|c| deficiency.simulate(c)).collect();
+        for first in 0..colors.len() {
+            for second in first + 1..colors.len() {
+                if distance(&colors[first], &colors[second]) >= MIN_DISTINGUISHABLE
+                    && distance(&simulated[first], &simulated[second]) < MIN_DISTINGUISHABLE
+                {
+                    conflicts.push(Conflict {
+                        deficiency,
+                        first,
+                        second,
+                    });
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hue_distance(a: f32, b: f32) -> f32 {
+        let d = (a - b).rem_euclid(360.);
+        d.min(360. - d)
+    }
+
+    #[test]
+    fn computes_harmonies() {
+        let base = Color::from_hex("3b82f6").unwrap();
+        let h = base.oklch().h();
+
+        macro_rules! test_case {
+            ($harmony:ident, $angle:literal => [$($offset:literal),*]) => {
+                let colors = ColorHarmony::$harmony.colors(&base, $angle);
+                let offsets = [$($offset as f32),*];
+                assert_eq!(colors.len(), offsets.len() + 1, "{}", stringify!($harmony));
+                assert_eq!(colors[0], base);
+                for (color, offset) in colors[1..].iter().zip(offsets) {
+                    let hue = color.oklch().h();
+                    assert!(
+                        hue_distance(hue, h + offset) < 1.,
+                        "{}: {hue} != {} + {offset}",
+                        stringify!($harmony),
+                        h
+                    );
+                }
+            };
+        }
+
+        test_case!(Complementary, 0. => [180]);
+        test_case!(Triadic, 0. => [120, 240]);
+        test_case!(Tetradic, 0. => [60, 180, 240]);
+        test_case!(Analogous, 20. => [-20, 20]);
+        test_case!(SplitComplementary, 40. => [140, 220]);
+        test_case!(Square, 0. => [90, 180, 270]);
+        test_case!(Compound, 30. => [30, 150, 180]);
+        test_case!(Monochromatic, 0. => [0, 0, 0, 0]);
+
+        // rotated members keep the perceived lightness of the base
+        for color in ColorHarmony::Triadic.colors(&base, 0.) {
+            assert!((color.oklch().l() - base.oklch().l()).abs() < 0.01);
+        }
+        let mono = ColorHarmony::Monochromatic.colors(&base, 0.);
+        assert!(mono[1].oklch().l() < mono[2].oklch().l());
+        assert!(mono[3].oklch().l() > base.oklch().l());
+    }
+
+    #[test]
+    fn rotates_hue() {
+        let base = Color::from_hex("3b82f6").unwrap().with_alpha(0.5);
+        let rotated = rotate_hue(&base, 360.);
+        assert_eq!(rotated.as_hex(), base.as_hex());
+        assert_eq!(rotated.alpha(), 0.5);
+    }
+
+    #[test]
+    fn finds_color_blind_conflicts() {
+        let red = Color::from_hex("ff0000").unwrap();
+        let green = Color::from_hex("a39000").unwrap();
+        let blue = Color::from_hex("1f77b4").unwrap();
+
+        let conflicts = color_blind_conflicts(&[red, green, blue]);
+        assert!(conflicts.iter().any(|conflict| conflict.first == 0
+            && conflict.second == 1
+            && conflict.deficiency != ColorVisionDeficiency::Tritanopia));
+        assert!(!conflicts
+            .iter()
+            .any(|conflict| conflict.first == 0 && conflict.second == 2));
+        assert!(color_blind_conflicts(&[Color::black(), Color::white()]).is_empty());
+    }
+}
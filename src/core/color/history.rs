@@ -0,0 +1,262 @@
+//! Persistent history of picked colors.
+//!
+//! The history is stored next to the saved [`Palettes`](super::palettes::Palettes), as a json file
+//! in the cache directory on desktop and in the `eframe` storage on the web.
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+use super::{palette::NamedPalette, Color, Palette};
+
+/// Number of colors kept in the history unless configured otherwise.
+pub const DEFAULT_HISTORY_LIMIT: usize = 20;
+
+/// The way a color ended up in the history.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum PickSource {
+    /// Picked from the screen with the zoom picker.
+    #[default]
+    Screen,
+    /// Adjusted with the color sliders.
+    Slider,
+    /// Picked from the image loaded in the image panel.
+    Image,
+}
+
+impl PickSource {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Screen => "Screen",
+            Self::Slider => "Slider",
+            Self::Image => "Image",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct HistoryEntry {
+    pub color: Color,
+    pub source: PickSource,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl HistoryEntry {
+    /// Returns the local time at which this entry was added formatted as `YYYY-MM-DD HH:MM:SS`.
+    pub fn display_time(&self) -> String {
+        chrono::DateTime::from_timestamp(self.timestamp as i64, 0)
+            .map(|time| {
+                time.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Picked colors ordered from the oldest to the newest.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ColorHistory {
+    entries: Vec<HistoryEntry>,
+}
+
+impl ColorHistory {
+    pub const STORAGE_KEY: &'static str = "dev-tools-rs.saved.history";
+    pub const FILE_NAME: &'static str = "history.json";
+
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn last(&self) -> Option<&HistoryEntry> {
+        self.entries.last()
+    }
+
+    /// Adds `color` picked now as the newest entry and drops the oldest entries above `limit`.
+    /// Returns `false` if the color is the same as the newest entry and nothing was added.
+    pub fn push(&mut self, color: Color, source: PickSource, limit: usize) -> bool {
+        self.push_entry(
+            HistoryEntry {
+                color,
+                source,
+                timestamp: crate::get_timestamp(),
+            },
+            limit,
+        )
+    }
+
+    pub fn push_entry(&mut self, entry: HistoryEntry, limit: usize) -> bool {
+        if self.last().map(|last| last.color) == Some(entry.color) {
+            return false;
+        }
+        self.entries.push(entry);
+        self.truncate(limit);
+        true
+    }
+
+    /// Keeps only the newest `limit` entries.
+    pub fn truncate(&mut self, limit: usize) {
+        let len = self.entries.len();
+        if len > limit {
+            self.entries.drain(..len - limit);
+        }
+    }
+
+    pub fn remove(&mut self, idx: usize) -> Option<HistoryEntry> {
+        if idx < self.entries.len() {
+            Some(self.entries.remove(idx))
+        } else {
+            None
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Creates a palette out of the entries at `indices` in the order they are given. Indices out
+    /// of bounds are skipped and so are colors that appear more than once.
+    pub fn as_palette(
+        &self,
+        indices: impl IntoIterator<Item = usize>,
+        name: impl Into<String>,
+    ) -> NamedPalette {
+        let mut palette = Palette::default();
+        for entry in indices.into_iter().filter_map(|idx| self.entries.get(idx)) {
+            palette.add(entry.color);
+        }
+        NamedPalette {
+            name: name.into(),
+            palette,
+        }
+    }
+
+    /// Loads the history from the specified file located at `path`. The file is expected to be a
+    /// valid json file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let data = fs::read(path).context("failed to read color history file")?;
+        serde_json::from_slice(&data).context("failed to deserialize color history file")
+    }
+
+    /// Loads the history from `path` or returns an empty history if the file doesn't exist.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::load(path)
+    }
+
+    pub fn load_from_storage(storage: &dyn eframe::Storage) -> Result<Self> {
+        if let Some(json) = storage.get_string(Self::STORAGE_KEY) {
+            Self::from_json_str(&json)
+        } else {
+            Err(anyhow!("color history not found in storage"))
+        }
+    }
+
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("failed to deserialize color history from json")
+    }
+
+    pub fn as_json_str(&self) -> Result<String> {
+        serde_json::to_string(&self).context("failed to serialize color history as json")
+    }
+
+    /// Saves this history as json file in the provided `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            if !parent.exists() {
+                fs::create_dir_all(parent)
+                    .context("failed to create parent directory for color history file")?;
+            }
+        }
+
+        let mut data = Vec::with_capacity(128);
+        serde_json::to_writer(&mut data, &self).context("failed to serialize color history")?;
+        fs::write(path, &data).context("failed to write color history to a file")
+    }
+
+    pub fn save_to_storage(&self, storage: &mut dyn eframe::Storage) -> Result<()> {
+        self.as_json_str().map(|json| {
+            storage.set_string(Self::STORAGE_KEY, json);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(hex: &str, timestamp: u64) -> HistoryEntry {
+        HistoryEntry {
+            color: Color::from_hex(hex).unwrap(),
+            source: PickSource::Screen,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn limits_history() {
+        let mut history = ColorHistory::default();
+        assert!(history.push_entry(entry("ff0000", 1), 3));
+        assert!(!history.push_entry(entry("ff0000", 2), 3));
+        assert!(history.push_entry(entry("00ff00", 3), 3));
+        assert!(history.push_entry(entry("0000ff", 4), 3));
+        assert!(history.push_entry(entry("ffffff", 5), 3));
+        assert_eq!(
+            history
+                .entries()
+                .iter()
+                .map(|entry| entry.timestamp)
+                .collect::<Vec<_>>(),
+            [3, 4, 5]
+        );
+
+        history.truncate(1);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history.last().unwrap().color.as_hex(), "#ffffff");
+
+        assert!(history.push(Color::black(), PickSource::Slider, 5));
+        assert_eq!(history.last().unwrap().source, PickSource::Slider);
+        assert!(history.last().unwrap().timestamp > 0);
+    }
+
+    #[test]
+    fn creates_palette_from_selection() {
+        let mut history = ColorHistory::default();
+        for (i, hex) in ["ff0000", "00ff00", "0000ff"].into_iter().enumerate() {
+            history.push_entry(entry(hex, i as u64), DEFAULT_HISTORY_LIMIT);
+        }
+
+        let palette = history.as_palette([2, 0, 2, 7], "picked");
+        assert_eq!(palette.name, "picked");
+        let colors: Vec<_> = palette.palette.iter().map(Color::as_hex).collect();
+        assert_eq!(colors, ["#0000ff", "#ff0000"]);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut history = ColorHistory::default();
+        history.push_entry(entry("3b82f6", 1_700_000_000), DEFAULT_HISTORY_LIMIT);
+        let json = history.as_json_str().unwrap();
+        assert_eq!(ColorHistory::from_json_str(&json).unwrap(), history);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(ColorHistory::FILE_NAME);
+        assert!(ColorHistory::load_or_default(&path).unwrap().is_empty());
+        history.save(&path).unwrap();
+        assert_eq!(ColorHistory::load(&path).unwrap(), history);
+    }
+}
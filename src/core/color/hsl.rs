@@ -10,6 +10,8 @@ pub struct Hsl {
     h: f32,
     s: f32,
     l: f32,
+    #[serde(default = "super::opaque")]
+    a: f32,
 }
 
 impl Hsl {
@@ -22,9 +24,16 @@ impl Hsl {
             h: hue,
             s: saturation,
             l: light,
+            a: 1.,
         }
     }
 
+    /// Returns this color with Alpha set to `alpha` in the range 0.0 ..= 1.0
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.a = super::clamp_alpha(alpha);
+        self
+    }
+
     #[inline(always)]
     /// Returns Hue in the range of 0.0 ..= 1.0
     pub fn h(&self) -> f32 {
@@ -43,6 +52,12 @@ impl Hsl {
         self.l
     }
 
+    #[inline(always)]
+    /// Returns Alpha in the range of 0.0 ..= 1.0
+    pub fn alpha(&self) -> f32 {
+        self.a
+    }
+
     /// Returns Hue in the range of 0.0 ..= 360.0
     pub fn h_scaled(&self) -> f32 {
         self.h * 360.
@@ -107,6 +122,10 @@ impl From<Color> for Hsl {
             Color::Hsv(c) => c.into(),
             Color::Hsl(c) => c,
             Color::Color32(c) => Rgb::from(c).into(),
+            Color::Lab(c) => Rgb::from(c).into(),
+            Color::Lch(c) => Rgb::from(c).into(),
+            Color::Oklab(c) => Rgb::from(c).into(),
+            Color::Oklch(c) => Rgb::from(c).into(),
         }
     }
 }
@@ -139,7 +158,7 @@ impl From<Hsv> for Hsl {
         }
         l /= 2.;
 
-        Hsl::new(h, ss, l)
+        Hsl::new(h, ss, l).with_alpha(color.alpha())
     }
 }
 
@@ -13,6 +13,8 @@ pub struct Hsv {
     h: f32,
     s: f32,
     v: f32,
+    #[serde(default = "super::opaque")]
+    a: f32,
 }
 
 impl Hsv {
@@ -25,9 +27,16 @@ impl Hsv {
             h: hue,
             s: saturation,
             v: value,
+            a: 1.,
         }
     }
 
+    /// Returns this color with Alpha set to `alpha` in the range 0.0 ..= 1.0
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.a = super::clamp_alpha(alpha);
+        self
+    }
+
     #[inline(always)]
     /// Returns Hue in the range of 0.0 ..= 1.0
     pub fn h(&self) -> f32 {
@@ -46,6 +55,12 @@ impl Hsv {
         self.v
     }
 
+    #[inline(always)]
+    /// Returns Alpha in the range of 0.0 ..= 1.0
+    pub fn alpha(&self) -> f32 {
+        self.a
+    }
+
     /// Returns Hue in the range of 0.0 ..= 360.0
     pub fn h_scaled(&self) -> f32 {
         self.h * 360.
@@ -90,14 +105,14 @@ impl From<Hsv> for Hsva {
             h: hsv.h(),
             s: hsv.s(),
             v: hsv.v(),
-            a: 1.,
+            a: hsv.alpha(),
         }
     }
 }
 
 impl From<Hsva> for Hsv {
     fn from(hsv: Hsva) -> Self {
-        Self::new(hsv.h, hsv.s, hsv.v)
+        Self::new(hsv.h, hsv.s, hsv.v).with_alpha(hsv.a)
     }
 }
 
@@ -123,6 +138,10 @@ impl From<Color> for Hsv {
             Color::Hsv(c) => c,
             Color::Hsl(c) => c.into(),
             Color::Color32(c) => Rgb::from(c).into(),
+            Color::Lab(c) => Rgb::from(c).into(),
+            Color::Lch(c) => Rgb::from(c).into(),
+            Color::Oklab(c) => Rgb::from(c).into(),
+            Color::Oklch(c) => Rgb::from(c).into(),
         }
     }
 }
@@ -154,7 +173,7 @@ impl From<Hsl> for Hsv {
         let v = (l + ss) / 2.;
         let s = (2. * ss) / (l + ss);
 
-        Hsv::new(h, s, v)
+        Hsv::new(h, s, v).with_alpha(color.alpha())
     }
 }
 
@@ -182,7 +201,7 @@ impl From<Rgb> for Hsv {
         let v = max;
         let s = if v == 0. { 0. } else { 1. - min / max };
 
-        Hsv::new(h, s, v)
+        Hsv::new(h, s, v).with_alpha(rgb.alpha())
     }
 }
 
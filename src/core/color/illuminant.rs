@@ -0,0 +1,112 @@
+use super::Xyz;
+use crate::utils::math::{Matrix1x3, Matrix3};
+
+use serde::{Deserialize, Serialize};
+
+/// Bradford cone response matrix used for chromatic adaptation between white points.
+const BRADFORD: Matrix3 = Matrix3([
+    [0.8951, 0.2664, -0.1614],
+    [-0.7502, 1.7135, 0.0367],
+    [0.0389, -0.0685, 1.0296],
+]);
+
+const BRADFORD_INVERSE: Matrix3 = Matrix3([
+    [0.9869929, -0.1470543, 0.1599627],
+    [0.4323053, 0.5183603, 0.0492912],
+    [-0.0085287, 0.0400428, 0.9684867],
+]);
+
+/// CIE standard illuminants (2° observer) used as the reference white of CIE color spaces.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Illuminant {
+    A,
+    B,
+    C,
+    D50,
+    D55,
+    #[default]
+    D65,
+    D75,
+    E,
+    F2,
+    F7,
+    F11,
+}
+
+impl Illuminant {
+    pub fn variants() -> &'static [Illuminant] {
+        use Illuminant::*;
+        &[A, B, C, D50, D55, D65, D75, E, F2, F7, F11]
+    }
+
+    /// Returns the reference white of this illuminant with Y normalized to 1.0
+    pub fn xyz(&self) -> Xyz {
+        use Illuminant::*;
+        match self {
+            A => Xyz::new(1.09850, 1., 0.35585),
+            B => Xyz::new(0.99072, 1., 0.85223),
+            C => Xyz::new(0.98074, 1., 1.18232),
+            D50 => Xyz::new(0.96422, 1., 0.82521),
+            D55 => Xyz::new(0.95682, 1., 0.92149),
+            D65 => Xyz::new(0.95047, 1., 1.08883),
+            D75 => Xyz::new(0.94972, 1., 1.22638),
+            E => Xyz::new(1., 1., 1.),
+            F2 => Xyz::new(0.99186, 1., 0.67393),
+            F7 => Xyz::new(0.95041, 1., 1.08747),
+            F11 => Xyz::new(1.00962, 1., 0.64350),
+        }
+    }
+
+    /// Returns a matrix that adapts XYZ values relative to this illuminant so that they are
+    /// relative to the `target` illuminant, using the Bradford method.
+    pub fn adaptation_matrix(&self, target: Illuminant) -> Matrix3 {
+        let source = BRADFORD * Matrix1x3::from(self.xyz());
+        let destination = BRADFORD * Matrix1x3::from(target.xyz());
+        let scale = Matrix3([
+            [destination[0] / source[0], 0., 0.],
+            [0., destination[1] / source[1], 0.],
+            [0., 0., destination[2] / source[2]],
+        ]);
+
+        BRADFORD_INVERSE * scale * BRADFORD
+    }
+}
+
+impl AsRef<str> for Illuminant {
+    fn as_ref(&self) -> &str {
+        use Illuminant::*;
+        match self {
+            A => "A (Incandescent)",
+            B => "B (Direct sunlight)",
+            C => "C (Average daylight)",
+            D50 => "D50 (Horizon light)",
+            D55 => "D55 (Mid-morning daylight)",
+            D65 => "D65 (Noon daylight)",
+            D75 => "D75 (North sky daylight)",
+            E => "E (Equal energy)",
+            F2 => "F2 (Cool white fluorescent)",
+            F7 => "F7 (Daylight fluorescent)",
+            F11 => "F11 (Narrow band fluorescent)",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Illuminant;
+    use crate::utils::math::Matrix1x3;
+
+    #[test]
+    fn adapts_white_points() {
+        for source in Illuminant::variants() {
+            for target in Illuminant::variants() {
+                let white = Matrix1x3::from(source.xyz());
+                let got = source.adaptation_matrix(*target) * white;
+                let want = target.xyz();
+                assert!((got[0] - want.x()).abs() < 1e-4, "{source:?} -> {target:?}");
+                assert!((got[1] - want.y()).abs() < 1e-4, "{source:?} -> {target:?}");
+                assert!((got[2] - want.z()).abs() < 1e-4, "{source:?} -> {target:?}");
+            }
+        }
+    }
+}
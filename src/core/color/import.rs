@@ -0,0 +1,637 @@
+//! Parsers for palette files created by other applications.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{fs, path::Path};
+
+use super::{
+    palette::{NamedPalette, Palette, PaletteEntry},
+    Cmyk, Color, Hsv, Lab, Rgb, U8_MAX,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteFileFormat {
+    /// GIMP `.gpl` palette
+    Gimp,
+    /// Adobe Swatch Exchange `.ase`
+    Ase,
+    /// Adobe Photoshop color swatches `.aco`
+    Aco,
+    /// Paint.NET `.txt` palette, one `AARRGGBB` color per line. Lines starting with `#` are read
+    /// as CSS hex colors so hex lists can be imported too.
+    PaintNet,
+    /// Sketch `.sketchpalette`
+    Sketch,
+    /// CSS custom properties or SCSS/Less variables with color values
+    CssVariables,
+}
+
+impl PaletteFileFormat {
+    pub fn all() -> [Self; 6] {
+        [
+            Self::Gimp,
+            Self::Ase,
+            Self::Aco,
+            Self::PaintNet,
+            Self::Sketch,
+            Self::CssVariables,
+        ]
+    }
+
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Self::Gimp => &["gpl"],
+            Self::Ase => &["ase"],
+            Self::Aco => &["aco"],
+            Self::PaintNet => &["txt"],
+            Self::Sketch => &["sketchpalette"],
+            Self::CssVariables => &["css", "scss", "sass", "less"],
+        }
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        Self::all()
+            .into_iter()
+            .find(|format| format.extensions().contains(&ext.as_str()))
+    }
+
+    /// Parses palette file contents `data`. `name` is used for palettes that have no name stored
+    /// in the file. Adobe swatch exchange files yield one palette per color group.
+    pub fn parse(self, data: &[u8], name: &str) -> Result<Vec<NamedPalette>> {
+        let text = || std::str::from_utf8(data).context("palette file is not valid UTF-8");
+        match self {
+            Self::Gimp => parse_gimp(text()?, name).map(|p| vec![p]),
+            Self::Ase => parse_ase(data, name),
+            Self::Aco => parse_aco(data, name).map(|p| vec![p]),
+            Self::PaintNet => parse_paint_net(text()?, name).map(|p| vec![p]),
+            Self::Sketch => parse_sketch(text()?, name).map(|p| vec![p]),
+            Self::CssVariables => parse_css_variables(text()?, name).map(|p| vec![p]),
+        }
+    }
+}
+
+/// Reads the palette file at `path`, the format is guessed from the file extension.
+pub fn import_palettes(path: impl AsRef<Path>) -> Result<Vec<NamedPalette>> {
+    let path = path.as_ref();
+    let format = PaletteFileFormat::from_path(path)
+        .ok_or_else(|| anyhow!("unsupported palette file `{}`", path.display()))?;
+    let data = fs::read(path).context("failed to read palette file")?;
+    let name = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("palette");
+    format.parse(&data, name)
+}
+
+fn named_palette(
+    name: impl Into<String>,
+    colors: impl IntoIterator<Item = impl Into<PaletteEntry>>,
+) -> NamedPalette {
+    let mut palette = Palette::default();
+    for entry in colors {
+        let entry = entry.into();
+        palette.add_named(entry.color, entry.name);
+    }
+    NamedPalette {
+        name: name.into(),
+        palette,
+    }
+}
+
+/// Returns `color` with the name read from a palette file, blank names are dropped.
+fn named_color(color: Color, name: &str) -> PaletteEntry {
+    let name = name.trim();
+    PaletteEntry {
+        color,
+        name: (!name.is_empty()).then(|| name.to_string()),
+    }
+}
+
+//####################################################################################################
+
+pub fn parse_gimp(text: &str, name: &str) -> Result<NamedPalette> {
+    let mut lines = text.lines().map(str::trim);
+    ensure!(
+        lines.next() == Some("GIMP Palette"),
+        "missing `GIMP Palette` header"
+    );
+
+    let mut palette_name = name.to_string();
+    let mut colors = vec![];
+    for (i, line) in lines.enumerate() {
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Columns:") {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix("Name:") {
+            let name = name.trim();
+            palette_name = name.strip_suffix(".gpl").unwrap_or(name).to_string();
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let channels = fields
+            .by_ref()
+            .take(3)
+            .map(str::parse::<u8>)
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("invalid color on line {}", i + 2))?;
+        let [r, g, b] = channels[..] else {
+            bail!("invalid color on line {}", i + 2);
+        };
+        let color_name = fields.collect::<Vec<_>>().join(" ");
+        colors.push(named_color(Rgb::new_scaled(r, g, b).into(), &color_name));
+    }
+
+    Ok(named_palette(palette_name, colors))
+}
+
+pub fn parse_paint_net(text: &str, name: &str) -> Result<NamedPalette> {
+    let mut colors = vec![];
+    for (i, line) in text.lines().map(str::trim).enumerate() {
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        let color = if line.starts_with('#') {
+            Color::from_css(line)?
+        } else {
+            ensure!(line.len() == 8, "invalid color on line {}", i + 1);
+            let argb = u32::from_str_radix(line, 16)
+                .with_context(|| format!("invalid color on line {}", i + 1))?;
+            let [a, r, g, b] = argb.to_be_bytes();
+            Rgb::new_scaled(r, g, b)
+                .with_alpha(a as f32 / U8_MAX)
+                .into()
+        };
+        colors.push(color);
+    }
+
+    Ok(named_palette(name, colors))
+}
+
+static CSS_VARIABLE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)(?:--|\$|@)([\w-]+)\s*:\s*([^;{}]+?)\s*(?:!default\s*)?;").unwrap()
+});
+
+/// Collects every `--name: <color>;`, `$name: <color>;` or `@name: <color>;` declaration, the
+/// colors are named after the variables. Values which are not plain colors, like references to
+/// other variables, are skipped.
+pub fn parse_css_variables(text: &str, name: &str) -> Result<NamedPalette> {
+    let colors: Vec<_> = CSS_VARIABLE
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let color = Color::from_css(&captures[2]).ok()?;
+            Some(named_color(color, &captures[1]))
+        })
+        .collect();
+    ensure!(!colors.is_empty(), "no color variables found");
+
+    Ok(named_palette(name, colors))
+}
+
+pub fn parse_sketch(text: &str, name: &str) -> Result<NamedPalette> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum SketchColor {
+        Components {
+            red: f32,
+            green: f32,
+            blue: f32,
+            #[serde(default = "super::opaque")]
+            alpha: f32,
+        },
+        Css(String),
+    }
+
+    #[derive(serde::Deserialize)]
+    struct SketchPalette {
+        colors: Vec<SketchColor>,
+    }
+
+    let palette: SketchPalette =
+        serde_json::from_str(text).context("failed to deserialize sketch palette")?;
+    let colors = palette
+        .colors
+        .into_iter()
+        .map(|color| match color {
+            SketchColor::Components {
+                red,
+                green,
+                blue,
+                alpha,
+            } => Ok(Rgb::new(red, green, blue).with_alpha(alpha).into()),
+            SketchColor::Css(text) => Color::from_css(&text),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(named_palette(name, colors))
+}
+
+//####################################################################################################
+
+/// Big endian reader over the contents of a binary swatch file.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn bytes(&mut self, n: usize) -> Result<&'a [u8]> {
+        ensure!(self.data.len() >= n, "unexpected end of palette file");
+        let (bytes, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        self.bytes(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        self.bytes(4)
+            .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        self.u32().map(f32::from_bits)
+    }
+
+    /// Reads `len` UTF-16 code units, dropping the null terminator.
+    fn utf16(&mut self, len: usize) -> Result<String> {
+        let units = (0..len).map(|_| self.u16()).collect::<Result<Vec<_>>>()?;
+        let units = units.strip_suffix(&[0]).unwrap_or(&units);
+        Ok(String::from_utf16_lossy(units))
+    }
+}
+
+const ASE_SIGNATURE: &[u8] = b"ASEF";
+const ASE_GROUP_START: u16 = 0xc001;
+const ASE_GROUP_END: u16 = 0xc002;
+const ASE_COLOR: u16 = 0x0001;
+
+pub fn parse_ase(data: &[u8], name: &str) -> Result<Vec<NamedPalette>> {
+    let mut reader = Reader { data };
+    ensure!(
+        reader.bytes(4)? == ASE_SIGNATURE,
+        "missing ASEF signature, not an Adobe swatch exchange file"
+    );
+    let _version = (reader.u16()?, reader.u16()?);
+    let blocks = reader.u32()?;
+
+    let mut ungrouped = NamedPalette {
+        name: name.to_string(),
+        palette: Palette::default(),
+    };
+    let mut groups: Vec<NamedPalette> = vec![];
+    let mut in_group = false;
+
+    for _ in 0..blocks {
+        let kind = reader.u16()?;
+        let len = reader.u32()? as usize;
+        let mut block = Reader {
+            data: reader.bytes(len)?,
+        };
+
+        match kind {
+            ASE_GROUP_START => {
+                let name_len = block.u16()? as usize;
+                groups.push(NamedPalette {
+                    name: block.utf16(name_len)?,
+                    palette: Palette::default(),
+                });
+                in_group = true;
+            }
+            ASE_GROUP_END => in_group = false,
+            ASE_COLOR => {
+                let name_len = block.u16()? as usize;
+                let color_name = block.utf16(name_len)?;
+                let model = block.bytes(4)?;
+                let color: Color = match model {
+                    b"RGB " => Rgb::new(block.f32()?, block.f32()?, block.f32()?).into(),
+                    b"CMYK" => {
+                        Cmyk::new(block.f32()?, block.f32()?, block.f32()?, block.f32()?).into()
+                    }
+                    b"LAB " => Lab::new(block.f32()? * 100., block.f32()?, block.f32()?).into(),
+                    b"Gray" => {
+                        let v = block.f32()?;
+                        Rgb::new(v, v, v).into()
+                    }
+                    _ => bail!(
+                        "unsupported color model `{}`",
+                        String::from_utf8_lossy(model)
+                    ),
+                };
+
+                let PaletteEntry { color, name } = named_color(color, &color_name);
+                match groups.last_mut() {
+                    Some(group) if in_group => group.palette.add_named(color, name),
+                    _ => ungrouped.palette.add_named(color, name),
+                };
+            }
+            _ => {}
+        }
+    }
+
+    if !ungrouped.palette.is_empty() || groups.is_empty() {
+        groups.insert(0, ungrouped);
+    }
+    Ok(groups)
+}
+
+pub fn parse_aco(data: &[u8], name: &str) -> Result<NamedPalette> {
+    fn read_color(reader: &mut Reader) -> Result<Option<Color>> {
+        let space = reader.u16()?;
+        let [w, x, y, z] = [reader.u16()?, reader.u16()?, reader.u16()?, reader.u16()?];
+        let unit = |v: u16| v as f32 / u16::MAX as f32;
+
+        Ok(match space {
+            0 => Some(Rgb::new(unit(w), unit(x), unit(y)).into()),
+            1 => Some(Hsv::new(unit(w), unit(x), unit(y)).into()),
+            // CMYK values are stored inverted, 0 being full ink coverage
+            2 => Some(Cmyk::new(1. - unit(w), 1. - unit(x), 1. - unit(y), 1. - unit(z)).into()),
+            7 => Some(
+                Lab::new(
+                    w as f32 / 100.,
+                    x as i16 as f32 / 100.,
+                    y as i16 as f32 / 100.,
+                )
+                .into(),
+            ),
+            // grayscale is stored as ink coverage in the range of 0 ..= 10000
+            8 => {
+                let v = 1. - (w as f32 / 10000.).clamp(0., 1.);
+                Some(Rgb::new(v, v, v).into())
+            }
+            _ => None,
+        })
+    }
+
+    let mut reader = Reader { data };
+    let mut colors = vec![];
+    while !reader.is_empty() {
+        let version = reader.u16()?;
+        ensure!(
+            version == 1 || version == 2,
+            "unsupported ACO version {version}"
+        );
+        let count = reader.u16()?;
+
+        // version 2 repeats the colors of version 1 followed by their names
+        colors.clear();
+        for _ in 0..count {
+            let color = read_color(&mut reader)?;
+            let color_name = if version == 2 {
+                let name_len = reader.u32()? as usize;
+                reader.utf16(name_len)?
+            } else {
+                String::new()
+            };
+            if let Some(color) = color {
+                colors.push(named_color(color, &color_name));
+            }
+        }
+    }
+
+    Ok(named_palette(name, colors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_palette() -> NamedPalette {
+        named_palette(
+            "sample",
+            [
+                named_color(Rgb::new_scaled(0, 0, 0).into(), "black"),
+                named_color(Rgb::new_scaled(255, 0, 0).into(), "red"),
+                named_color(Rgb::new_scaled(120, 30, 80).into(), "plum"),
+                named_color(Rgb::new_scaled(255, 255, 255).into(), "white"),
+            ],
+        )
+    }
+
+    fn hex_list(palette: &NamedPalette) -> String {
+        palette.palette.as_hex_list()
+    }
+
+    fn color_names(palette: &NamedPalette) -> Vec<Option<&str>> {
+        palette
+            .palette
+            .entries()
+            .map(|entry| entry.name.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_gimp_palette() {
+        let palette = sample_palette();
+        let gpl = palette.palette.as_gimp_palette(&palette.name);
+        let imported = parse_gimp(&gpl, "fallback").unwrap();
+        assert_eq!(imported.name, "sample");
+        assert_eq!(hex_list(&imported), hex_list(&palette));
+        assert_eq!(color_names(&imported), color_names(&palette));
+
+        let gpl = "GIMP Palette\nColumns: 4\n# comment\n  0   0 255\tNavy  Blue\n255 255 0\n";
+        let imported = parse_gimp(gpl, "fallback").unwrap();
+        assert_eq!(imported.name, "fallback");
+        assert_eq!(hex_list(&imported), "#0000ff\n#ffff00\n");
+        assert_eq!(color_names(&imported), [Some("Navy Blue"), None]);
+
+        assert!(parse_gimp("0 0 0\n", "x").is_err());
+        assert!(parse_gimp("GIMP Palette\n0 0\n", "x").is_err());
+    }
+
+    #[test]
+    fn round_trips_hex_list() {
+        let mut palette = sample_palette();
+        palette
+            .palette
+            .add(Rgb::new_scaled(1, 2, 3).with_alpha(0.5).into());
+        let imported = parse_paint_net(&palette.palette.as_hex_list(), "sample").unwrap();
+        assert_eq!(hex_list(&imported), hex_list(&palette));
+    }
+
+    #[test]
+    fn round_trips_exported_formats() {
+        let palette = sample_palette();
+
+        let ase = parse_ase(&palette.as_ase(), "fallback").unwrap();
+        assert_eq!(ase.len(), 1);
+        assert_eq!(ase[0].name, "sample");
+        assert_eq!(hex_list(&ase[0]), hex_list(&palette));
+        assert_eq!(color_names(&ase[0]), color_names(&palette));
+
+        for exported in [palette.as_css_variables(), palette.as_scss_variables()] {
+            let imported = parse_css_variables(&exported, "sample").unwrap();
+            assert_eq!(hex_list(&imported), hex_list(&palette));
+            assert_eq!(color_names(&imported), color_names(&palette));
+        }
+    }
+
+    #[test]
+    fn parses_paint_net_palette() {
+        let txt = "; paint.net Palette File\n;\nFFFF0000\n80781E50\n";
+        let imported = parse_paint_net(txt, "pdn").unwrap();
+        assert_eq!(hex_list(&imported), "#ff0000\n#781e5080\n");
+        assert!(parse_paint_net("FFF\n", "pdn").is_err());
+    }
+
+    #[test]
+    fn parses_css_variables() {
+        let css = r#"
+:root {
+  --brand: #ff0000;
+  --brand-dark: rgb(120 30 80);
+  --spacing: 4px;
+  --link: var(--brand);
+}
+$accent: hsl(120, 100%, 50%) !default;
+@muted: gray;
+"#;
+        let imported = parse_css_variables(css, "theme").unwrap();
+        assert_eq!(imported.name, "theme");
+        assert_eq!(hex_list(&imported), "#ff0000\n#781e50\n#00ff00\n#808080\n");
+        assert_eq!(
+            color_names(&imported),
+            [
+                Some("brand"),
+                Some("brand-dark"),
+                Some("accent"),
+                Some("muted")
+            ]
+        );
+        assert!(parse_css_variables("body { color: red; }", "x").is_err());
+    }
+
+    #[test]
+    fn parses_sketch_palette() {
+        let json = r##"{
+  "compatibleVersion": "2.0",
+  "pluginVersion": "2.22",
+  "colors": [
+    { "red": 1, "green": 0, "blue": 0, "alpha": 1 },
+    { "red": 0, "green": 0, "blue": 1, "alpha": 0.5 },
+    "#00ff00"
+  ]
+}"##;
+        let imported = parse_sketch(json, "sketch").unwrap();
+        assert_eq!(hex_list(&imported), "#ff0000\n#0000ff80\n#00ff00\n");
+    }
+
+    fn utf16_name(out: &mut Vec<u8>, name: &str) {
+        let units: Vec<u16> = name.encode_utf16().chain([0]).collect();
+        out.extend((units.len() as u16).to_be_bytes());
+        for unit in units {
+            out.extend(unit.to_be_bytes());
+        }
+    }
+
+    fn ase_block(out: &mut Vec<u8>, kind: u16, body: Vec<u8>) {
+        out.extend(kind.to_be_bytes());
+        out.extend((body.len() as u32).to_be_bytes());
+        out.extend(body);
+    }
+
+    fn ase_color(name: &str, model: &[u8; 4], values: &[f32]) -> Vec<u8> {
+        let mut body = vec![];
+        utf16_name(&mut body, name);
+        body.extend(model);
+        for v in values {
+            body.extend(v.to_be_bytes());
+        }
+        body.extend(2u16.to_be_bytes());
+        body
+    }
+
+    #[test]
+    fn parses_ase() {
+        let mut data = b"ASEF".to_vec();
+        data.extend(1u16.to_be_bytes());
+        data.extend(0u16.to_be_bytes());
+        data.extend(5u32.to_be_bytes());
+        ase_block(
+            &mut data,
+            ASE_COLOR,
+            ase_color("red", b"RGB ", &[1., 0., 0.]),
+        );
+        let mut group = vec![];
+        utf16_name(&mut group, "Brand");
+        ase_block(&mut data, ASE_GROUP_START, group);
+        ase_block(
+            &mut data,
+            ASE_COLOR,
+            ase_color("ink", b"CMYK", &[0., 0., 0., 1.]),
+        );
+        ase_block(&mut data, ASE_COLOR, ase_color("gray", b"Gray", &[0.5]));
+        ase_block(&mut data, ASE_GROUP_END, vec![]);
+
+        let imported = parse_ase(&data, "swatches").unwrap();
+        assert_eq!(imported.len(), 2);
+        assert_eq!(imported[0].name, "swatches");
+        assert_eq!(hex_list(&imported[0]), "#ff0000\n");
+        assert_eq!(color_names(&imported[0]), [Some("red")]);
+        assert_eq!(imported[1].name, "Brand");
+        assert_eq!(hex_list(&imported[1]), "#000000\n#808080\n");
+        assert_eq!(color_names(&imported[1]), [Some("ink"), Some("gray")]);
+
+        assert!(parse_ase(b"ASEF\0\x01\0\0\0\0\0\x01", "x").is_err());
+        assert!(parse_ase(b"GIMP", "x").is_err());
+    }
+
+    #[test]
+    fn parses_aco() {
+        fn color(out: &mut Vec<u8>, space: u16, values: [u16; 4]) {
+            out.extend(space.to_be_bytes());
+            for v in values {
+                out.extend(v.to_be_bytes());
+            }
+        }
+
+        let colors = [
+            (0, [0xffff, 0, 0, 0]),
+            (2, [0xffff, 0xffff, 0xffff, 0]),
+            (8, [5000, 0, 0, 0]),
+        ];
+        let mut data = vec![];
+        data.extend(1u16.to_be_bytes());
+        data.extend(3u16.to_be_bytes());
+        for (space, values) in colors {
+            color(&mut data, space, values);
+        }
+        let v1 = parse_aco(&data, "swatches").unwrap();
+        assert_eq!(hex_list(&v1), "#ff0000\n#000000\n#808080\n");
+        assert_eq!(color_names(&v1), [None, None, None]);
+
+        data.extend(2u16.to_be_bytes());
+        data.extend(3u16.to_be_bytes());
+        for ((space, values), name) in colors.into_iter().zip([b'r', b'k', b'g']) {
+            color(&mut data, space, values);
+            data.extend(2u32.to_be_bytes());
+            data.extend([0, name, 0, 0]);
+        }
+        let v2 = parse_aco(&data, "swatches").unwrap();
+        assert_eq!(hex_list(&v2), hex_list(&v1));
+        assert_eq!(color_names(&v2), [Some("r"), Some("k"), Some("g")]);
+
+        assert!(parse_aco(&[0, 3, 0, 0], "x").is_err());
+        assert!(parse_aco(&[0, 1, 0, 1, 0, 0], "x").is_err());
+    }
+
+    #[test]
+    fn guesses_format_from_path() {
+        assert_eq!(
+            PaletteFileFormat::from_path("a/b/colors.GPL"),
+            Some(PaletteFileFormat::Gimp)
+        );
+        assert_eq!(
+            PaletteFileFormat::from_path("theme.scss"),
+            Some(PaletteFileFormat::CssVariables)
+        );
+        assert_eq!(PaletteFileFormat::from_path("image.png"), None);
+    }
+}
@@ -0,0 +1,189 @@
+use super::{Color, Illuminant, Rgb, RgbWorkingSpace, Xyz, CIE_E, CIE_K};
+
+use serde::{Deserialize, Serialize};
+
+/// CIE 1976 L*a*b* color
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Lab {
+    l: f32,
+    a: f32,
+    b: f32,
+    #[serde(default = "super::opaque")]
+    alpha: f32,
+}
+
+impl Lab {
+    pub fn new(l: f32, a: f32, b: f32) -> Self {
+        let l = if l.is_nan() { 0. } else { l };
+        let a = if a.is_nan() { 0. } else { a };
+        let b = if b.is_nan() { 0. } else { b };
+        Self { l, a, b, alpha: 1. }
+    }
+
+    /// Returns this color with Alpha set to `alpha` in the range 0.0 ..= 1.0
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = super::clamp_alpha(alpha);
+        self
+    }
+
+    #[inline(always)]
+    /// Returns Alpha in the range of 0.0 ..= 1.0
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    #[inline(always)]
+    /// Returns Lightness in the range of 0.0 ..= 100.0
+    pub fn l(&self) -> f32 {
+        self.l
+    }
+
+    #[inline(always)]
+    /// Returns the green-red axis, roughly in the range of -128.0 ..= 127.0
+    pub fn a(&self) -> f32 {
+        self.a
+    }
+
+    #[inline(always)]
+    /// Returns the blue-yellow axis, roughly in the range of -128.0 ..= 127.0
+    pub fn b(&self) -> f32 {
+        self.b
+    }
+
+    /// Converts `xyz` to L*a*b* relative to the `illuminant` reference white.
+    pub fn from_xyz(xyz: Xyz, illuminant: Illuminant) -> Self {
+        fn f(t: f32) -> f32 {
+            if t > CIE_E {
+                t.cbrt()
+            } else {
+                (CIE_K * t + 16.) / 116.
+            }
+        }
+
+        let white = illuminant.xyz();
+        let fx = f(xyz.x() / white.x());
+        let fy = f(xyz.y() / white.y());
+        let fz = f(xyz.z() / white.z());
+
+        Self::new(116. * fy - 16., 500. * (fx - fy), 200. * (fy - fz))
+    }
+
+    /// Converts this color relative to the `illuminant` reference white to XYZ.
+    pub fn to_xyz(self, illuminant: Illuminant) -> Xyz {
+        let fy = (self.l + 16.) / 116.;
+        let fx = self.a / 500. + fy;
+        let fz = fy - self.b / 200.;
+
+        let xr = if fx.powi(3) > CIE_E {
+            fx.powi(3)
+        } else {
+            (116. * fx - 16.) / CIE_K
+        };
+        let yr = if self.l > CIE_K * CIE_E {
+            fy.powi(3)
+        } else {
+            self.l / CIE_K
+        };
+        let zr = if fz.powi(3) > CIE_E {
+            fz.powi(3)
+        } else {
+            (116. * fz - 16.) / CIE_K
+        };
+
+        let white = illuminant.xyz();
+        Xyz::new(xr * white.x(), yr * white.y(), zr * white.z())
+    }
+}
+
+//####################################################################################################
+
+// Standalone L*a*b* colors follow CSS Color Level 4 and are sRGB adapted to a D50 reference white.
+
+impl From<Rgb> for Lab {
+    fn from(rgb: Rgb) -> Self {
+        let xyz = Xyz::from_rgb(rgb, RgbWorkingSpace::SRGB, Illuminant::D50);
+        Lab::from_xyz(xyz, Illuminant::D50).with_alpha(rgb.alpha())
+    }
+}
+
+impl From<Lab> for Rgb {
+    fn from(color: Lab) -> Self {
+        color
+            .to_xyz(Illuminant::D50)
+            .to_rgb(RgbWorkingSpace::SRGB, Illuminant::D50)
+            .with_alpha(color.alpha)
+    }
+}
+
+impl From<Color> for Lab {
+    fn from(c: Color) -> Lab {
+        match c {
+            Color::Lab(c) => c,
+            Color::Lch(c) => c.into(),
+            Color::Oklab(c) => c.into(),
+            Color::Oklch(c) => c.into(),
+            c => Rgb::from(c).into(),
+        }
+    }
+}
+
+impl From<&Color> for Lab {
+    fn from(c: &Color) -> Self {
+        (*c).into()
+    }
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use crate::core::color::{Illuminant, Lab, Rgb, Xyz};
+
+    #[test]
+    fn xyz_to_lab() {
+        macro_rules! test_case {
+            ($illuminant:ident; Xyz: $x:expr, $y:expr, $z:expr; Lab: $l:expr, $a:expr, $b:expr) => {
+                let xyz = Xyz::new($x, $y, $z);
+                let got = Lab::from_xyz(xyz, Illuminant::$illuminant);
+                assert!((got.l() - $l).abs() < 1e-2, "{got:?}");
+                assert!((got.a() - $a).abs() < 1e-2, "{got:?}");
+                assert!((got.b() - $b).abs() < 1e-2, "{got:?}");
+
+                let back = got.to_xyz(Illuminant::$illuminant);
+                assert!((back.x() - $x).abs() < 1e-4, "{back:?}");
+                assert!((back.y() - $y).abs() < 1e-4, "{back:?}");
+                assert!((back.z() - $z).abs() < 1e-4, "{back:?}");
+            };
+        }
+
+        test_case!(D65; Xyz: 0., 0., 0.; Lab: 0., 0., 0.);
+        test_case!(D65; Xyz: 0.95047, 1., 1.08883; Lab: 100., 0., 0.);
+        test_case!(D65; Xyz: 0.41246, 0.21267, 0.01933; Lab: 53.2408, 80.0925, 67.2032);
+        test_case!(D65; Xyz: 0.18044, 0.07218, 0.95030; Lab: 32.2970, 79.1875, -107.8602);
+        test_case!(D65; Xyz: 0.001, 0.002, 0.003; Lab: 1.8066, -3.6906, -1.1762);
+        test_case!(D50; Xyz: 0.96422, 1., 0.82521; Lab: 100., 0., 0.);
+    }
+
+    #[test]
+    fn rgb_to_lab() {
+        macro_rules! test_case {
+            (Rgb: $r:expr, $g:expr, $b:expr; Lab: $l:expr, $a:expr, $lb:expr) => {
+                let rgb = Rgb::new_scaled($r, $g, $b);
+                let got = Lab::from(rgb);
+                assert!((got.l() - $l).abs() < 5e-2, "{got:?}");
+                assert!((got.a() - $a).abs() < 5e-2, "{got:?}");
+                assert!((got.b() - $lb).abs() < 5e-2, "{got:?}");
+
+                let back = Rgb::from(got);
+                assert!((back.r_scaled() - $r as f32).abs() < 0.5, "{back:?}");
+                assert!((back.g_scaled() - $g as f32).abs() < 0.5, "{back:?}");
+                assert!((back.b_scaled() - $b as f32).abs() < 0.5, "{back:?}");
+            };
+        }
+
+        test_case!(Rgb: 0, 0, 0; Lab: 0., 0., 0.);
+        test_case!(Rgb: 255, 255, 255; Lab: 100., 0., 0.);
+        test_case!(Rgb: 255, 0, 0; Lab: 54.29, 80.80, 69.89);
+        test_case!(Rgb: 0, 128, 0; Lab: 46.28, -47.55, 48.59);
+    }
+}
@@ -0,0 +1,374 @@
+use super::{Color, Lab, Luv, Oklab, Rgb};
+
+use serde::{Deserialize, Serialize};
+
+/// Returns the chroma and hue in degrees of the cartesian coordinates `x` and `y`.
+fn polar(x: f32, y: f32) -> (f32, f32) {
+    let c = (x * x + y * y).sqrt();
+    let h = y.atan2(x).to_degrees();
+    (c, if h < 0. { h + 360. } else { h })
+}
+
+/// Returns the cartesian coordinates of chroma `c` and hue `h` in degrees.
+fn cartesian(c: f32, h: f32) -> (f32, f32) {
+    let h = h.to_radians();
+    (c * h.cos(), c * h.sin())
+}
+
+/// Cylindrical representation of the CIE L*a*b* color space
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LchAb {
+    l: f32,
+    c: f32,
+    h: f32,
+    #[serde(default = "super::opaque")]
+    alpha: f32,
+}
+
+impl LchAb {
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        let l = if l.is_nan() { 0. } else { l };
+        let c = if c.is_nan() { 0. } else { c };
+        let h = if h.is_nan() { 0. } else { h };
+        Self { l, c, h, alpha: 1. }
+    }
+
+    /// Returns this color with Alpha set to `alpha` in the range 0.0 ..= 1.0
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = super::clamp_alpha(alpha);
+        self
+    }
+
+    #[inline(always)]
+    /// Returns Alpha in the range of 0.0 ..= 1.0
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    #[inline(always)]
+    /// Returns Lightness in the range of 0.0 ..= 100.0
+    pub fn l(&self) -> f32 {
+        self.l
+    }
+
+    #[inline(always)]
+    /// Returns Chroma, roughly in the range of 0.0 ..= 150.0 for sRGB colors
+    pub fn c(&self) -> f32 {
+        self.c
+    }
+
+    #[inline(always)]
+    /// Returns Hue in degrees in the range of 0.0 ..= 360.0
+    pub fn h(&self) -> f32 {
+        self.h
+    }
+}
+
+impl From<Lab> for LchAb {
+    fn from(lab: Lab) -> Self {
+        let (c, h) = polar(lab.a(), lab.b());
+        Self::new(lab.l(), c, h).with_alpha(lab.alpha())
+    }
+}
+
+impl From<LchAb> for Lab {
+    fn from(lch: LchAb) -> Self {
+        let (a, b) = cartesian(lch.c, lch.h);
+        Lab::new(lch.l, a, b).with_alpha(lch.alpha)
+    }
+}
+
+impl From<Rgb> for LchAb {
+    fn from(rgb: Rgb) -> Self {
+        Lab::from(rgb).into()
+    }
+}
+
+impl From<LchAb> for Rgb {
+    fn from(color: LchAb) -> Self {
+        Lab::from(color).into()
+    }
+}
+
+impl From<Oklab> for LchAb {
+    fn from(color: Oklab) -> Self {
+        Lab::from(color).into()
+    }
+}
+
+impl From<LchAb> for Oklab {
+    fn from(color: LchAb) -> Self {
+        Lab::from(color).into()
+    }
+}
+
+impl From<Oklch> for LchAb {
+    fn from(color: Oklch) -> Self {
+        Lab::from(Oklab::from(color)).into()
+    }
+}
+
+impl From<Color> for LchAb {
+    fn from(c: Color) -> LchAb {
+        match c {
+            Color::Lch(c) => c,
+            Color::Lab(c) => c.into(),
+            Color::Oklab(c) => c.into(),
+            Color::Oklch(c) => c.into(),
+            c => Rgb::from(c).into(),
+        }
+    }
+}
+
+impl From<&Color> for LchAb {
+    fn from(c: &Color) -> Self {
+        (*c).into()
+    }
+}
+
+/// Cylindrical representation of the CIE L*u*v* color space
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LchUv {
+    l: f32,
+    c: f32,
+    h: f32,
+}
+
+impl LchUv {
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        let l = if l.is_nan() { 0. } else { l };
+        let c = if c.is_nan() { 0. } else { c };
+        let h = if h.is_nan() { 0. } else { h };
+        Self { l, c, h }
+    }
+
+    #[inline(always)]
+    /// Returns Lightness in the range of 0.0 ..= 100.0
+    pub fn l(&self) -> f32 {
+        self.l
+    }
+
+    #[inline(always)]
+    /// Returns Chroma, roughly in the range of 0.0 ..= 180.0 for sRGB colors
+    pub fn c(&self) -> f32 {
+        self.c
+    }
+
+    #[inline(always)]
+    /// Returns Hue in degrees in the range of 0.0 ..= 360.0
+    pub fn h(&self) -> f32 {
+        self.h
+    }
+}
+
+impl From<Luv> for LchUv {
+    fn from(luv: Luv) -> Self {
+        let (c, h) = polar(luv.u(), luv.v());
+        Self::new(luv.l(), c, h)
+    }
+}
+
+impl From<LchUv> for Luv {
+    fn from(lch: LchUv) -> Self {
+        let (u, v) = cartesian(lch.c, lch.h);
+        Luv::new(lch.l, u, v)
+    }
+}
+
+/// Cylindrical representation of the Oklab color space
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Oklch {
+    l: f32,
+    c: f32,
+    h: f32,
+    #[serde(default = "super::opaque")]
+    alpha: f32,
+}
+
+impl Oklch {
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        let l = if l.is_nan() { 0. } else { l };
+        let c = if c.is_nan() { 0. } else { c };
+        let h = if h.is_nan() { 0. } else { h };
+        Self { l, c, h, alpha: 1. }
+    }
+
+    /// Returns this color with Alpha set to `alpha` in the range 0.0 ..= 1.0
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = super::clamp_alpha(alpha);
+        self
+    }
+
+    #[inline(always)]
+    /// Returns Alpha in the range of 0.0 ..= 1.0
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    #[inline(always)]
+    /// Returns Lightness in the range of 0.0 ..= 1.0
+    pub fn l(&self) -> f32 {
+        self.l
+    }
+
+    #[inline(always)]
+    /// Returns Chroma, roughly in the range of 0.0 ..= 0.4
+    pub fn c(&self) -> f32 {
+        self.c
+    }
+
+    #[inline(always)]
+    /// Returns Hue in degrees in the range of 0.0 ..= 360.0
+    pub fn h(&self) -> f32 {
+        self.h
+    }
+
+    /// Returns Lightness in the range of 0.0 ..= 100.0
+    pub fn l_scaled(&self) -> f32 {
+        self.l * 100.
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(color: Oklab) -> Self {
+        let (c, h) = polar(color.a(), color.b());
+        Self::new(color.l(), c, h).with_alpha(color.alpha())
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(color: Oklch) -> Self {
+        let (a, b) = cartesian(color.c, color.h);
+        Oklab::new(color.l, a, b).with_alpha(color.alpha)
+    }
+}
+
+impl From<Rgb> for Oklch {
+    fn from(rgb: Rgb) -> Self {
+        Oklab::from(rgb).into()
+    }
+}
+
+impl From<Oklch> for Rgb {
+    fn from(color: Oklch) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<Lab> for Oklch {
+    fn from(color: Lab) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<Oklch> for Lab {
+    fn from(color: Oklch) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<LchAb> for Oklch {
+    fn from(color: LchAb) -> Self {
+        Oklab::from(Lab::from(color)).into()
+    }
+}
+
+impl From<Color> for Oklch {
+    fn from(c: Color) -> Oklch {
+        match c {
+            Color::Oklch(c) => c,
+            Color::Oklab(c) => c.into(),
+            Color::Lab(c) => c.into(),
+            Color::Lch(c) => c.into(),
+            c => Rgb::from(c).into(),
+        }
+    }
+}
+
+impl From<&Color> for Oklch {
+    fn from(c: &Color) -> Self {
+        (*c).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::color::{Lab, LchAb, LchUv, Luv, Oklab, Oklch, Rgb};
+
+    #[test]
+    fn lab_to_lch() {
+        macro_rules! test_case {
+            (Lab: $l:expr, $a:expr, $b:expr; Lch: $cl:expr, $c:expr, $h:expr) => {
+                let got = LchAb::from(Lab::new($l, $a, $b));
+                assert!((got.l() - $cl).abs() < 1e-2, "{got:?}");
+                assert!((got.c() - $c).abs() < 1e-2, "{got:?}");
+                assert!((got.h() - $h).abs() < 1e-2, "{got:?}");
+
+                let back = Lab::from(got);
+                assert!((back.a() - $a).abs() < 1e-2, "{back:?}");
+                assert!((back.b() - $b).abs() < 1e-2, "{back:?}");
+            };
+        }
+
+        test_case!(Lab: 0., 0., 0.; Lch: 0., 0., 0.);
+        test_case!(Lab: 53.2408, 80.0925, 67.2032; Lch: 53.2408, 104.5518, 39.9990);
+        test_case!(Lab: 32.2970, 79.1875, -107.8602; Lch: 32.2970, 133.8076, 306.2849);
+    }
+
+    #[test]
+    fn luv_to_lch() {
+        macro_rules! test_case {
+            (Luv: $l:expr, $u:expr, $v:expr; Lch: $cl:expr, $c:expr, $h:expr) => {
+                let got = LchUv::from(Luv::new($l, $u, $v));
+                assert!((got.l() - $cl).abs() < 1e-2, "{got:?}");
+                assert!((got.c() - $c).abs() < 1e-2, "{got:?}");
+                assert!((got.h() - $h).abs() < 1e-2, "{got:?}");
+
+                let back = Luv::from(got);
+                assert!((back.u() - $u).abs() < 1e-2, "{back:?}");
+                assert!((back.v() - $v).abs() < 1e-2, "{back:?}");
+            };
+        }
+
+        test_case!(Luv: 0., 0., 0.; Lch: 0., 0., 0.);
+        test_case!(Luv: 53.2408, 175.0151, 37.7564; Lch: 53.2408, 179.0414, 12.1740);
+        test_case!(Luv: 32.2970, -9.4054, -130.3423; Lch: 32.2970, 130.6812, 265.8727);
+    }
+
+    #[test]
+    fn rgb_to_oklch() {
+        macro_rules! test_case {
+            (Rgb: $r:expr, $g:expr, $b:expr; Oklch: $l:expr, $c:expr, $h:expr) => {
+                let got = Oklch::from(Rgb::new_scaled($r, $g, $b));
+                assert!((got.l() - $l).abs() < 1e-3, "{got:?}");
+                assert!((got.c() - $c).abs() < 1e-3, "{got:?}");
+                assert!((got.h() - $h).abs() < 1e-1, "{got:?}");
+
+                let back = Rgb::from(got);
+                assert!((back.r_scaled() - $r as f32).abs() < 0.5, "{back:?}");
+                assert!((back.g_scaled() - $g as f32).abs() < 0.5, "{back:?}");
+                assert!((back.b_scaled() - $b as f32).abs() < 0.5, "{back:?}");
+            };
+        }
+
+        test_case!(Rgb: 255, 0, 0; Oklch: 0.62796, 0.25768, 29.23);
+        test_case!(Rgb: 0, 255, 0; Oklch: 0.86644, 0.29483, 142.50);
+        test_case!(Rgb: 0, 0, 255; Oklch: 0.45201, 0.31321, 264.05);
+    }
+
+    #[test]
+    fn converts_between_cie_types() {
+        let rgb = Rgb::new_scaled(120, 30, 80);
+        let lch = LchAb::from(Oklch::from(rgb));
+        let want = LchAb::from(rgb);
+        assert!((lch.l() - want.l()).abs() < 1e-2, "{lch:?}");
+        assert!((lch.c() - want.c()).abs() < 1e-2, "{lch:?}");
+        assert!((lch.h() - want.h()).abs() < 1e-1, "{lch:?}");
+
+        let oklab = Oklab::from(LchAb::from(Lab::from(rgb)));
+        let want = Oklab::from(rgb);
+        assert!((oklab.l() - want.l()).abs() < 1e-3, "{oklab:?}");
+        assert!((oklab.a() - want.a()).abs() < 1e-3, "{oklab:?}");
+        assert!((oklab.b() - want.b()).abs() < 1e-3, "{oklab:?}");
+    }
+}
@@ -0,0 +1,121 @@
+use super::{Illuminant, Xyz, CIE_E, CIE_K};
+
+use serde::{Deserialize, Serialize};
+
+/// CIE 1976 L*u*v* color
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Luv {
+    l: f32,
+    u: f32,
+    v: f32,
+}
+
+/// Returns the u' and v' chromaticity coordinates of `xyz`.
+fn uv_prime(xyz: Xyz) -> (f32, f32) {
+    let denominator = xyz.x() + 15. * xyz.y() + 3. * xyz.z();
+    if denominator == 0. {
+        return (0., 0.);
+    }
+    (4. * xyz.x() / denominator, 9. * xyz.y() / denominator)
+}
+
+impl Luv {
+    pub fn new(l: f32, u: f32, v: f32) -> Self {
+        let l = if l.is_nan() { 0. } else { l };
+        let u = if u.is_nan() { 0. } else { u };
+        let v = if v.is_nan() { 0. } else { v };
+        Self { l, u, v }
+    }
+
+    #[inline(always)]
+    /// Returns Lightness in the range of 0.0 ..= 100.0
+    pub fn l(&self) -> f32 {
+        self.l
+    }
+
+    #[inline(always)]
+    /// Returns the u* coordinate, roughly in the range of -100.0 ..= 180.0 for sRGB colors
+    pub fn u(&self) -> f32 {
+        self.u
+    }
+
+    #[inline(always)]
+    /// Returns the v* coordinate, roughly in the range of -135.0 ..= 110.0 for sRGB colors
+    pub fn v(&self) -> f32 {
+        self.v
+    }
+
+    /// Converts `xyz` to L*u*v* relative to the `illuminant` reference white.
+    pub fn from_xyz(xyz: Xyz, illuminant: Illuminant) -> Self {
+        let white = illuminant.xyz();
+        let yr = xyz.y() / white.y();
+        let l = if yr > CIE_E {
+            116. * yr.cbrt() - 16.
+        } else {
+            CIE_K * yr
+        };
+
+        if l == 0. {
+            return Self::new(0., 0., 0.);
+        }
+
+        let (u, v) = uv_prime(xyz);
+        let (u_white, v_white) = uv_prime(white);
+
+        Self::new(l, 13. * l * (u - u_white), 13. * l * (v - v_white))
+    }
+
+    /// Converts this color relative to the `illuminant` reference white to XYZ.
+    pub fn to_xyz(self, illuminant: Illuminant) -> Xyz {
+        if self.l <= 0. {
+            return Xyz::new(0., 0., 0.);
+        }
+
+        let white = illuminant.xyz();
+        let (u_white, v_white) = uv_prime(white);
+
+        let y = if self.l > CIE_K * CIE_E {
+            ((self.l + 16.) / 116.).powi(3)
+        } else {
+            self.l / CIE_K
+        } * white.y();
+
+        let a = (52. * self.l / (self.u + 13. * self.l * u_white) - 1.) / 3.;
+        let b = -5. * y;
+        let c = -1. / 3.;
+        let d = y * (39. * self.l / (self.v + 13. * self.l * v_white) - 5.);
+
+        let x = (d - b) / (a - c);
+        let z = x * a + b;
+
+        Xyz::new(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::color::{Illuminant, Luv, Xyz};
+
+    #[test]
+    fn xyz_to_luv() {
+        macro_rules! test_case {
+            (Xyz: $x:expr, $y:expr, $z:expr; Luv: $l:expr, $u:expr, $v:expr) => {
+                let xyz = Xyz::new($x, $y, $z);
+                let got = Luv::from_xyz(xyz, Illuminant::D65);
+                assert!((got.l() - $l).abs() < 1e-2, "{got:?}");
+                assert!((got.u() - $u).abs() < 1e-2, "{got:?}");
+                assert!((got.v() - $v).abs() < 1e-2, "{got:?}");
+
+                let back = got.to_xyz(Illuminant::D65);
+                assert!((back.x() - $x).abs() < 1e-4, "{back:?}");
+                assert!((back.y() - $y).abs() < 1e-4, "{back:?}");
+                assert!((back.z() - $z).abs() < 1e-4, "{back:?}");
+            };
+        }
+
+        test_case!(Xyz: 0., 0., 0.; Luv: 0., 0., 0.);
+        test_case!(Xyz: 0.95047, 1., 1.08883; Luv: 100., 0., 0.);
+        test_case!(Xyz: 0.41246, 0.21267, 0.01933; Luv: 53.2408, 175.0151, 37.7564);
+        test_case!(Xyz: 0.18044, 0.07218, 0.95030; Luv: 32.2970, -9.4054, -130.3423);
+    }
+}
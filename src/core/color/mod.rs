@@ -1,16 +1,45 @@
 mod cmyk;
+pub mod contrast;
+mod css;
+pub mod cvd;
+pub mod delta_e;
 mod format;
 pub mod gradient;
+pub mod harmony;
+pub mod history;
 mod hsl;
 mod hsv;
+mod illuminant;
+pub mod import;
+mod lab;
+mod lch;
+mod luv;
+pub mod names;
+mod oklab;
 mod palette;
 pub mod palettes;
+pub mod ramp;
 mod rgb;
+pub mod scale;
+mod working_space;
+mod xyy;
+mod xyz;
 
 pub use cmyk::Cmyk;
+pub use css::named_color;
+pub use format::{CustomColorFormat, CustomPaletteFormat, FormatError};
 pub use hsl::Hsl;
 pub use hsv::Hsv;
+pub use illuminant::Illuminant;
+pub use lab::Lab;
+pub use lch::{LchAb, LchUv, Oklch};
+pub use luv::Luv;
+pub use oklab::Oklab;
+pub use palette::{NamedPalette, Palette, PaletteFormat};
 pub use rgb::Rgb;
+pub use working_space::{Compand, RgbWorkingSpace};
+pub use xyy::Xyy;
+pub use xyz::Xyz;
 
 use eframe::{
     egui::{Color32, Rgba},
@@ -23,6 +52,24 @@ pub const CIE_K: f32 = 24389. / 27.;
 pub const U8_MAX: f32 = u8::MAX as f32;
 pub const U8_MIN: f32 = u8::MIN as f32;
 
+/// Default alpha of colors deserialized from data saved without an alpha channel.
+fn opaque() -> f32 {
+    1.
+}
+
+fn clamp_alpha(alpha: f32) -> f32 {
+    if alpha.is_nan() {
+        1.
+    } else {
+        alpha.clamp(0., 1.)
+    }
+}
+
+/// Formats `alpha` for CSS output with at most 2 decimal digits, e.g. `0.5`.
+fn format_alpha(alpha: f32) -> String {
+    ((alpha * 100.).round() / 100.).to_string()
+}
+
 //################################################################################
 
 const fn hex_val(ch: u8) -> u8 {
@@ -65,6 +112,30 @@ pub enum ColorFormat {
     CssRgb,
     #[serde(rename = "css-hsl")]
     CssHsl { degree_symbol: bool },
+    #[serde(rename = "css-lab")]
+    CssLab,
+    #[serde(rename = "css-lch")]
+    CssLch,
+    #[serde(rename = "css-oklab")]
+    CssOklab,
+    #[serde(rename = "css-oklch")]
+    CssOklch,
+    #[serde(rename = "swift-uicolor")]
+    SwiftUiColor,
+    #[serde(rename = "swiftui")]
+    SwiftUi,
+    #[serde(rename = "android-parse-color")]
+    AndroidParseColor,
+    #[serde(rename = "android-argb")]
+    AndroidArgb,
+    #[serde(rename = "jetpack-compose")]
+    JetpackCompose,
+    #[serde(rename = "flutter")]
+    Flutter,
+    #[serde(rename = "dotnet")]
+    DotNet,
+    #[serde(rename = "unity")]
+    Unity,
 }
 
 impl ColorFormat {
@@ -89,6 +160,10 @@ pub enum Color {
     Hsv(Hsv),
     Hsl(Hsl),
     Color32(Color32),
+    Lab(Lab),
+    Lch(LchAb),
+    Oklab(Oklab),
+    Oklch(Oklch),
 }
 
 impl Color {
@@ -100,62 +175,252 @@ impl Color {
         Self::Rgb(Rgb::new(1., 1., 1.))
     }
 
+    /// Returns Alpha in the range of 0.0 ..= 1.0
+    pub fn alpha(&self) -> f32 {
+        match self {
+            Color::Rgb(c) => c.alpha(),
+            Color::Cmyk(c) => c.alpha(),
+            Color::Hsv(c) => c.alpha(),
+            Color::Hsl(c) => c.alpha(),
+            Color::Color32(c) => c.a() as f32 / U8_MAX,
+            Color::Lab(c) => c.alpha(),
+            Color::Lch(c) => c.alpha(),
+            Color::Oklab(c) => c.alpha(),
+            Color::Oklch(c) => c.alpha(),
+        }
+    }
+
+    /// Returns this color with Alpha set to `alpha` in the range 0.0 ..= 1.0
+    pub fn with_alpha(self, alpha: f32) -> Self {
+        match self {
+            Color::Rgb(c) => c.with_alpha(alpha).into(),
+            Color::Cmyk(c) => c.with_alpha(alpha).into(),
+            Color::Hsv(c) => c.with_alpha(alpha).into(),
+            Color::Hsl(c) => c.with_alpha(alpha).into(),
+            Color::Color32(c) => Rgb::from(c).with_alpha(alpha).into(),
+            Color::Lab(c) => c.with_alpha(alpha).into(),
+            Color::Lch(c) => c.with_alpha(alpha).into(),
+            Color::Oklab(c) => c.with_alpha(alpha).into(),
+            Color::Oklch(c) => c.with_alpha(alpha).into(),
+        }
+    }
+
+    pub fn is_opaque(&self) -> bool {
+        self.alpha() >= 1.
+    }
+
+    /// Returns the CSS4 ` / alpha` suffix for translucent colors and an empty string otherwise.
+    fn css_alpha_suffix(&self) -> String {
+        if self.is_opaque() {
+            String::new()
+        } else {
+            format!(" / {}", format_alpha(self.alpha()))
+        }
+    }
+
     pub fn intensity(&self) -> f32 {
         let rgb = self.rgb();
         0.215 * rgb.r() + 0.7 * rgb.g() + 0.085 * rgb.b()
     }
 
+    /// Returns black or white, whichever has the higher WCAG contrast ratio over this color.
     pub fn contrast(&self) -> Color {
-        if self.intensity() > 0.5 {
-            Self::black()
+        let black = Self::black();
+        let white = Self::white();
+        if contrast::contrast_ratio(&black, self) > contrast::contrast_ratio(&white, self) {
+            black
         } else {
-            Self::white()
+            white
         }
     }
 
+    /// Returns `#rrggbb` for opaque colors and `#rrggbbaa` otherwise.
     pub fn as_hex(&self) -> String {
         let color = self.as_rgb_triplet_scaled();
-        format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+        let hex = format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2);
+        if self.is_opaque() {
+            hex
+        } else {
+            format!("{hex}{:02x}", (self.alpha() * U8_MAX).round() as u8)
+        }
     }
 
     pub fn as_css_rgb(&self) -> String {
         let color = self.as_rgb_triplet_scaled();
-        format!("rgb({},{},{})", color.0, color.1, color.2)
+        if self.is_opaque() {
+            format!("rgb({},{},{})", color.0, color.1, color.2)
+        } else {
+            let alpha = format_alpha(self.alpha());
+            format!("rgba({},{},{},{alpha})", color.0, color.1, color.2)
+        }
     }
 
     pub fn as_css_rgb_padded(&self) -> String {
         let color = self.as_rgb_triplet_scaled();
-        format!("rgb({:>3},{:>3},{:>3})", color.0, color.1, color.2)
+        if self.is_opaque() {
+            format!("rgb({:>3},{:>3},{:>3})", color.0, color.1, color.2)
+        } else {
+            let alpha = format_alpha(self.alpha());
+            format!(
+                "rgba({:>3},{:>3},{:>3},{alpha:>4})",
+                color.0, color.1, color.2
+            )
+        }
     }
 
     pub fn as_css_hsl(&self, degree_symbol: bool) -> String {
         let color = self.hsl();
-        format!(
-            "hsl({}{},{}%,{}%)",
+        let degree = if degree_symbol { "°" } else { "" };
+        let (h, s, l) = (
             color.h_scaled() as u16,
-            if degree_symbol { "°" } else { "" },
             color.s_scaled() as u16,
-            color.l_scaled() as u16
-        )
+            color.l_scaled() as u16,
+        );
+        if self.is_opaque() {
+            format!("hsl({h}{degree},{s}%,{l}%)")
+        } else {
+            let alpha = format_alpha(self.alpha());
+            format!("hsla({h}{degree},{s}%,{l}%,{alpha})")
+        }
     }
 
     pub fn as_css_hsl_padded(&self, degree_symbol: bool) -> String {
         let color = self.hsl();
-        format!(
-            "hsl({:>3}{},{:>3}%,{:>3}%)",
+        let degree = if degree_symbol { "°" } else { "" };
+        let (h, s, l) = (
             color.h_scaled() as u16,
-            if degree_symbol { "°" } else { "" },
             color.s_scaled() as u16,
-            color.l_scaled() as u16
+            color.l_scaled() as u16,
+        );
+        if self.is_opaque() {
+            format!("hsl({h:>3}{degree},{s:>3}%,{l:>3}%)")
+        } else {
+            let alpha = format_alpha(self.alpha());
+            format!("hsla({h:>3}{degree},{s:>3}%,{l:>3}%,{alpha:>4})")
+        }
+    }
+
+    pub fn as_css_lab(&self) -> String {
+        let color = Lab::from(self);
+        format!(
+            "lab({:.2}% {:.2} {:.2}{})",
+            color.l(),
+            color.a(),
+            color.b(),
+            self.css_alpha_suffix()
+        )
+    }
+
+    pub fn as_css_lch(&self) -> String {
+        let color = LchAb::from(self);
+        format!(
+            "lch({:.2}% {:.2} {:.2}{})",
+            color.l(),
+            color.c(),
+            color.h(),
+            self.css_alpha_suffix()
+        )
+    }
+
+    pub fn as_css_oklab(&self) -> String {
+        let color = self.oklab();
+        format!(
+            "oklab({:.2}% {:.4} {:.4}{})",
+            color.l_scaled(),
+            color.a(),
+            color.b(),
+            self.css_alpha_suffix()
         )
     }
 
+    pub fn as_css_oklch(&self) -> String {
+        let color = self.oklch();
+        format!(
+            "oklch({:.2}% {:.4} {:.2}{})",
+            color.l_scaled(),
+            color.c(),
+            color.h(),
+            self.css_alpha_suffix()
+        )
+    }
+
+    /// Returns the red, green, blue and alpha channels in the range of 0.0 ..= 1.0 formatted with
+    /// three decimals for use in source code.
+    fn code_channels(&self) -> [String; 4] {
+        let color = self.rgb();
+        [color.r(), color.g(), color.b(), self.alpha()].map(|channel| format!("{channel:.3}"))
+    }
+
+    /// Returns the color as `AARRGGBB` in uppercase hex.
+    fn argb_hex(&self) -> String {
+        let (r, g, b) = self.as_rgb_triplet_scaled();
+        let a = (self.alpha() * U8_MAX).round() as u8;
+        format!("{a:02X}{r:02X}{g:02X}{b:02X}")
+    }
+
+    pub fn as_swift_uicolor(&self) -> String {
+        let [r, g, b, a] = self.code_channels();
+        format!("UIColor(red: {r}, green: {g}, blue: {b}, alpha: {a})")
+    }
+
+    pub fn as_swiftui(&self) -> String {
+        let [r, g, b, a] = self.code_channels();
+        if self.is_opaque() {
+            format!("Color(red: {r}, green: {g}, blue: {b})")
+        } else {
+            format!("Color(red: {r}, green: {g}, blue: {b}, opacity: {a})")
+        }
+    }
+
+    pub fn as_android_parse_color(&self) -> String {
+        if self.is_opaque() {
+            format!("Color.parseColor(\"{}\")", self.as_hex().to_uppercase())
+        } else {
+            format!("Color.parseColor(\"#{}\")", self.argb_hex())
+        }
+    }
+
+    pub fn as_android_argb(&self) -> String {
+        format!("0x{}", self.argb_hex())
+    }
+
+    pub fn as_jetpack_compose(&self) -> String {
+        format!("Color(0x{})", self.argb_hex())
+    }
+
+    pub fn as_flutter(&self) -> String {
+        format!("Color(0x{})", self.argb_hex())
+    }
+
+    pub fn as_dotnet(&self) -> String {
+        let (r, g, b) = self.as_rgb_triplet_scaled();
+        let a = (self.alpha() * U8_MAX).round() as u8;
+        format!("Color.FromArgb({a}, {r}, {g}, {b})")
+    }
+
+    pub fn as_unity(&self) -> String {
+        let [r, g, b, a] = self.code_channels();
+        format!("new Color({r}f, {g}f, {b}f, {a}f)")
+    }
+
     pub fn display(&self, format: ColorFormat) -> String {
         match format {
             ColorFormat::Hex => self.as_hex(),
             ColorFormat::HexUpercase => self.as_hex().to_uppercase(),
             ColorFormat::CssRgb => self.as_css_rgb(),
             ColorFormat::CssHsl { degree_symbol } => self.as_css_hsl(degree_symbol),
+            ColorFormat::CssLab => self.as_css_lab(),
+            ColorFormat::CssLch => self.as_css_lch(),
+            ColorFormat::CssOklab => self.as_css_oklab(),
+            ColorFormat::CssOklch => self.as_css_oklch(),
+            ColorFormat::SwiftUiColor => self.as_swift_uicolor(),
+            ColorFormat::SwiftUi => self.as_swiftui(),
+            ColorFormat::AndroidParseColor => self.as_android_parse_color(),
+            ColorFormat::AndroidArgb => self.as_android_argb(),
+            ColorFormat::JetpackCompose => self.as_jetpack_compose(),
+            ColorFormat::Flutter => self.as_flutter(),
+            ColorFormat::DotNet => self.as_dotnet(),
+            ColorFormat::Unity => self.as_unity(),
         }
     }
 
@@ -163,6 +428,11 @@ impl Color {
         parse_hex(hex).map(|(r, g, b)| Rgb::new_scaled(r, g, b).into())
     }
 
+    /// Parses any CSS color value, see [`css::parse_color`].
+    pub fn from_css(text: &str) -> anyhow::Result<Self> {
+        css::parse_color(text)
+    }
+
     pub fn as_hue_offset(&self, offset: f32) -> Color {
         let mut hsv = self.hsv();
         hsv.offset_hue(offset);
@@ -178,9 +448,9 @@ impl Color {
     pub fn as_rgb_triplet_scaled(&self) -> (u8, u8, u8) {
         let color = self.rgb();
         (
-            color.r_scaled().floor() as u8,
-            color.g_scaled().floor() as u8,
-            color.b_scaled().floor() as u8,
+            color.r_scaled().round() as u8,
+            color.g_scaled().round() as u8,
+            color.b_scaled().round() as u8,
         )
     }
 
@@ -217,6 +487,40 @@ impl Color {
         self.into()
     }
 
+    pub fn oklab(&self) -> Oklab {
+        self.into()
+    }
+
+    pub fn oklch(&self) -> Oklch {
+        self.into()
+    }
+
+    /// Returns this color as XYZ, treating its RGB values as encoded in the working space `ws`
+    /// and adapting the result to the `illuminant` reference white.
+    pub fn xyz(&self, ws: RgbWorkingSpace, illuminant: Illuminant) -> Xyz {
+        Xyz::from_rgb(self.rgb(), ws, illuminant)
+    }
+
+    pub fn xyy(&self, ws: RgbWorkingSpace, illuminant: Illuminant) -> Xyy {
+        Xyy::from_xyz(self.xyz(ws, illuminant), illuminant)
+    }
+
+    pub fn lab(&self, ws: RgbWorkingSpace, illuminant: Illuminant) -> Lab {
+        Lab::from_xyz(self.xyz(ws, illuminant), illuminant)
+    }
+
+    pub fn lch_ab(&self, ws: RgbWorkingSpace, illuminant: Illuminant) -> LchAb {
+        self.lab(ws, illuminant).into()
+    }
+
+    pub fn luv(&self, ws: RgbWorkingSpace, illuminant: Illuminant) -> Luv {
+        Luv::from_xyz(self.xyz(ws, illuminant), illuminant)
+    }
+
+    pub fn lch_uv(&self, ws: RgbWorkingSpace, illuminant: Illuminant) -> LchUv {
+        self.luv(ws, illuminant).into()
+    }
+
     pub fn shades(&self, total: u8) -> Vec<Color> {
         if total == 0 {
             return vec![*self];
@@ -236,7 +540,7 @@ impl Color {
 
         (0..total)
             .map(|_| {
-                let c = Color32::from_rgb(base_r, base_g, base_b);
+                let c = Rgb::new_scaled(base_r, base_g, base_b).with_alpha(rgb.alpha());
                 base_r = base_r.saturating_sub(step_r);
                 base_g = base_g.saturating_sub(step_g);
                 base_b = base_b.saturating_sub(step_b);
@@ -263,7 +567,7 @@ impl Color {
 
         (0..total)
             .map(|_| {
-                let c = Color32::from_rgb(base_r, base_g, base_b);
+                let c = Rgb::new_scaled(base_r, base_g, base_b).with_alpha(rgb.alpha());
                 base_r = base_r.saturating_add(step_r);
                 base_g = base_g.saturating_add(step_g);
                 base_b = base_b.saturating_add(step_b);
@@ -360,6 +664,10 @@ impl From<Color> for Color32 {
             Color::Hsv(c) => c.into(),
             Color::Hsl(c) => c.into(),
             Color::Color32(c) => c,
+            Color::Lab(c) => c.into(),
+            Color::Lch(c) => c.into(),
+            Color::Oklab(c) => c.into(),
+            Color::Oklch(c) => c.into(),
         }
     }
 }
@@ -390,6 +698,10 @@ macro_rules! to_epaint_color {
             Color::Hsv(c) => Rgb::from(c).into(),
             Color::Hsl(c) => Rgb::from(c).into(),
             Color::Color32(c) => c.into(),
+            Color::Lab(c) => Rgb::from(c).into(),
+            Color::Lch(c) => Rgb::from(c).into(),
+            Color::Oklab(c) => Rgb::from(c).into(),
+            Color::Oklch(c) => Rgb::from(c).into(),
         }
     };
 }
@@ -438,11 +750,62 @@ impl From<Rgb> for Color {
     }
 }
 
+impl From<Lab> for Color {
+    fn from(c: Lab) -> Color {
+        Color::Lab(c)
+    }
+}
+
+impl From<LchAb> for Color {
+    fn from(c: LchAb) -> Color {
+        Color::Lch(c)
+    }
+}
+
+impl From<Oklab> for Color {
+    fn from(c: Oklab) -> Color {
+        Color::Oklab(c)
+    }
+}
+
+impl From<Oklch> for Color {
+    fn from(c: Oklch) -> Color {
+        Color::Oklch(c)
+    }
+}
+
+//##################################################################################################
+
+/// Implements conversions in both directions between a perceptual color type and the given RGB
+/// based color types, going through `Rgb`.
+macro_rules! impl_rgb_conversions {
+    ($ty:ident: $($other:ident),+) => {
+        $(
+            impl From<$other> for $ty {
+                fn from(c: $other) -> $ty {
+                    Rgb::from(c).into()
+                }
+            }
+
+            impl From<$ty> for $other {
+                fn from(c: $ty) -> $other {
+                    Rgb::from(c).into()
+                }
+            }
+        )+
+    };
+}
+
+impl_rgb_conversions!(Lab: Cmyk, Hsv, Hsl, Color32);
+impl_rgb_conversions!(LchAb: Cmyk, Hsv, Hsl, Color32);
+impl_rgb_conversions!(Oklab: Cmyk, Hsv, Hsl, Color32);
+impl_rgb_conversions!(Oklch: Cmyk, Hsv, Hsl, Color32);
+
 //##################################################################################################
 
 #[cfg(test)]
 mod tests {
-    use super::parse_hex;
+    use super::{parse_hex, Color, ColorFormat, Hsl, Lab, LchAb, Oklab, Oklch, Rgb};
     #[test]
     fn parses_hex() {
         macro_rules! test_case {
@@ -468,4 +831,124 @@ mod tests {
         test_case!("12abf", None);
         test_case!("12abfff", None);
     }
+
+    #[test]
+    fn converts_between_variants() {
+        let colors: [Color; 5] = [
+            Rgb::new_scaled(120, 30, 80).into(),
+            Lab::from(Rgb::new_scaled(120, 30, 80)).into(),
+            LchAb::from(Rgb::new_scaled(120, 30, 80)).into(),
+            Oklab::from(Rgb::new_scaled(120, 30, 80)).into(),
+            Oklch::from(Hsl::from(Rgb::new_scaled(120, 30, 80))).into(),
+        ];
+
+        for color in colors {
+            assert_eq!(color.as_hex(), "#781e50", "{color:?}");
+            assert_eq!(Color::from(color.cmyk()).as_hex(), "#781e50", "{color:?}");
+            assert_eq!(Color::from(color.hsv()).as_hex(), "#781e50", "{color:?}");
+            assert_eq!(Color::from(color.hsl()).as_hex(), "#781e50", "{color:?}");
+            assert_eq!(
+                Color::from(color.color32()).as_hex(),
+                "#781e50",
+                "{color:?}"
+            );
+            assert_eq!(
+                Color::from(Lab::from(color)).as_hex(),
+                "#781e50",
+                "{color:?}"
+            );
+            assert_eq!(
+                Color::from(LchAb::from(color)).as_hex(),
+                "#781e50",
+                "{color:?}"
+            );
+            assert_eq!(Color::from(color.oklab()).as_hex(), "#781e50", "{color:?}");
+            assert_eq!(Color::from(color.oklch()).as_hex(), "#781e50", "{color:?}");
+        }
+    }
+
+    #[test]
+    fn displays_css_colors() {
+        macro_rules! test_case {
+            ($format:ident, $color:expr => $want:literal) => {
+                assert_eq!(Color::from($color).display(ColorFormat::$format), $want);
+            };
+        }
+
+        test_case!(CssLab, Rgb::new(1., 1., 1.) => "lab(100.00% 0.00 0.00)");
+        test_case!(CssLab, Rgb::new_scaled(0, 128, 0) => "lab(46.28% -47.56 48.58)");
+        test_case!(CssLch, Lab::new(50., 30., 40.) => "lch(50.00% 50.00 53.13)");
+        test_case!(CssOklab, Rgb::new(0., 0., 1.) => "oklab(45.20% -0.0325 -0.3115)");
+        test_case!(CssOklch, Oklch::new(0.7, 0.15, 180.) => "oklch(70.00% 0.1500 180.00)");
+    }
+
+    #[test]
+    fn displays_code_colors() {
+        macro_rules! test_case {
+            ($format:ident, $color:expr => $want:literal) => {
+                assert_eq!(Color::from($color).display(ColorFormat::$format), $want);
+            };
+        }
+
+        let color = Rgb::new_scaled(120, 30, 80);
+        let translucent = color.with_alpha(0.5);
+        test_case!(SwiftUiColor, color => "UIColor(red: 0.471, green: 0.118, blue: 0.314, alpha: 1.000)");
+        test_case!(SwiftUi, color => "Color(red: 0.471, green: 0.118, blue: 0.314)");
+        test_case!(SwiftUi, translucent => "Color(red: 0.471, green: 0.118, blue: 0.314, opacity: 0.500)");
+        test_case!(AndroidParseColor, color => "Color.parseColor(\"#781E50\")");
+        test_case!(AndroidParseColor, translucent => "Color.parseColor(\"#80781E50\")");
+        test_case!(AndroidArgb, color => "0xFF781E50");
+        test_case!(JetpackCompose, translucent => "Color(0x80781E50)");
+        test_case!(Flutter, color => "Color(0xFF781E50)");
+        test_case!(DotNet, translucent => "Color.FromArgb(128, 120, 30, 80)");
+        test_case!(Unity, color => "new Color(0.471f, 0.118f, 0.314f, 1.000f)");
+    }
+
+    #[test]
+    fn displays_translucent_colors() {
+        macro_rules! test_case {
+            ($format:expr, $color:expr => $want:literal) => {
+                assert_eq!(Color::from($color).display($format), $want);
+            };
+        }
+
+        let color = Rgb::new_scaled(120, 30, 80).with_alpha(0.5);
+        test_case!(ColorFormat::Hex, color => "#781e5080");
+        test_case!(ColorFormat::CssRgb, color => "rgba(120,30,80,0.5)");
+        test_case!(ColorFormat::CssHsl { degree_symbol: false }, color => "hsla(326,59%,29%,0.5)");
+        test_case!(ColorFormat::CssLab, Lab::new(50., 30., 40.).with_alpha(0.25) => "lab(50.00% 30.00 40.00 / 0.25)");
+        test_case!(ColorFormat::CssOklch, Oklch::new(0.7, 0.15, 180.).with_alpha(0.) => "oklch(70.00% 0.1500 180.00 / 0)");
+        test_case!(ColorFormat::CssRgb, Rgb::new_scaled(120, 30, 80) => "rgb(120,30,80)");
+    }
+
+    #[test]
+    fn keeps_alpha_between_variants() {
+        let colors: [Color; 6] = [
+            Rgb::new_scaled(120, 30, 80).with_alpha(0.4).into(),
+            Hsl::new(0.9, 0.6, 0.3).with_alpha(0.4).into(),
+            Lab::new(30., 40., -10.).with_alpha(0.4).into(),
+            LchAb::new(30., 40., 300.).with_alpha(0.4).into(),
+            Oklab::new(0.4, 0.1, -0.02).with_alpha(0.4).into(),
+            Oklch::new(0.4, 0.1, 340.).with_alpha(0.4).into(),
+        ];
+
+        for color in colors {
+            assert_eq!(color.alpha(), 0.4, "{color:?}");
+            assert_eq!(color.rgb().alpha(), 0.4, "{color:?}");
+            assert_eq!(color.cmyk().alpha(), 0.4, "{color:?}");
+            assert_eq!(color.hsv().alpha(), 0.4, "{color:?}");
+            assert_eq!(color.hsl().alpha(), 0.4, "{color:?}");
+            assert_eq!(Lab::from(color).alpha(), 0.4, "{color:?}");
+            assert_eq!(LchAb::from(color).alpha(), 0.4, "{color:?}");
+            assert_eq!(color.oklab().alpha(), 0.4, "{color:?}");
+            assert_eq!(color.oklch().alpha(), 0.4, "{color:?}");
+            assert_eq!(color.color32().a(), 102, "{color:?}");
+            assert_eq!(Color::from(color.color32()).as_hex().len(), 9, "{color:?}");
+        }
+
+        let color = Color::from(Rgb::new_scaled(120, 30, 80)).with_alpha(0.5);
+        assert_eq!(color.as_hex(), "#781e5080");
+        assert_eq!(color.with_alpha(1.).as_hex(), "#781e50");
+        assert!(!color.is_opaque());
+    }
 }
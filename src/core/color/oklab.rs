@@ -0,0 +1,222 @@
+#![allow(clippy::excessive_precision)]
+use super::{Color, Illuminant, Lab, Rgb, RgbWorkingSpace, Xyz};
+use crate::utils::math::{Matrix1x3, Matrix3};
+
+use serde::{Deserialize, Serialize};
+
+/// Converts XYZ relative to D65 to approximate cone responses. Uses the matrix recalculated by
+/// CSS Color Level 4 for a consistent D65 white point.
+const XYZ_TO_LMS: Matrix3 = Matrix3([
+    [0.8190224380, 0.3619062601, -0.1288737815],
+    [0.0329836539, 0.9292868616, 0.0361446664],
+    [0.0481771894, 0.2642395318, 0.6335478285],
+]);
+
+const LMS_TO_XYZ: Matrix3 = Matrix3([
+    [1.2268798758, -0.5578149945, 0.2813910457],
+    [-0.0405757452, 1.1122868033, -0.0717110581],
+    [-0.0763729367, -0.4214933324, 1.5869240198],
+]);
+
+/// Converts non-linear cone responses to Oklab.
+const LMS_TO_OKLAB: Matrix3 = Matrix3([
+    [0.2104542553, 0.7936177850, -0.0040720468],
+    [1.9779984951, -2.4285922050, 0.4505937099],
+    [0.0259040371, 0.7827717662, -0.8086757660],
+]);
+
+const OKLAB_TO_LMS: Matrix3 = Matrix3([
+    [1., 0.3963377774, 0.2158037573],
+    [1., -0.1055613458, -0.0638541728],
+    [1., -0.0894841775, -1.2914855480],
+]);
+
+/// Oklab perceptual color space as defined by Björn Ottosson
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Oklab {
+    l: f32,
+    a: f32,
+    b: f32,
+    #[serde(default = "super::opaque")]
+    alpha: f32,
+}
+
+impl Oklab {
+    pub fn new(l: f32, a: f32, b: f32) -> Self {
+        let l = if l.is_nan() { 0. } else { l };
+        let a = if a.is_nan() { 0. } else { a };
+        let b = if b.is_nan() { 0. } else { b };
+        Self { l, a, b, alpha: 1. }
+    }
+
+    /// Returns this color with Alpha set to `alpha` in the range 0.0 ..= 1.0
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = super::clamp_alpha(alpha);
+        self
+    }
+
+    #[inline(always)]
+    /// Returns Alpha in the range of 0.0 ..= 1.0
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    #[inline(always)]
+    /// Returns Lightness in the range of 0.0 ..= 1.0
+    pub fn l(&self) -> f32 {
+        self.l
+    }
+
+    #[inline(always)]
+    /// Returns the green-red axis, roughly in the range of -0.4 ..= 0.4
+    pub fn a(&self) -> f32 {
+        self.a
+    }
+
+    #[inline(always)]
+    /// Returns the blue-yellow axis, roughly in the range of -0.4 ..= 0.4
+    pub fn b(&self) -> f32 {
+        self.b
+    }
+
+    /// Returns Lightness in the range of 0.0 ..= 100.0
+    pub fn l_scaled(&self) -> f32 {
+        self.l * 100.
+    }
+
+    /// Converts `xyz` relative to the D65 reference white to Oklab.
+    pub fn from_xyz(xyz: Xyz) -> Self {
+        let lms = XYZ_TO_LMS * Matrix1x3::from(xyz);
+        let lms = Matrix1x3::from([lms[0].cbrt(), lms[1].cbrt(), lms[2].cbrt()]);
+        let lab = LMS_TO_OKLAB * lms;
+        Self::new(lab[0], lab[1], lab[2])
+    }
+
+    /// Converts this color to XYZ relative to the D65 reference white.
+    pub fn to_xyz(self) -> Xyz {
+        let lms = OKLAB_TO_LMS * Matrix1x3::from([self.l, self.a, self.b]);
+        let lms = Matrix1x3::from([lms[0].powi(3), lms[1].powi(3), lms[2].powi(3)]);
+        (LMS_TO_XYZ * lms).into()
+    }
+}
+
+//####################################################################################################
+
+impl From<Rgb> for Oklab {
+    fn from(rgb: Rgb) -> Self {
+        Self::from_xyz(Xyz::from_rgb(rgb, RgbWorkingSpace::SRGB, Illuminant::D65))
+            .with_alpha(rgb.alpha())
+    }
+}
+
+impl From<Oklab> for Rgb {
+    fn from(color: Oklab) -> Self {
+        color
+            .to_xyz()
+            .to_rgb(RgbWorkingSpace::SRGB, Illuminant::D65)
+            .with_alpha(color.alpha)
+    }
+}
+
+impl From<Lab> for Oklab {
+    fn from(color: Lab) -> Self {
+        let xyz = color
+            .to_xyz(Illuminant::D50)
+            .adapt(Illuminant::D50, Illuminant::D65);
+        Self::from_xyz(xyz).with_alpha(color.alpha())
+    }
+}
+
+impl From<Oklab> for Lab {
+    fn from(color: Oklab) -> Self {
+        let xyz = color.to_xyz().adapt(Illuminant::D65, Illuminant::D50);
+        Lab::from_xyz(xyz, Illuminant::D50).with_alpha(color.alpha)
+    }
+}
+
+//####################################################################################################
+
+impl From<Color> for Oklab {
+    fn from(c: Color) -> Oklab {
+        match c {
+            Color::Oklab(c) => c,
+            Color::Oklch(c) => c.into(),
+            Color::Lab(c) => c.into(),
+            Color::Lch(c) => c.into(),
+            c => Rgb::from(c).into(),
+        }
+    }
+}
+
+impl From<&Color> for Oklab {
+    fn from(c: &Color) -> Self {
+        (*c).into()
+    }
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use crate::core::color::{Lab, Oklab, Rgb, Xyz};
+
+    #[test]
+    fn xyz_to_oklab() {
+        macro_rules! test_case {
+            (Xyz: $x:expr, $y:expr, $z:expr; Oklab: $l:expr, $a:expr, $b:expr) => {
+                let got = Oklab::from_xyz(Xyz::new($x, $y, $z));
+                assert!((got.l() - $l).abs() < 1e-3, "{got:?}");
+                assert!((got.a() - $a).abs() < 1e-3, "{got:?}");
+                assert!((got.b() - $b).abs() < 1e-3, "{got:?}");
+
+                let back = got.to_xyz();
+                assert!((back.x() - $x).abs() < 1e-4, "{back:?}");
+                assert!((back.y() - $y).abs() < 1e-4, "{back:?}");
+                assert!((back.z() - $z).abs() < 1e-4, "{back:?}");
+            };
+        }
+
+        test_case!(Xyz: 0.950, 1., 1.089; Oklab: 1., 0., 0.);
+        test_case!(Xyz: 1., 0., 0.; Oklab: 0.450, 1.236, -0.019);
+        test_case!(Xyz: 0., 1., 0.; Oklab: 0.922, -0.671, 0.263);
+        test_case!(Xyz: 0., 0., 1.; Oklab: 0.153, -1.415, -0.449);
+    }
+
+    #[test]
+    fn rgb_to_oklab() {
+        macro_rules! test_case {
+            (Rgb: $r:expr, $g:expr, $b:expr; Oklab: $l:expr, $a:expr, $ob:expr) => {
+                let rgb = Rgb::new_scaled($r, $g, $b);
+                let got = Oklab::from(rgb);
+                assert!((got.l() - $l).abs() < 1e-3, "{got:?}");
+                assert!((got.a() - $a).abs() < 1e-3, "{got:?}");
+                assert!((got.b() - $ob).abs() < 1e-3, "{got:?}");
+
+                let back = Rgb::from(got);
+                assert!((back.r_scaled() - $r as f32).abs() < 0.5, "{back:?}");
+                assert!((back.g_scaled() - $g as f32).abs() < 0.5, "{back:?}");
+                assert!((back.b_scaled() - $b as f32).abs() < 0.5, "{back:?}");
+            };
+        }
+
+        test_case!(Rgb: 0, 0, 0; Oklab: 0., 0., 0.);
+        test_case!(Rgb: 255, 255, 255; Oklab: 1., 0., 0.);
+        test_case!(Rgb: 255, 0, 0; Oklab: 0.62796, 0.22486, 0.12585);
+        test_case!(Rgb: 0, 255, 0; Oklab: 0.86644, -0.23389, 0.17950);
+        test_case!(Rgb: 0, 0, 255; Oklab: 0.45201, -0.03246, -0.31153);
+    }
+
+    #[test]
+    fn lab_to_oklab() {
+        let lab = Lab::from(Rgb::new(1., 0., 0.));
+        let got = Oklab::from(lab);
+        assert!((got.l() - 0.62796).abs() < 1e-3, "{got:?}");
+        assert!((got.a() - 0.22486).abs() < 1e-3, "{got:?}");
+        assert!((got.b() - 0.12585).abs() < 1e-3, "{got:?}");
+
+        let back = Lab::from(got);
+        assert!((back.l() - lab.l()).abs() < 1e-2, "{back:?}");
+        assert!((back.a() - lab.a()).abs() < 1e-2, "{back:?}");
+        assert!((back.b() - lab.b()).abs() < 1e-2, "{back:?}");
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::fmt::Write as _;
 
-use super::{format::CustomPaletteFormat, Color};
+use super::{format::CustomPaletteFormat, Color, Illuminant, RgbWorkingSpace};
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct NamedPalette {
@@ -19,54 +19,268 @@ impl Default for NamedPalette {
 }
 
 impl NamedPalette {
-    pub fn display(&self, format: &PaletteFormat) -> String {
+    /// Returns this palette formatted as text. Binary formats can't be displayed, they fall back
+    /// to a hex list.
+    pub fn display(
+        &self,
+        format: &PaletteFormat,
+        ws: RgbWorkingSpace,
+        illuminant: Illuminant,
+    ) -> String {
         match format {
             PaletteFormat::Gimp => self.palette.as_gimp_palette(&self.name),
-            PaletteFormat::HexList => self.palette.as_hex_list(),
-            PaletteFormat::Custom(_, fmt) => fmt.format_palette(&self.palette).unwrap_or_default(),
+            PaletteFormat::HexList | PaletteFormat::Ase => self.palette.as_hex_list(),
+            PaletteFormat::CssVariables => self.as_css_variables(),
+            PaletteFormat::Scss => self.as_scss_variables(),
+            PaletteFormat::Tailwind => self.as_tailwind_config(),
+            PaletteFormat::DesignTokens => self.as_design_tokens(),
+            PaletteFormat::Custom(_, fmt) => fmt
+                .format_palette(&self.palette, ws, illuminant)
+                .unwrap_or_default(),
         }
     }
+
+    /// Returns the contents of a file with this palette saved in `format`.
+    pub fn export(
+        &self,
+        format: &PaletteFormat,
+        ws: RgbWorkingSpace,
+        illuminant: Illuminant,
+    ) -> Vec<u8> {
+        match format {
+            PaletteFormat::Ase => self.as_ase(),
+            _ => self.display(format, ws, illuminant).into_bytes(),
+        }
+    }
+
+    /// Returns the name of this palette usable as an identifier in CSS, SCSS and JavaScript.
+    pub fn slug(&self) -> String {
+        slugify(&self.name).unwrap_or_else(|| "color".into())
+    }
+
+    /// Returns the name of every color, its own name or else the palette slug followed by the
+    /// 1-based position.
+    pub fn color_names(&self) -> Vec<String> {
+        let slug = self.slug();
+        self.palette
+            .entries()
+            .enumerate()
+            .map(|(i, entry)| match &entry.name {
+                Some(name) => name.clone(),
+                None => format!("{slug}-{}", i + 1),
+            })
+            .collect()
+    }
+
+    /// Returns the color names usable as CSS and SCSS variables.
+    fn variable_names(&self) -> Vec<String> {
+        let slug = self.slug();
+        self.palette
+            .entries()
+            .enumerate()
+            .map(|(i, entry)| {
+                entry
+                    .name
+                    .as_deref()
+                    .and_then(slugify)
+                    .unwrap_or_else(|| format!("{slug}-{}", i + 1))
+            })
+            .collect()
+    }
+
+    /// Returns the keys of the colors within the palette group of Tailwind and design tokens.
+    fn group_keys(&self) -> Vec<String> {
+        self.palette
+            .entries()
+            .enumerate()
+            .map(|(i, entry)| {
+                entry
+                    .name
+                    .as_deref()
+                    .and_then(slugify)
+                    .unwrap_or_else(|| (i + 1).to_string())
+            })
+            .collect()
+    }
+
+    pub fn as_css_variables(&self) -> String {
+        let mut css = String::from(":root {\n");
+        for (name, color) in self.variable_names().iter().zip(self.palette.iter()) {
+            let _ = writeln!(css, "  --{name}: {};", color.as_hex());
+        }
+        css.push_str("}\n");
+        css
+    }
+
+    pub fn as_scss_variables(&self) -> String {
+        self.variable_names().iter().zip(self.palette.iter()).fold(
+            String::new(),
+            |mut scss, (name, color)| {
+                let _ = writeln!(scss, "${name}: {};", color.as_hex());
+                scss
+            },
+        )
+    }
+
+    /// Returns a Tailwind config with this palette as `theme.colors`, so that the colors are
+    /// available as e.g. `bg-brand-1`.
+    pub fn as_tailwind_config(&self) -> String {
+        let mut js = String::from("module.exports = {\n  theme: {\n    colors: {\n");
+        let _ = writeln!(js, "      '{}': {{", self.slug());
+        for (key, color) in self.group_keys().iter().zip(self.palette.iter()) {
+            let _ = writeln!(js, "        '{key}': '{}',", color.as_hex());
+        }
+        js.push_str("      },\n    },\n  },\n};\n");
+        js
+    }
+
+    /// Returns this palette as a group of W3C Design Tokens.
+    pub fn as_design_tokens(&self) -> String {
+        let group = serde_json::Value::from(self.slug()).to_string();
+        let mut json = format!("{{\n  {group}: {{\n");
+        for (i, (key, color)) in self
+            .group_keys()
+            .iter()
+            .zip(self.palette.iter())
+            .enumerate()
+        {
+            if i > 0 {
+                json.push_str(",\n");
+            }
+            let _ = write!(
+                json,
+                "    {}: {{ \"$type\": \"color\", \"$value\": \"{}\" }}",
+                serde_json::Value::from(key.as_str()),
+                color.as_hex()
+            );
+        }
+        if !self.palette.is_empty() {
+            json.push('\n');
+        }
+        json.push_str("  }\n}\n");
+        json
+    }
+
+    /// Returns this palette as an Adobe Swatch Exchange file with a single color group. ASE has no
+    /// alpha channel so the colors are saved as opaque.
+    pub fn as_ase(&self) -> Vec<u8> {
+        fn utf16_name(out: &mut Vec<u8>, name: &str) {
+            let units: Vec<u16> = name.encode_utf16().chain([0]).collect();
+            out.extend((units.len() as u16).to_be_bytes());
+            for unit in units {
+                out.extend(unit.to_be_bytes());
+            }
+        }
+        fn block(out: &mut Vec<u8>, kind: u16, body: &[u8]) {
+            out.extend(kind.to_be_bytes());
+            out.extend((body.len() as u32).to_be_bytes());
+            out.extend(body);
+        }
+
+        let mut ase = b"ASEF".to_vec();
+        ase.extend(1u16.to_be_bytes());
+        ase.extend(0u16.to_be_bytes());
+        ase.extend((self.palette.len() as u32 + 2).to_be_bytes());
+
+        let mut group = vec![];
+        utf16_name(&mut group, &self.name);
+        block(&mut ase, 0xc001, &group);
+
+        for (name, color) in self.color_names().iter().zip(self.palette.iter()) {
+            let rgb = color.rgb();
+            let mut entry = vec![];
+            utf16_name(&mut entry, name);
+            entry.extend(b"RGB ");
+            for channel in [rgb.r(), rgb.g(), rgb.b()] {
+                entry.extend(channel.to_be_bytes());
+            }
+            // normal (non global, non spot) color
+            entry.extend(2u16.to_be_bytes());
+            block(&mut ase, 0x0001, &entry);
+        }
+
+        block(&mut ase, 0xc002, &[]);
+        ase
+    }
+}
+
+/// Returns `name` in lowercase with every run of other characters than letters and digits
+/// replaced by a dash, `None` when nothing is left.
+fn slugify(name: &str) -> Option<String> {
+    let mut slug = String::with_capacity(name.len());
+    for ch in name.chars().flat_map(char::to_lowercase) {
+        if ch.is_alphanumeric() {
+            slug.push(ch);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    (!slug.is_empty()).then_some(slug)
+}
+
+/// A color of a palette with the name it was given, e.g. in an imported palette file.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct PaletteEntry {
+    #[serde(flatten)]
+    pub color: Color,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl From<Color> for PaletteEntry {
+    fn from(color: Color) -> Self {
+        Self { color, name: None }
+    }
 }
 
 #[derive(Clone, Default, Debug, Deserialize, Serialize, PartialEq)]
-pub struct Palette(Vec<Color>);
+pub struct Palette(Vec<PaletteEntry>);
 
 impl Palette {
     pub fn iter(&self) -> impl Iterator<Item = &Color> {
+        self.0.iter().map(|entry| &entry.color)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &PaletteEntry> {
         self.0.iter()
     }
-    pub fn add(&mut self, color: Color) -> bool {
-        if !self
-            .0
-            .iter()
+
+    fn contains(&self, color: &Color) -> bool {
+        self.iter()
             .any(|clr| clr.as_rgb_triplet_scaled() == color.as_rgb_triplet_scaled())
-        {
-            self.0.push(color);
+    }
+
+    pub fn add(&mut self, color: Color) -> bool {
+        self.add_named(color, None)
+    }
+
+    pub fn add_named(&mut self, color: Color, name: Option<String>) -> bool {
+        if !self.contains(&color) {
+            self.0.push(PaletteEntry { color, name });
             return true;
         }
         false
     }
 
     pub fn insert(&mut self, i: usize, color: Color) {
-        if !self
-            .0
-            .iter()
-            .any(|clr| clr.as_rgb_triplet_scaled() == color.as_rgb_triplet_scaled())
-        {
-            self.0.insert(i, color);
+        if !self.contains(&color) {
+            self.0.insert(i, color.into());
         }
     }
 
     pub fn remove(&mut self, color: &Color) -> Option<Color> {
         self.0
             .iter()
-            .position(|clr| clr.as_rgb_triplet_scaled() == color.as_rgb_triplet_scaled())
-            .map(|i| self.0.remove(i))
+            .position(|entry| entry.color.as_rgb_triplet_scaled() == color.as_rgb_triplet_scaled())
+            .map(|i| self.0.remove(i).color)
     }
 
     pub fn remove_pos(&mut self, i: usize) -> Option<Color> {
         if i < self.0.len() {
-            Some(self.0.remove(i))
+            Some(self.0.remove(i).color)
         } else {
             None
         }
@@ -84,24 +298,25 @@ impl Palette {
         self.0.is_empty()
     }
 
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
     pub fn as_gimp_palette(&self, name: &str) -> String {
         let mut gpl = format!("GIMP Palette\nName: {}.gpl\nColumns: 1\n#\n", name);
-        for (i, color) in self.0.iter().enumerate() {
-            let color = color.color32();
-            let _ = writeln!(
-                gpl,
-                "{}\t{}\t{}\tcolor {}",
-                color.r(),
-                color.g(),
-                color.b(),
-                i
-            );
+        for (i, entry) in self.0.iter().enumerate() {
+            let color = entry.color.color32();
+            let _ = write!(gpl, "{}\t{}\t{}\t", color.r(), color.g(), color.b());
+            let _ = match &entry.name {
+                Some(name) => writeln!(gpl, "{name}"),
+                None => writeln!(gpl, "color {i}"),
+            };
         }
         gpl
     }
 
     pub fn as_hex_list(&self) -> String {
-        self.0.iter().fold(String::new(), |mut s, color| {
+        self.iter().fold(String::new(), |mut s, color| {
             s.push_str(&color.as_hex());
             s.push('\n');
             s
@@ -111,6 +326,12 @@ impl Palette {
 
 impl std::iter::FromIterator<Color> for Palette {
     fn from_iter<T: IntoIterator<Item = Color>>(iter: T) -> Self {
+        Self(iter.into_iter().map(PaletteEntry::from).collect())
+    }
+}
+
+impl std::iter::FromIterator<PaletteEntry> for Palette {
+    fn from_iter<T: IntoIterator<Item = PaletteEntry>>(iter: T) -> Self {
         Self(iter.into_iter().collect())
     }
 }
@@ -119,6 +340,11 @@ impl std::iter::FromIterator<Color> for Palette {
 pub enum PaletteFormat {
     Gimp,
     HexList,
+    Ase,
+    CssVariables,
+    Scss,
+    Tailwind,
+    DesignTokens,
     Custom(String, CustomPaletteFormat),
 }
 
@@ -133,18 +359,46 @@ impl AsRef<str> for PaletteFormat {
         match self {
             PaletteFormat::Gimp => "GIMP (gpl)",
             PaletteFormat::HexList => "Hex list",
+            PaletteFormat::Ase => "Adobe swatch exchange (ase)",
+            PaletteFormat::CssVariables => "CSS variables",
+            PaletteFormat::Scss => "SCSS variables",
+            PaletteFormat::Tailwind => "Tailwind config",
+            PaletteFormat::DesignTokens => "Design tokens (json)",
             PaletteFormat::Custom(name, _) => name,
         }
     }
 }
 
 impl PaletteFormat {
+    /// Returns every format that doesn't need a user defined template.
+    pub fn builtin() -> [PaletteFormat; 7] {
+        [
+            PaletteFormat::Gimp,
+            PaletteFormat::HexList,
+            PaletteFormat::Ase,
+            PaletteFormat::CssVariables,
+            PaletteFormat::Scss,
+            PaletteFormat::Tailwind,
+            PaletteFormat::DesignTokens,
+        ]
+    }
+
     pub fn extension(&self) -> &str {
         match self {
             PaletteFormat::Gimp => "gpl",
+            PaletteFormat::Ase => "ase",
+            PaletteFormat::CssVariables => "css",
+            PaletteFormat::Scss => "scss",
+            PaletteFormat::Tailwind => "js",
+            PaletteFormat::DesignTokens => "json",
             _ => "txt",
         }
     }
+
+    /// Whether palettes in this format can't be represented as text, e.g. to be copied.
+    pub fn is_binary(&self) -> bool {
+        matches!(self, PaletteFormat::Ase)
+    }
 }
 
 #[cfg(test)]
@@ -180,4 +434,131 @@ Columns: 1
 
         assert_eq!(colors.as_gimp_palette("colors"), want);
     }
+
+    fn brand_palette() -> NamedPalette {
+        let mut palette = Palette::default();
+        palette.add(Rgb::new_scaled(255, 0, 0).into());
+        palette.add(Rgb::new_scaled(120, 30, 80).with_alpha(0.5).into());
+        NamedPalette {
+            name: "My Brand!".into(),
+            palette,
+        }
+    }
+
+    #[test]
+    fn derives_color_names() {
+        let palette = brand_palette();
+        assert_eq!(palette.slug(), "my-brand");
+        assert_eq!(palette.color_names(), ["my-brand-1", "my-brand-2"]);
+
+        let unnamed = NamedPalette {
+            name: " -- ".into(),
+            palette: Palette::default(),
+        };
+        assert_eq!(unnamed.slug(), "color");
+    }
+
+    #[test]
+    fn export_named_colors() {
+        let mut palette = brand_palette();
+        palette
+            .palette
+            .add_named(Rgb::new_scaled(18, 52, 86).into(), Some("Deep Navy".into()));
+        assert_eq!(
+            palette.color_names(),
+            ["my-brand-1", "my-brand-2", "Deep Navy"]
+        );
+
+        assert!(palette
+            .as_css_variables()
+            .contains("  --deep-navy: #123456;\n"));
+        assert!(palette
+            .as_scss_variables()
+            .ends_with("$deep-navy: #123456;\n"));
+        assert!(palette
+            .as_tailwind_config()
+            .contains("        '2': '#781e5080',\n        'deep-navy': '#123456',\n"));
+        let tokens: serde_json::Value = serde_json::from_str(&palette.as_design_tokens()).unwrap();
+        assert_eq!(tokens["my-brand"]["deep-navy"]["$value"], "#123456");
+        assert!(palette
+            .palette
+            .as_gimp_palette("brand")
+            .ends_with("18\t52\t86\tDeep Navy\n"));
+        let ase = palette.export(&PaletteFormat::Ase, RgbWorkingSpace::SRGB, Illuminant::D65);
+        let name: Vec<u8> = "Deep Navy\0"
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect();
+        assert!(ase.windows(name.len()).any(|window| window == name));
+    }
+
+    #[test]
+    fn loads_palettes_saved_without_names() {
+        let palette: Palette =
+            serde_json::from_str(r#"[{"Rgb":{"r":1.0,"g":0.0,"b":0.0,"a":1.0}}]"#).unwrap();
+        assert_eq!(palette.entries().next().unwrap().name, None);
+        assert_eq!(palette.len(), 1);
+
+        let mut named = Palette::default();
+        named.add_named(Rgb::new_scaled(255, 0, 0).into(), Some("red".into()));
+        let json = serde_json::to_string(&named).unwrap();
+        assert_eq!(serde_json::from_str::<Palette>(&json).unwrap(), named);
+    }
+
+    #[test]
+    fn export_text_formats() {
+        let palette = brand_palette();
+
+        let want = r#":root {
+  --my-brand-1: #ff0000;
+  --my-brand-2: #781e5080;
+}
+"#;
+        assert_eq!(palette.as_css_variables(), want);
+
+        let want = r#"$my-brand-1: #ff0000;
+$my-brand-2: #781e5080;
+"#;
+        assert_eq!(palette.as_scss_variables(), want);
+
+        let want = r#"module.exports = {
+  theme: {
+    colors: {
+      'my-brand': {
+        '1': '#ff0000',
+        '2': '#781e5080',
+      },
+    },
+  },
+};
+"#;
+        assert_eq!(palette.as_tailwind_config(), want);
+
+        let tokens: serde_json::Value = serde_json::from_str(&palette.as_design_tokens()).unwrap();
+        assert_eq!(
+            tokens,
+            serde_json::json!({
+                "my-brand": {
+                    "1": { "$type": "color", "$value": "#ff0000" },
+                    "2": { "$type": "color", "$value": "#781e5080" },
+                }
+            })
+        );
+
+        let empty = NamedPalette::default();
+        let tokens: serde_json::Value = serde_json::from_str(&empty.as_design_tokens()).unwrap();
+        assert_eq!(tokens, serde_json::json!({ "palette": {} }));
+    }
+
+    #[test]
+    fn export_ase() {
+        let palette = brand_palette();
+        let ase = palette.export(&PaletteFormat::Ase, RgbWorkingSpace::SRGB, Illuminant::D65);
+        assert_eq!(&ase[..12], b"ASEF\0\x01\0\0\0\0\0\x04");
+        assert!(PaletteFormat::Ase.is_binary());
+        assert_eq!(
+            palette.display(&PaletteFormat::Ase, RgbWorkingSpace::SRGB, Illuminant::D65),
+            palette.palette.as_hex_list()
+        );
+    }
 }
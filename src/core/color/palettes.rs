@@ -102,6 +102,18 @@ impl Palettes {
         false
     }
 
+    /// Adds `palette` as the last palette, appending a number to its name if a palette with the
+    /// same name already exists.
+    pub fn append(&mut self, mut palette: NamedPalette) {
+        let base = palette.name.clone();
+        let mut n = 2;
+        while self.palettes.iter().any(|p| p.name == palette.name) {
+            palette.name = format!("{base} ({n})");
+            n += 1;
+        }
+        self.palettes.push(palette);
+    }
+
     pub fn insert(&mut self, i: usize, palette: NamedPalette) {
         if !self.palettes.iter().any(|p| p.name == palette.name) {
             self.palettes.insert(i, palette);
@@ -321,6 +333,12 @@ mod tests {
             palette: Palette::default(),
         };
         assert_eq!(palettes.current(), &p);
+
+        palettes.append(p.clone());
+        palettes.append(p.clone());
+        assert_eq!(palettes.len(), 4);
+        assert_eq!(palettes[2].name, "palette0 (2)");
+        assert_eq!(palettes[3].name, "palette0 (3)");
     }
 
     #[test]
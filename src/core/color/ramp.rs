@@ -0,0 +1,211 @@
+//! Tailwind style 50 ..= 950 shade ramps built around a base color in Oklch.
+//!
+//! The base color is kept as is at the step whose reference lightness is closest to its own, the
+//! other steps follow the reference lightness curve bent towards the base. Chroma tapers off
+//! towards both ends and every shade is brought into the sRGB gamut by lowering its chroma.
+
+use super::{contrast::Contrast, palette::NamedPalette, Color, Oklab, Oklch, Rgb};
+
+/// Names of the steps of a ramp.
+pub const SHADE_STEPS: [u16; 11] = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950];
+
+/// Reference Oklch lightness of every step.
+const LIGHTNESS: [f32; 11] = [
+    0.971, 0.936, 0.885, 0.808, 0.711, 0.637, 0.577, 0.505, 0.444, 0.396, 0.258,
+];
+
+/// Reference chroma of every step relative to the most saturated one.
+const CHROMA: [f32; 11] = [
+    0.05, 0.13, 0.26, 0.47, 0.78, 0.97, 1., 0.87, 0.72, 0.58, 0.38,
+];
+
+/// Hues that the light and the dark end are shifted towards, yellow and blue in Oklch.
+const WARM_HUE: f32 = 90.;
+const COOL_HUE: f32 = 265.;
+
+/// A single step of a [`ShadeRamp`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shade {
+    pub step: u16,
+    pub color: Color,
+    pub on_white: Contrast,
+    pub on_black: Contrast,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShadeRamp {
+    pub base: Color,
+    /// Maximum hue rotation in degrees at the ends of the ramp, the light end moves towards warm
+    /// and the dark end towards cool hues.
+    pub hue_shift: f32,
+}
+
+impl ShadeRamp {
+    pub fn new(base: Color) -> Self {
+        Self {
+            base,
+            hue_shift: 0.,
+        }
+    }
+
+    pub fn with_hue_shift(mut self, hue_shift: f32) -> Self {
+        self.hue_shift = hue_shift;
+        self
+    }
+
+    /// Returns the index into [`SHADE_STEPS`] at which the base color ends up.
+    pub fn base_index(&self) -> usize {
+        let l = self.base.oklch().l();
+        (0..LIGHTNESS.len())
+            .min_by(|&a, &b| {
+                (LIGHTNESS[a] - l)
+                    .abs()
+                    .total_cmp(&(LIGHTNESS[b] - l).abs())
+            })
+            .unwrap_or(5)
+    }
+
+    pub fn shades(&self) -> Vec<Shade> {
+        let base = self.base.oklch();
+        let anchor = self.base_index();
+        let last = SHADE_STEPS.len() - 1;
+        let offset = base.l() - LIGHTNESS[anchor];
+
+        SHADE_STEPS
+            .iter()
+            .enumerate()
+            .map(|(i, &step)| {
+                let color = if i == anchor {
+                    self.base
+                } else {
+                    // 0.0 at the base and 1.0 at the end of the ramp on the same side
+                    let distance = if i < anchor {
+                        (anchor - i) as f32 / anchor as f32
+                    } else {
+                        (i - anchor) as f32 / (last - anchor) as f32
+                    };
+                    let l = LIGHTNESS[i] + offset * (1. - distance);
+                    let c = base.c() * CHROMA[i] / CHROMA[anchor];
+                    let target = if i < anchor { WARM_HUE } else { COOL_HUE };
+                    let h = rotate_towards(base.h(), target, self.hue_shift * distance);
+                    into_gamut(l, c, h).with_alpha(self.base.alpha()).into()
+                };
+
+                Shade {
+                    step,
+                    color,
+                    on_white: Contrast::new(&color, &Color::white()),
+                    on_black: Contrast::new(&color, &Color::black()),
+                }
+            })
+            .collect()
+    }
+
+    pub fn colors(&self) -> Vec<Color> {
+        self.shades().into_iter().map(|shade| shade.color).collect()
+    }
+
+    pub fn as_palette(&self, name: impl Into<String>) -> NamedPalette {
+        NamedPalette {
+            name: name.into(),
+            palette: self.colors().into_iter().collect(),
+        }
+    }
+}
+
+/// Rotates `hue` by at most `degrees` along the shorter path towards `target`.
+fn rotate_towards(hue: f32, target: f32, degrees: f32) -> f32 {
+    let delta = (target - hue + 180.).rem_euclid(360.) - 180.;
+    (hue + delta.clamp(-degrees.abs(), degrees.abs())).rem_euclid(360.)
+}
+
+/// Returns the color with the highest chroma up to `c` that fits in sRGB.
+pub(super) fn into_gamut(l: f32, c: f32, h: f32) -> Oklch {
+    let l = l.clamp(0., 1.);
+    let fits = |c: f32| {
+        let oklch = Oklch::new(l, c, h);
+        let mapped = Oklab::from(Rgb::from(Oklab::from(oklch)));
+        let oklab = Oklab::from(oklch);
+        (mapped.l() - oklab.l()).abs() < 1e-3
+            && (mapped.a() - oklab.a()).abs() < 1e-3
+            && (mapped.b() - oklab.b()).abs() < 1e-3
+    };
+    if fits(c) {
+        return Oklch::new(l, c, h);
+    }
+
+    let (mut inside, mut outside) = (0., c);
+    for _ in 0..16 {
+        let mid = (inside + outside) / 2.;
+        if fits(mid) {
+            inside = mid;
+        } else {
+            outside = mid;
+        }
+    }
+    Oklch::new(l, inside, h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_base_color() {
+        macro_rules! test_case {
+            ($hex:literal => $step:literal) => {
+                let base = Color::from_hex($hex).unwrap();
+                let ramp = ShadeRamp::new(base);
+                let shades = ramp.shades();
+                let shade = shades.iter().find(|shade| shade.color == base).unwrap();
+                assert_eq!(shade.step, $step, "{}", $hex);
+                assert_eq!(SHADE_STEPS[ramp.base_index()], $step, "{}", $hex);
+            };
+        }
+
+        test_case!("3b82f6" => 500);
+        test_case!("fef2f2" => 50);
+        test_case!("111111" => 950);
+    }
+
+    #[test]
+    fn generates_ramp() {
+        let base = Color::from_hex("3b82f6").unwrap();
+        for hue_shift in [0., 20.] {
+            let shades = ShadeRamp::new(base).with_hue_shift(hue_shift).shades();
+            assert_eq!(shades.len(), 11);
+            assert_eq!(
+                shades.iter().map(|shade| shade.step).collect::<Vec<_>>(),
+                SHADE_STEPS
+            );
+
+            // lighter steps come first and contrast is reported against both backgrounds
+            for pair in shades.windows(2) {
+                assert!(pair[0].color.oklch().l() > pair[1].color.oklch().l());
+                assert!(pair[0].on_white.ratio < pair[1].on_white.ratio);
+                assert!(pair[0].on_black.ratio > pair[1].on_black.ratio);
+            }
+            assert!(shades[0].on_black.aaa_normal());
+            assert!(shades[10].on_white.aaa_normal());
+        }
+
+        let palette = ShadeRamp::new(base).as_palette("blue");
+        assert_eq!(palette.name, "blue");
+        assert_eq!(palette.palette.len(), 11);
+    }
+
+    #[test]
+    fn shifts_hue() {
+        let base = Color::from_hex("3b82f6").unwrap();
+        let h = base.oklch().h();
+        let shades = ShadeRamp::new(base).with_hue_shift(20.).shades();
+        let light = shades[0].color.oklch().h();
+        let dark = shades[10].color.oklch().h();
+        // blue moves towards yellow when lighter and stays close to blue when darker
+        assert!(light < h - 10., "{light}");
+        assert!((dark - h).abs() <= 20.5, "{dark}");
+
+        assert_eq!(rotate_towards(350., 10., 5.), 355.);
+        assert_eq!(rotate_towards(10., 350., 30.), 350.);
+    }
+}
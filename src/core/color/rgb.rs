@@ -13,6 +13,8 @@ pub struct Rgb {
     r: f32,
     g: f32,
     b: f32,
+    #[serde(default = "super::opaque")]
+    a: f32,
 }
 
 impl Rgb {
@@ -44,6 +46,7 @@ impl Rgb {
             r: red,
             g: green,
             b: blue,
+            a: 1.,
         }
     }
 
@@ -52,9 +55,16 @@ impl Rgb {
             r: red,
             g: green,
             b: blue,
+            a: 1.,
         }
     }
 
+    /// Returns this color with Alpha set to `alpha` in the range 0.0 ..= 1.0
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.a = super::clamp_alpha(alpha);
+        self
+    }
+
     pub fn new_scaled(red: u8, green: u8, blue: u8) -> Self {
         Self::new(
             red as f32 / U8_MAX,
@@ -81,6 +91,12 @@ impl Rgb {
         self.b
     }
 
+    #[inline(always)]
+    /// Returns Alpha value in the range 0.0 ..= 1.0
+    pub fn alpha(&self) -> f32 {
+        self.a
+    }
+
     #[inline(always)]
     /// Returns Red value in the range 0.0 ..= 255.0
     pub fn r_scaled(&self) -> f32 {
@@ -99,6 +115,12 @@ impl Rgb {
         self.b * U8_MAX
     }
 
+    #[inline(always)]
+    /// Returns Alpha value in the range 0.0 ..= 255.0
+    pub fn alpha_scaled(&self) -> f32 {
+        self.a * U8_MAX
+    }
+
     pub fn gamma_compand(mut self, gamma: f32) -> Rgb {
         self.r = self.r.powf(1. / gamma);
         self.g = self.g.powf(1. / gamma);
@@ -176,22 +198,20 @@ impl Rgb {
 
 impl From<Rgb> for Color32 {
     fn from(rgb: Rgb) -> Self {
-        Color32::from_rgba_premultiplied(
-            rgb.r_scaled() as u8,
-            rgb.g_scaled() as u8,
-            rgb.b_scaled() as u8,
-            u8::MAX,
+        Color32::from_rgba_unmultiplied(
+            rgb.r_scaled().round() as u8,
+            rgb.g_scaled().round() as u8,
+            rgb.b_scaled().round() as u8,
+            rgb.alpha_scaled().round() as u8,
         )
     }
 }
 
 impl From<Color32> for Rgb {
     fn from(color: Color32) -> Self {
-        Self::new(
-            color.r() as f32 / U8_MAX,
-            color.g() as f32 / U8_MAX,
-            color.b() as f32 / U8_MAX,
-        )
+        let [r, g, b, a] = color.to_srgba_unmultiplied();
+        Self::new(r as f32 / U8_MAX, g as f32 / U8_MAX, b as f32 / U8_MAX)
+            .with_alpha(a as f32 / U8_MAX)
     }
 }
 
@@ -235,6 +255,10 @@ impl From<Color> for Rgb {
             Color::Hsv(c) => c.into(),
             Color::Hsl(c) => c.into(),
             Color::Color32(c) => c.into(),
+            Color::Lab(c) => c.into(),
+            Color::Lch(c) => c.into(),
+            Color::Oklab(c) => c.into(),
+            Color::Oklch(c) => c.into(),
         }
     }
 }
@@ -251,7 +275,7 @@ impl From<Cmyk> for Rgb {
         let r = (1. - cmyk.c()) * (1. - k);
         let g = (1. - cmyk.m()) * (1. - k);
         let b = (1. - cmyk.y()) * (1. - k);
-        Rgb::new(r, g, b)
+        Rgb::new(r, g, b).with_alpha(cmyk.alpha())
     }
 }
 
@@ -273,7 +297,7 @@ impl From<Hsv> for Rgb {
         let q = v * (1. - f * s);
         let t = v * (1. - (1. - f) * s);
 
-        match h.floor() as i32 % 6 {
+        let rgb = match h.floor() as i32 % 6 {
             0 => Rgb::new(v,  t,  p ),
             1 => Rgb::new(q,  v,  p ),
             2 => Rgb::new(p,  v,  t ),
@@ -281,7 +305,8 @@ impl From<Hsv> for Rgb {
             4 => Rgb::new(t,  p,  v ),
             5 => Rgb::new(v,  p,  q ),
             _ => Rgb::new(0., 0., 0.),
-        }
+        };
+        rgb.with_alpha(hsv.alpha())
     }
 }
 
@@ -304,6 +329,7 @@ mod tests {
     use crate::core::color::Cmyk;
     use crate::core::color::Hsv;
     use crate::{core::color::Rgb, utils::math::eq_f32};
+    use eframe::egui::Color32;
 
     #[test]
     fn rgb_scaled_init() {
@@ -316,6 +342,13 @@ mod tests {
         assert_eq!(rgb.b_scaled() as u32, 0);
     }
 
+    #[test]
+    fn rgb_to_color32_rounds() {
+        // colors converted back from other spaces end up just below the channel value
+        let rgb = Rgb::new(0.99999, 0.4, 0.00001);
+        assert_eq!(Color32::from(rgb), Color32::from_rgb(255, 102, 0));
+    }
+
     #[test]
     fn cmyk_to_rgb() {
         macro_rules! test_case {
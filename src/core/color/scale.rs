@@ -0,0 +1,357 @@
+//! Color scales interpolating between any number of evenly spaced stops.
+//!
+//! Unlike [`Gradient`](super::gradient::Gradient), which only feeds slider textures, a scale can
+//! interpolate in a perceptually uniform space and be sampled into a fixed number of steps for
+//! data visualization ramps.
+
+use serde::{Deserialize, Serialize};
+
+use super::{palette::NamedPalette, Color, Lab, Oklab, Oklch, Rgb};
+
+/// Below this Oklch chroma the hue of a color is meaningless and is taken from the other stop.
+const ACHROMATIC_CHROMA: f32 = 1e-4;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum InterpolationSpace {
+    LinearRgb,
+    Lab,
+    #[default]
+    Oklab,
+    Oklch,
+}
+
+impl InterpolationSpace {
+    pub fn all() -> [Self; 4] {
+        [Self::LinearRgb, Self::Lab, Self::Oklab, Self::Oklch]
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::LinearRgb => "Linear RGB",
+            Self::Lab => "CIELAB",
+            Self::Oklab => "Oklab",
+            Self::Oklch => "Oklch",
+        }
+    }
+}
+
+/// The way hues are interpolated in polar spaces, see CSS Color Level 4.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum HuePath {
+    #[default]
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+impl HuePath {
+    pub fn all() -> [Self; 4] {
+        [
+            Self::Shorter,
+            Self::Longer,
+            Self::Increasing,
+            Self::Decreasing,
+        ]
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Shorter => "Shorter",
+            Self::Longer => "Longer",
+            Self::Increasing => "Increasing",
+            Self::Decreasing => "Decreasing",
+        }
+    }
+
+    /// Returns the hue at `t` going from `from` to `to` in degrees along this path.
+    pub fn interpolate(self, from: f32, to: f32, t: f32) -> f32 {
+        let from = from.rem_euclid(360.);
+        let to = to.rem_euclid(360.);
+        let mut delta = to - from;
+        match self {
+            Self::Shorter if delta > 180. => delta -= 360.,
+            Self::Shorter if delta < -180. => delta += 360.,
+            Self::Longer if 0. < delta && delta < 180. => delta -= 360.,
+            Self::Longer if -180. < delta && delta <= 0. => delta += 360.,
+            Self::Increasing if delta < 0. => delta += 360.,
+            Self::Decreasing if delta > 0. => delta -= 360.,
+            _ => {}
+        }
+        (from + delta * t).rem_euclid(360.)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn all() -> [Self; 4] {
+        [Self::Linear, Self::EaseIn, Self::EaseOut, Self::EaseInOut]
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Linear => "Linear",
+            Self::EaseIn => "Ease in",
+            Self::EaseOut => "Ease out",
+            Self::EaseInOut => "Ease in-out",
+        }
+    }
+
+    /// Maps `t` in the range of 0.0 ..= 1.0 with a cubic curve.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1. - (1. - t).powi(3),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4. * t * t * t
+                } else {
+                    1. - (-2. * t + 2.).powi(3) / 2.
+                }
+            }
+        }
+    }
+}
+
+//####################################################################################################
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ColorScale {
+    pub stops: Vec<Color>,
+    pub space: InterpolationSpace,
+    /// Only used when interpolating in [`InterpolationSpace::Oklch`].
+    pub hue_path: HuePath,
+    /// Applied to the position along the whole scale.
+    pub easing: Easing,
+}
+
+impl ColorScale {
+    pub fn new(stops: impl IntoIterator<Item = Color>) -> Self {
+        Self {
+            stops: stops.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_space(mut self, space: InterpolationSpace) -> Self {
+        self.space = space;
+        self
+    }
+
+    pub fn with_hue_path(mut self, hue_path: HuePath) -> Self {
+        self.hue_path = hue_path;
+        self
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns the color at position `t` in the range of 0.0 ..= 1.0, or `None` if the scale has
+    /// no stops.
+    pub fn at(&self, t: f32) -> Option<Color> {
+        let last = self.stops.len().checked_sub(1)?;
+        if last == 0 {
+            return Some(self.stops[0]);
+        }
+
+        let position = self.easing.apply(t) * last as f32;
+        let idx = (position.floor() as usize).min(last - 1);
+        Some(self.mix(
+            &self.stops[idx],
+            &self.stops[idx + 1],
+            position - idx as f32,
+        ))
+    }
+
+    /// Samples `steps` evenly spaced colors including both ends of the scale.
+    pub fn colors(&self, steps: usize) -> Vec<Color> {
+        match steps {
+            0 => vec![],
+            1 => self.at(0.).into_iter().collect(),
+            _ => (0..steps)
+                .filter_map(|i| self.at(i as f32 / (steps - 1) as f32))
+                .collect(),
+        }
+    }
+
+    /// Returns a CSS `linear-gradient()` going to the right through `steps` sampled colors.
+    pub fn as_css_linear_gradient(&self, steps: usize) -> String {
+        let colors = self.colors(steps.max(2));
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        let stops = colors
+            .iter()
+            .enumerate()
+            .map(|(i, color)| {
+                let percent = i as f32 / last * 100.;
+                format!("{} {}%", color.as_hex(), (percent * 100.).round() / 100.)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("linear-gradient(to right, {stops})")
+    }
+
+    pub fn as_hex_list(&self, steps: usize) -> String {
+        self.colors(steps)
+            .iter()
+            .map(Color::as_hex)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn as_palette(&self, steps: usize, name: impl Into<String>) -> NamedPalette {
+        NamedPalette {
+            name: name.into(),
+            palette: self.colors(steps).into_iter().collect(),
+        }
+    }
+
+    fn mix(&self, from: &Color, to: &Color, t: f32) -> Color {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let alpha = lerp(from.alpha(), to.alpha());
+
+        match self.space {
+            InterpolationSpace::LinearRgb => {
+                let from = from.rgb().inverse_srgb_compand();
+                let to = to.rgb().inverse_srgb_compand();
+                Rgb::new(
+                    lerp(from.r(), to.r()),
+                    lerp(from.g(), to.g()),
+                    lerp(from.b(), to.b()),
+                )
+                .srgb_compand()
+                .with_alpha(alpha)
+                .into()
+            }
+            InterpolationSpace::Lab => {
+                let from = Lab::from(from.rgb());
+                let to = Lab::from(to.rgb());
+                let lab = Lab::new(
+                    lerp(from.l(), to.l()),
+                    lerp(from.a(), to.a()),
+                    lerp(from.b(), to.b()),
+                );
+                Rgb::from(lab).with_alpha(alpha).into()
+            }
+            InterpolationSpace::Oklab => {
+                let from = from.oklab();
+                let to = to.oklab();
+                Oklab::new(
+                    lerp(from.l(), to.l()),
+                    lerp(from.a(), to.a()),
+                    lerp(from.b(), to.b()),
+                )
+                .with_alpha(alpha)
+                .into()
+            }
+            InterpolationSpace::Oklch => {
+                let from = from.oklch();
+                let to = to.oklch();
+                let (from_h, to_h) =
+                    match (from.c() < ACHROMATIC_CHROMA, to.c() < ACHROMATIC_CHROMA) {
+                        (true, false) => (to.h(), to.h()),
+                        (false, true) => (from.h(), from.h()),
+                        _ => (from.h(), to.h()),
+                    };
+                Oklch::new(
+                    lerp(from.l(), to.l()),
+                    lerp(from.c(), to.c()),
+                    self.hue_path.interpolate(from_h, to_h, t),
+                )
+                .with_alpha(alpha)
+                .into()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_hue_paths() {
+        macro_rules! test_case {
+            ($path:ident, $from:literal => $to:literal, $t:literal, $want:literal) => {
+                let hue = HuePath::$path.interpolate($from, $to, $t);
+                assert!((hue - $want).abs() < 1e-3, "{:?}: {hue}", stringify!($path));
+            };
+        }
+
+        test_case!(Shorter, 350. => 10., 0.5, 0.);
+        test_case!(Shorter, 10. => 350., 0.5, 0.);
+        test_case!(Longer, 350. => 10., 0.5, 180.);
+        test_case!(Longer, 0. => 90., 0.5, 225.);
+        test_case!(Increasing, 350. => 10., 0.5, 0.);
+        test_case!(Increasing, 10. => 350., 0.5, 180.);
+        test_case!(Decreasing, 350. => 10., 0.5, 180.);
+        test_case!(Decreasing, 10. => 350., 0.5, 0.);
+    }
+
+    #[test]
+    fn eases() {
+        for easing in Easing::all() {
+            assert_eq!(easing.apply(0.), 0., "{easing:?}");
+            assert_eq!(easing.apply(1.), 1., "{easing:?}");
+        }
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+    }
+
+    #[test]
+    fn samples_scales() {
+        let black = Color::black();
+        let white = Color::white();
+
+        for space in InterpolationSpace::all() {
+            let scale = ColorScale::new([black, white]).with_space(space);
+            let colors = scale.colors(5);
+            assert_eq!(colors.len(), 5);
+            assert_eq!(colors[0].as_hex(), "#000000", "{space:?}");
+            assert_eq!(colors[4].as_hex(), "#ffffff", "{space:?}");
+        }
+
+        let scale = ColorScale::new([black, white]).with_space(InterpolationSpace::LinearRgb);
+        assert_eq!(scale.at(0.5).unwrap().as_hex(), "#bcbcbc");
+        let scale = scale.with_space(InterpolationSpace::Oklab);
+        assert_eq!(scale.at(0.5).unwrap().as_hex(), "#636363");
+
+        // the hue of white is taken from the other stop
+        let red = Color::from_hex("ff0000").unwrap();
+        let scale = ColorScale::new([red, white]).with_space(InterpolationSpace::Oklch);
+        let mid = scale.at(0.5).unwrap().oklch();
+        assert!((mid.h() - red.oklch().h()).abs() < 0.5, "{}", mid.h());
+
+        let scale = ColorScale::new([black, red, white]);
+        assert_eq!(scale.at(0.5).unwrap().as_hex(), "#ff0000");
+        assert!(ColorScale::default().colors(3).is_empty());
+        assert_eq!(ColorScale::new([red]).colors(3).len(), 3);
+    }
+
+    #[test]
+    fn exports_scales() {
+        let scale = ColorScale::new([Color::black(), Color::white()])
+            .with_space(InterpolationSpace::LinearRgb);
+        assert_eq!(
+            scale.as_css_linear_gradient(3),
+            "linear-gradient(to right, #000000 0%, #bcbcbc 50%, #ffffff 100%)"
+        );
+        assert_eq!(scale.as_hex_list(3), "#000000\n#bcbcbc\n#ffffff");
+
+        let palette = scale.as_palette(4, "ramp");
+        assert_eq!(palette.name, "ramp");
+        assert_eq!(palette.palette.len(), 4);
+    }
+}
@@ -0,0 +1,153 @@
+use super::{Illuminant, Rgb};
+use crate::utils::math::{Matrix1x3, Matrix3};
+
+use serde::{Deserialize, Serialize};
+
+/// Transfer function that maps linear light to the encoded RGB values of a working space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Compand {
+    Srgb,
+    Gamma(f32),
+}
+
+/// RGB working spaces defined by their primaries, reference white and companding function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum RgbWorkingSpace {
+    Adobe,
+    Apple,
+    Cie,
+    DisplayP3,
+    ProPhoto,
+    #[default]
+    SRGB,
+    WideGamut,
+}
+
+impl RgbWorkingSpace {
+    pub fn variants() -> &'static [RgbWorkingSpace] {
+        use RgbWorkingSpace::*;
+        &[Adobe, Apple, Cie, DisplayP3, ProPhoto, SRGB, WideGamut]
+    }
+
+    /// Returns the xy chromaticity coordinates of the red, green and blue primaries.
+    #[rustfmt::skip]
+    pub fn primaries(&self) -> [(f32, f32); 3] {
+        use RgbWorkingSpace::*;
+        match self {
+            Adobe     => [(0.64,   0.33  ), (0.21,   0.71  ), (0.15,   0.06  )],
+            Apple     => [(0.625,  0.34  ), (0.28,   0.595 ), (0.155,  0.07  )],
+            Cie       => [(0.735,  0.265 ), (0.274,  0.717 ), (0.167,  0.009 )],
+            DisplayP3 => [(0.68,   0.32  ), (0.265,  0.69  ), (0.15,   0.06  )],
+            ProPhoto  => [(0.7347, 0.2653), (0.1596, 0.8404), (0.0366, 0.0001)],
+            SRGB      => [(0.64,   0.33  ), (0.30,   0.60  ), (0.15,   0.06  )],
+            WideGamut => [(0.735,  0.265 ), (0.115,  0.826 ), (0.157,  0.018 )],
+        }
+    }
+
+    pub fn reference_illuminant(&self) -> Illuminant {
+        use RgbWorkingSpace::*;
+        match self {
+            Adobe | Apple | DisplayP3 | SRGB => Illuminant::D65,
+            Cie => Illuminant::E,
+            ProPhoto | WideGamut => Illuminant::D50,
+        }
+    }
+
+    pub fn compand(&self) -> Compand {
+        use RgbWorkingSpace::*;
+        match self {
+            SRGB | DisplayP3 => Compand::Srgb,
+            Adobe => Compand::Gamma(563. / 256.),
+            Apple | ProPhoto => Compand::Gamma(1.8),
+            Cie | WideGamut => Compand::Gamma(2.2),
+        }
+    }
+
+    /// Converts a linear RGB color into the encoded values of this working space.
+    pub fn compand_channels(&self, linear: Rgb) -> Rgb {
+        match self.compand() {
+            Compand::Srgb => linear.srgb_compand(),
+            Compand::Gamma(gamma) => linear.gamma_compand(gamma),
+        }
+    }
+
+    /// Converts encoded values of this working space into linear RGB.
+    pub fn inverse_compand_channels(&self, color: Rgb) -> Rgb {
+        match self.compand() {
+            Compand::Srgb => color.inverse_srgb_compand(),
+            Compand::Gamma(gamma) => color.inverse_gamma_compand(gamma),
+        }
+    }
+
+    /// Returns the matrix converting linear RGB of this working space to XYZ relative to its
+    /// reference illuminant.
+    pub fn rgb_to_xyz_matrix(&self) -> Matrix3 {
+        let [r, g, b] = self.primaries().map(|(x, y)| [x / y, 1., (1. - x - y) / y]);
+        let primaries = Matrix3([[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]]);
+        let white = Matrix1x3::from(self.reference_illuminant().xyz());
+        let s = primaries
+            .inverse()
+            .expect("working space primaries are linearly independent")
+            * white;
+
+        Matrix3([
+            [s[0] * r[0], s[1] * g[0], s[2] * b[0]],
+            [s[0] * r[1], s[1] * g[1], s[2] * b[1]],
+            [s[0] * r[2], s[1] * g[2], s[2] * b[2]],
+        ])
+    }
+
+    /// Returns the matrix converting XYZ relative to the reference illuminant of this working
+    /// space to linear RGB.
+    pub fn xyz_to_rgb_matrix(&self) -> Matrix3 {
+        self.rgb_to_xyz_matrix()
+            .inverse()
+            .expect("working space primaries are linearly independent")
+    }
+}
+
+impl AsRef<str> for RgbWorkingSpace {
+    fn as_ref(&self) -> &str {
+        use RgbWorkingSpace::*;
+        match self {
+            Adobe => "Adobe RGB (1998)",
+            Apple => "Apple RGB",
+            Cie => "CIE RGB",
+            DisplayP3 => "Display P3",
+            ProPhoto => "ProPhoto RGB",
+            SRGB => "sRGB",
+            WideGamut => "Wide Gamut RGB",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RgbWorkingSpace;
+    use crate::utils::math::Matrix1x3;
+
+    #[test]
+    fn builds_srgb_matrix() {
+        let got = RgbWorkingSpace::SRGB.rgb_to_xyz_matrix();
+        let want = [
+            [0.4124564, 0.3575761, 0.1804375],
+            [0.2126729, 0.7151522, 0.072175],
+            [0.0193339, 0.119192, 0.9503041],
+        ];
+
+        for (got, want) in got.0.iter().flatten().zip(want.iter().flatten()) {
+            assert!((got - want).abs() < 1e-4, "{got} != {want}");
+        }
+    }
+
+    #[test]
+    fn maps_white_to_reference_illuminant() {
+        for ws in RgbWorkingSpace::variants() {
+            let white = ws.rgb_to_xyz_matrix() * Matrix1x3::from([1., 1., 1.]);
+            let want = ws.reference_illuminant().xyz();
+            assert!((white[0] - want.x()).abs() < 1e-4, "{ws:?}");
+            assert!((white[1] - want.y()).abs() < 1e-4, "{ws:?}");
+            assert!((white[2] - want.z()).abs() < 1e-4, "{ws:?}");
+        }
+    }
+}
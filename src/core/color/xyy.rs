@@ -0,0 +1,93 @@
+#![allow(non_snake_case)]
+use super::{Illuminant, Xyz};
+
+use serde::{Deserialize, Serialize};
+
+/// CIE xyY color, chromaticity coordinates x and y together with the luminance Y.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Xyy {
+    x: f32,
+    y: f32,
+    Y: f32,
+}
+
+impl Xyy {
+    pub fn new(x: f32, y: f32, Y: f32) -> Self {
+        let x = if x.is_nan() { 0. } else { x };
+        let y = if y.is_nan() { 0. } else { y };
+        let Y = if Y.is_nan() { 0. } else { Y };
+        Self { x, y, Y }
+    }
+
+    #[inline(always)]
+    /// Returns x chromaticity in the range of 0.0 ..= 1.0
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+
+    #[inline(always)]
+    /// Returns y chromaticity in the range of 0.0 ..= 1.0
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    #[inline(always)]
+    /// Returns Y luminance in the range of 0.0 ..= 1.0
+    pub fn Y(&self) -> f32 {
+        self.Y
+    }
+
+    /// Converts `xyz` to xyY. Black has no chromaticity so it takes the chromaticity of the
+    /// `illuminant` reference white.
+    pub fn from_xyz(xyz: Xyz, illuminant: Illuminant) -> Self {
+        let sum = xyz.x() + xyz.y() + xyz.z();
+        if sum == 0. {
+            let white = illuminant.xyz();
+            let white_sum = white.x() + white.y() + white.z();
+            return Self::new(white.x() / white_sum, white.y() / white_sum, 0.);
+        }
+
+        Self::new(xyz.x() / sum, xyz.y() / sum, xyz.y())
+    }
+}
+
+impl From<Xyy> for Xyz {
+    fn from(color: Xyy) -> Self {
+        if color.y == 0. {
+            return Xyz::new(0., 0., 0.);
+        }
+        let x = color.x * color.Y / color.y;
+        let z = (1. - color.x - color.y) * color.Y / color.y;
+        Xyz::new(x, color.Y, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::color::{Illuminant, Xyy, Xyz};
+
+    #[test]
+    fn xyz_to_xyy() {
+        macro_rules! test_case {
+            (Xyz: $x:expr, $y:expr, $z:expr; Xyy: $cx:expr, $cy:expr, $cY:expr) => {
+                let xyz = Xyz::new($x, $y, $z);
+                let got = Xyy::from_xyz(xyz, Illuminant::D65);
+                assert!((got.x() - $cx).abs() < 1e-4, "{got:?}");
+                assert!((got.y() - $cy).abs() < 1e-4, "{got:?}");
+                assert!((got.Y() - $cY).abs() < 1e-4, "{got:?}");
+            };
+        }
+
+        test_case!(Xyz: 0., 0., 0.; Xyy: 0.31273, 0.32902, 0.);
+        test_case!(Xyz: 0.95047, 1., 1.08883; Xyy: 0.31273, 0.32902, 1.);
+        test_case!(Xyz: 0.41246, 0.21267, 0.01933; Xyy: 0.64, 0.33, 0.21267);
+    }
+
+    #[test]
+    fn xyy_to_xyz() {
+        let got = Xyz::from(Xyy::new(0.64, 0.33, 0.21267));
+        assert!((got.x() - 0.41246).abs() < 1e-4, "{got:?}");
+        assert!((got.y() - 0.21267).abs() < 1e-4, "{got:?}");
+        assert!((got.z() - 0.01933).abs() < 1e-4, "{got:?}");
+    }
+}
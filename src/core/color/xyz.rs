@@ -0,0 +1,117 @@
+use super::{Illuminant, Rgb, RgbWorkingSpace};
+use crate::utils::math::Matrix1x3;
+
+use serde::{Deserialize, Serialize};
+
+/// CIE 1931 XYZ color with the luminance Y of the reference white normalized to 1.0
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Xyz {
+    x: f32,
+    y: f32,
+    z: f32,
+}
+
+impl Xyz {
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        let x = if x.is_nan() { 0. } else { x };
+        let y = if y.is_nan() { 0. } else { y };
+        let z = if z.is_nan() { 0. } else { z };
+        Self { x, y, z }
+    }
+
+    #[inline(always)]
+    /// Returns X value, in the range of 0.0 ..= ~1.1 for colors inside the working space
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+
+    #[inline(always)]
+    /// Returns Y (luminance) value in the range of 0.0 ..= 1.0 for colors inside the working space
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    #[inline(always)]
+    /// Returns Z value, in the range of 0.0 ..= ~1.2 for colors inside the working space
+    pub fn z(&self) -> f32 {
+        self.z
+    }
+
+    /// Converts an `rgb` color encoded in the working space `ws` to XYZ relative to the
+    /// `illuminant` reference white.
+    pub fn from_rgb(rgb: Rgb, ws: RgbWorkingSpace, illuminant: Illuminant) -> Self {
+        let linear = ws.inverse_compand_channels(rgb);
+        let xyz = ws.rgb_to_xyz_matrix() * Matrix1x3::from(linear);
+        Xyz::from(xyz).adapt(ws.reference_illuminant(), illuminant)
+    }
+
+    /// Converts this color relative to the `illuminant` reference white to an RGB color encoded
+    /// in the working space `ws`. Out of gamut channels are clamped.
+    pub fn to_rgb(self, ws: RgbWorkingSpace, illuminant: Illuminant) -> Rgb {
+        let xyz = self.adapt(illuminant, ws.reference_illuminant());
+        let linear = Rgb::from(ws.xyz_to_rgb_matrix() * Matrix1x3::from(xyz));
+        ws.compand_channels(linear)
+    }
+
+    /// Adapts this color from the `source` reference white to the `target` reference white.
+    pub fn adapt(self, source: Illuminant, target: Illuminant) -> Self {
+        if source == target {
+            return self;
+        }
+        (source.adaptation_matrix(target) * Matrix1x3::from(self)).into()
+    }
+}
+
+//####################################################################################################
+
+impl From<Matrix1x3> for Xyz {
+    fn from(mx: Matrix1x3) -> Self {
+        Self::new(mx[0], mx[1], mx[2])
+    }
+}
+
+impl From<Xyz> for Matrix1x3 {
+    fn from(color: Xyz) -> Self {
+        [color.x, color.y, color.z].into()
+    }
+}
+
+//####################################################################################################
+
+#[cfg(test)]
+mod tests {
+    use crate::core::color::{Illuminant, Rgb, RgbWorkingSpace, Xyz};
+
+    #[test]
+    fn rgb_to_xyz() {
+        macro_rules! test_case {
+            ($ws:ident, $illuminant:ident; Rgb: $r:expr, $g:expr, $b:expr; Xyz: $x:expr, $y:expr, $z:expr) => {
+                let rgb = Rgb::new_scaled($r, $g, $b);
+                let got = Xyz::from_rgb(rgb, RgbWorkingSpace::$ws, Illuminant::$illuminant);
+                assert!((got.x() - $x).abs() < 1e-3, "{got:?}");
+                assert!((got.y() - $y).abs() < 1e-3, "{got:?}");
+                assert!((got.z() - $z).abs() < 1e-3, "{got:?}");
+
+                let back = got.to_rgb(RgbWorkingSpace::$ws, Illuminant::$illuminant);
+                assert_eq!(
+                    (
+                        back.r_scaled().round() as u8,
+                        back.g_scaled().round() as u8,
+                        back.b_scaled().round() as u8
+                    ),
+                    ($r, $g, $b)
+                );
+            };
+        }
+
+        test_case!(SRGB, D65; Rgb: 0, 0, 0; Xyz: 0., 0., 0.);
+        test_case!(SRGB, D65; Rgb: 255, 255, 255; Xyz: 0.95047, 1., 1.08883);
+        test_case!(SRGB, D65; Rgb: 255, 0, 0; Xyz: 0.41246, 0.21267, 0.01933);
+        test_case!(SRGB, D65; Rgb: 0, 255, 0; Xyz: 0.35758, 0.71515, 0.11919);
+        test_case!(SRGB, D65; Rgb: 0, 0, 255; Xyz: 0.18044, 0.07218, 0.95030);
+        test_case!(SRGB, D65; Rgb: 120, 30, 80; Xyz: 0.09658, 0.05502, 0.08141);
+        test_case!(SRGB, D50; Rgb: 255, 0, 0; Xyz: 0.43607, 0.22249, 0.01392);
+        test_case!(SRGB, D50; Rgb: 255, 255, 255; Xyz: 0.96422, 1., 0.82521);
+        test_case!(Adobe, D65; Rgb: 255, 0, 0; Xyz: 0.57667, 0.29734, 0.02703);
+    }
+}
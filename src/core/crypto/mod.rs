@@ -1,21 +1,25 @@
 pub mod asymmetric;
+pub mod stream;
 pub mod symmetric;
 
 use anyhow::{anyhow, Result};
 use asymmetric::{
     generate_des_iv, generate_des_key, generate_triple_des_key,
-    rsa::{generate_rsa_keypair, rsa_decrypt, rsa_encrypt, rsa_sign, rsa_verify},
+    rsa::{generate_rsa_keypair, rsa_decrypt, rsa_encrypt, rsa_sign, rsa_verify_bytes, RsaOptions},
 };
 use base64::{self, Engine};
 use hex;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::sync::mpsc::Receiver;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use stream::CipherJob;
 use symmetric::{
+    aead::{aead_decrypt, aead_decrypt_detached, aead_encrypt, AeadCipher},
     aes::AesKeySize,
     des::{des_decrypt, des_encrypt},
-    generate_aes_iv, generate_aes_key,
+    generate_aead_key, generate_aes_iv, generate_aes_key, generate_random_bytes,
     tdes::{triple_des_decrypt, triple_des_encrypt},
 };
 
@@ -76,6 +80,12 @@ pub enum CryptoAlgorithm {
     AES,
     DES,
     TripleDES,
+    AesGcm,
+    AesGcmSiv,
+    AesCcm,
+    AesCcm8,
+    ChaCha20Poly1305,
+    XChaCha20Poly1305,
     RSA,
 }
 
@@ -85,6 +95,12 @@ impl fmt::Display for CryptoAlgorithm {
             CryptoAlgorithm::AES => write!(f, "AES"),
             CryptoAlgorithm::DES => write!(f, "DES"),
             CryptoAlgorithm::TripleDES => write!(f, "Triple DES"),
+            CryptoAlgorithm::AesGcm => write!(f, "AES-GCM"),
+            CryptoAlgorithm::AesGcmSiv => write!(f, "AES-GCM-SIV"),
+            CryptoAlgorithm::AesCcm => write!(f, "AES-CCM"),
+            CryptoAlgorithm::AesCcm8 => write!(f, "AES-CCM-8"),
+            CryptoAlgorithm::ChaCha20Poly1305 => write!(f, "ChaCha20-Poly1305"),
+            CryptoAlgorithm::XChaCha20Poly1305 => write!(f, "XChaCha20-Poly1305"),
             CryptoAlgorithm::RSA => write!(f, "RSA"),
         }
     }
@@ -96,17 +112,50 @@ impl CryptoAlgorithm {
             CryptoAlgorithm::AES,
             CryptoAlgorithm::DES,
             CryptoAlgorithm::TripleDES,
+            CryptoAlgorithm::AesGcm,
+            CryptoAlgorithm::AesGcmSiv,
+            CryptoAlgorithm::AesCcm,
+            CryptoAlgorithm::AesCcm8,
+            CryptoAlgorithm::ChaCha20Poly1305,
+            CryptoAlgorithm::XChaCha20Poly1305,
             CryptoAlgorithm::RSA,
         ]
     }
 
     pub fn is_symmetric(&self) -> bool {
+        !matches!(self, CryptoAlgorithm::RSA)
+    }
+
+    /// Whether this is an authenticated encryption algorithm that doesn't use a block cipher mode.
+    pub fn is_aead(&self) -> bool {
+        self.aead_cipher(AesKeySize::Aes128).is_some()
+    }
+
+    /// Whether the key size of this algorithm is selected with an [`AesKeySize`].
+    pub fn uses_aes_key_size(&self) -> bool {
         matches!(
             self,
-            CryptoAlgorithm::AES | CryptoAlgorithm::DES | CryptoAlgorithm::TripleDES
+            CryptoAlgorithm::AES
+                | CryptoAlgorithm::AesGcm
+                | CryptoAlgorithm::AesGcmSiv
+                | CryptoAlgorithm::AesCcm
+                | CryptoAlgorithm::AesCcm8
         )
     }
 
+    /// Returns the AEAD cipher of this algorithm, `key_size` is used by the AES based ones.
+    pub fn aead_cipher(&self, key_size: AesKeySize) -> Option<AeadCipher> {
+        match self {
+            CryptoAlgorithm::AesGcm => Some(AeadCipher::AesGcm(key_size)),
+            CryptoAlgorithm::AesGcmSiv => Some(AeadCipher::AesGcmSiv(key_size)),
+            CryptoAlgorithm::AesCcm => Some(AeadCipher::AesCcm(key_size)),
+            CryptoAlgorithm::AesCcm8 => Some(AeadCipher::AesCcm8(key_size)),
+            CryptoAlgorithm::ChaCha20Poly1305 => Some(AeadCipher::ChaCha20Poly1305),
+            CryptoAlgorithm::XChaCha20Poly1305 => Some(AeadCipher::XChaCha20Poly1305),
+            _ => None,
+        }
+    }
+
     pub fn is_asymmetric(&self) -> bool {
         !self.is_symmetric()
     }
@@ -117,6 +166,11 @@ impl CryptoAlgorithm {
 pub enum CipherMode {
     ECB,
     CBC,
+    CTR,
+    /// Full block cipher feedback, CFB-128 for AES and CFB-64 for (Triple) DES.
+    CFB,
+    CFB8,
+    OFB,
 }
 
 impl fmt::Display for CipherMode {
@@ -124,13 +178,83 @@ impl fmt::Display for CipherMode {
         match self {
             CipherMode::ECB => write!(f, "ECB"),
             CipherMode::CBC => write!(f, "CBC"),
+            CipherMode::CTR => write!(f, "CTR"),
+            CipherMode::CFB => write!(f, "CFB"),
+            CipherMode::CFB8 => write!(f, "CFB8"),
+            CipherMode::OFB => write!(f, "OFB"),
         }
     }
 }
 
 impl CipherMode {
     pub fn variants() -> &'static [CipherMode] {
-        &[CipherMode::ECB, CipherMode::CBC]
+        &[
+            CipherMode::ECB,
+            CipherMode::CBC,
+            CipherMode::CTR,
+            CipherMode::CFB,
+            CipherMode::CFB8,
+            CipherMode::OFB,
+        ]
+    }
+
+    /// Whether this mode needs an initialization vector, or the initial counter block for CTR.
+    pub fn uses_iv(&self) -> bool {
+        !matches!(self, CipherMode::ECB)
+    }
+
+    /// Whether this mode refuses to fall back to an all-zero IV when none is given. The stream
+    /// modes would encrypt every message with the same keystream, CBC keeps the zero IV it always
+    /// defaulted to.
+    pub fn requires_iv(&self) -> bool {
+        matches!(
+            self,
+            CipherMode::CTR | CipherMode::CFB | CipherMode::CFB8 | CipherMode::OFB
+        )
+    }
+
+    /// Whether this mode only encrypts whole blocks so that the plaintext must be padded.
+    pub fn uses_padding(&self) -> bool {
+        matches!(self, CipherMode::ECB | CipherMode::CBC)
+    }
+}
+
+/// Padding applied to the plaintext in ECB and CBC mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Padding {
+    #[default]
+    Pkcs7,
+    /// Zero bytes up to the block size, nothing is added to aligned input.
+    Zero,
+    /// Random bytes followed by the padding length.
+    Iso10126,
+    /// Zero bytes followed by the padding length.
+    AnsiX923,
+    /// The input must already be a multiple of the block size.
+    NoPadding,
+}
+
+impl fmt::Display for Padding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Padding::Pkcs7 => write!(f, "PKCS#7"),
+            Padding::Zero => write!(f, "Zero"),
+            Padding::Iso10126 => write!(f, "ISO 10126"),
+            Padding::AnsiX923 => write!(f, "ANSI X9.23"),
+            Padding::NoPadding => write!(f, "None"),
+        }
+    }
+}
+
+impl Padding {
+    pub fn variants() -> &'static [Padding] {
+        &[
+            Padding::Pkcs7,
+            Padding::Zero,
+            Padding::Iso10126,
+            Padding::AnsiX923,
+            Padding::NoPadding,
+        ]
     }
 }
 
@@ -180,18 +304,128 @@ impl Default for OutputEncoding {
     }
 }
 
+/// Encoding of the text entered for keys, IVs, plaintext and ciphertext.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputEncoding {
+    /// The UTF-8 bytes of the text are used as they are.
+    #[default]
+    Utf8,
+    Hex,
+    Base64,
+}
+
+impl fmt::Display for InputEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputEncoding::Utf8 => write!(f, "UTF-8"),
+            InputEncoding::Hex => write!(f, "Hex"),
+            InputEncoding::Base64 => write!(f, "Base64"),
+        }
+    }
+}
+
+impl InputEncoding {
+    pub fn variants() -> &'static [InputEncoding] {
+        &[
+            InputEncoding::Utf8,
+            InputEncoding::Hex,
+            InputEncoding::Base64,
+        ]
+    }
+
+    /// Decodes `text` into bytes, `what` names the decoded value in errors. Whitespace around hex
+    /// and base64 text is ignored.
+    pub fn decode(&self, text: &str, what: &str) -> Result<Vec<u8>> {
+        match self {
+            InputEncoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            InputEncoding::Hex => hex::decode(text.trim())
+                .map_err(|_| anyhow!("Invalid {what} format - must be valid hex")),
+            InputEncoding::Base64 => base64::engine::general_purpose::STANDARD
+                .decode(text.trim())
+                .map_err(|_| anyhow!("Invalid {what} format - must be valid base64")),
+        }
+    }
+
+    /// Encodes `bytes` as text. Bytes that are not valid UTF-8 are shown as a hex dump instead.
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        match self {
+            InputEncoding::Utf8 => {
+                String::from_utf8(bytes.to_vec()).unwrap_or_else(|_| hex_dump(bytes))
+            }
+            InputEncoding::Hex => hex::encode(bytes),
+            InputEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(bytes),
+        }
+    }
+}
+
+/// Formats `bytes` like `hexdump -C`, 16 bytes per line with their offset and printable
+/// characters.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(line, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|byte| format!("{byte:02x}")).collect();
+            let (left, right) = hex.split_at(hex.len().min(8));
+            let text: String = chunk
+                .iter()
+                .map(|&byte| {
+                    if byte.is_ascii_graphic() || byte == b' ' {
+                        byte as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!(
+                "{:08x}  {:<23}  {:<23}  |{text}|",
+                line * 16,
+                left.join(" "),
+                right.join(" ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The encodings of every text input of the symmetric ciphers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputEncodings {
+    pub key: InputEncoding,
+    pub iv: InputEncoding,
+    pub plaintext: InputEncoding,
+    pub ciphertext: InputEncoding,
+}
+
+impl Default for InputEncodings {
+    fn default() -> Self {
+        Self {
+            key: InputEncoding::Utf8,
+            iv: InputEncoding::Utf8,
+            plaintext: InputEncoding::Utf8,
+            ciphertext: InputEncoding::Hex,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CryptoInput {
     pub algorithm: CryptoAlgorithm,
     pub operation: CryptoOperation,
     pub mode: Option<CipherMode>,
+    pub padding: Padding, // Padding for ECB and CBC mode
     pub input_text: String,
+    pub input_encodings: InputEncodings, // Encodings of the key, IV and input text
     pub key: String,
     pub aes_key_size: Option<AesKeySize>, // Key size for AES (128, 192, or 256 bits)
-    pub iv: Option<String>,               // Initialization Vector for CBC mode
+    pub iv: Option<String>,               // Initialization Vector for CBC mode or AEAD nonce
+    pub associated_data: Option<String>,  // Authenticated but not encrypted data for AEAD
+    pub tag: Option<String>,              // AEAD authentication tag kept apart from the ciphertext
+    pub detached_tag: bool,               // Whether the AEAD tag is not appended to the ciphertext
     pub public_key: Option<String>,       // For asymmetric algorithms
     pub private_key: Option<String>,      // For asymmetric algorithms
     pub rsa_key_size: Option<RsaKeySize>, // Key size for RSA (512, 1024, 2048, 3072, 4096 bits)
+    pub rsa_options: RsaOptions,          // Padding schemes and hash for RSA
     pub signature: Option<String>,
     pub encoding: OutputEncoding,
 }
@@ -202,13 +436,19 @@ impl Default for CryptoInput {
             algorithm: CryptoAlgorithm::AES,
             operation: CryptoOperation::Encrypt,
             mode: Some(CipherMode::CBC),
+            padding: Padding::default(),
             input_text: String::new(),
+            input_encodings: InputEncodings::default(),
             key: String::new(),
             aes_key_size: Some(AesKeySize::Aes128),
             iv: None,
+            associated_data: None,
+            tag: None,
+            detached_tag: false,
             public_key: None,
             private_key: None,
             rsa_key_size: Some(RsaKeySize::Rsa2048),
+            rsa_options: RsaOptions::default(),
             signature: None,
             encoding: OutputEncoding::default(),
         }
@@ -229,6 +469,22 @@ impl Default for KeyGenerationState {
     }
 }
 
+/// Progress of a file encrypted or decrypted on a background thread, also the message sent from
+/// the thread.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FileProcessingState {
+    #[default]
+    Idle,
+    Processing {
+        processed: u64,
+        total: u64,
+    },
+    Completed {
+        written: u64,
+    },
+    Failed(String),
+}
+
 #[derive(Debug, Clone)]
 pub struct KeyGenerationResult {
     pub public_key: String,
@@ -242,6 +498,8 @@ pub struct CryptographyProcessor {
     pub error: Option<String>,
     pub key_generation_state: KeyGenerationState,
     pub key_generation_receiver: Option<Arc<Mutex<Receiver<Result<KeyGenerationResult, String>>>>>,
+    pub file_processing_state: FileProcessingState,
+    pub file_processing_receiver: Option<Arc<Mutex<Receiver<FileProcessingState>>>>,
 }
 
 impl Default for CryptographyProcessor {
@@ -252,6 +510,8 @@ impl Default for CryptographyProcessor {
             error: None,
             key_generation_state: KeyGenerationState::default(),
             key_generation_receiver: None,
+            file_processing_state: FileProcessingState::default(),
+            file_processing_receiver: None,
         }
     }
 }
@@ -264,6 +524,8 @@ impl Clone for CryptographyProcessor {
             error: self.error.clone(),
             key_generation_state: self.key_generation_state.clone(),
             key_generation_receiver: None, // Can't clone receiver
+            file_processing_state: self.file_processing_state.clone(),
+            file_processing_receiver: None,
         }
     }
 }
@@ -281,6 +543,7 @@ impl CryptographyProcessor {
             CryptoAlgorithm::DES => self.process_des(),
             CryptoAlgorithm::TripleDES => self.process_triple_des(),
             CryptoAlgorithm::RSA => self.process_rsa(),
+            _ => self.process_aead(),
         };
 
         match result {
@@ -307,7 +570,9 @@ impl CryptographyProcessor {
                     &self.input.key,
                     key_size,
                     mode,
+                    self.input.padding,
                     self.input.iv.as_deref(),
+                    self.input.input_encodings,
                 )?;
 
                 // Handle encoding in CryptographyProcessor
@@ -324,11 +589,16 @@ impl CryptographyProcessor {
                     &self.input.key,
                     key_size,
                     mode,
+                    self.input.padding,
                     self.input.iv.as_deref(),
+                    self.input.input_encodings,
                 )?;
 
-                String::from_utf8(decrypted_bytes)
-                    .map_err(|e| anyhow!("Invalid UTF-8 in decrypted text: {}", e))
+                Ok(self
+                    .input
+                    .input_encodings
+                    .plaintext
+                    .encode(&decrypted_bytes))
             }
             _ => Err(anyhow!("Invalid operation for AES")),
         }
@@ -342,7 +612,9 @@ impl CryptographyProcessor {
                     &self.input.input_text,
                     &self.input.key,
                     mode,
+                    self.input.padding,
                     self.input.iv.as_deref(),
+                    self.input.input_encodings,
                 )?;
 
                 match self.input.encoding {
@@ -356,7 +628,9 @@ impl CryptographyProcessor {
                 &self.input.input_text,
                 &self.input.key,
                 mode,
+                self.input.padding,
                 self.input.iv.as_deref(),
+                self.input.input_encodings,
             ),
             _ => Err(anyhow!("Invalid operation for DES")),
         }
@@ -370,7 +644,9 @@ impl CryptographyProcessor {
                     &self.input.input_text,
                     &self.input.key,
                     mode,
+                    self.input.padding,
                     self.input.iv.as_deref(),
+                    self.input.input_encodings,
                 )?;
 
                 match self.input.encoding {
@@ -384,12 +660,73 @@ impl CryptographyProcessor {
                 &self.input.input_text,
                 &self.input.key,
                 mode,
+                self.input.padding,
                 self.input.iv.as_deref(),
+                self.input.input_encodings,
             ),
             _ => Err(anyhow!("Invalid operation for Triple DES")),
         }
     }
 
+    fn aead_cipher(&self) -> Option<AeadCipher> {
+        let key_size = self.input.aes_key_size.unwrap_or(AesKeySize::Aes128);
+        self.input.algorithm.aead_cipher(key_size)
+    }
+
+    fn encode_output(&self, bytes: &[u8]) -> String {
+        match self.input.encoding {
+            OutputEncoding::Hex => hex::encode(bytes),
+            OutputEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(bytes),
+        }
+    }
+
+    /// The associated data is always used as text, every other input is decoded with its
+    /// selected encoding.
+    fn process_aead(&mut self) -> Result<String> {
+        let cipher = self
+            .aead_cipher()
+            .ok_or_else(|| anyhow!("{} is not an AEAD algorithm", self.input.algorithm))?;
+        let encodings = self.input.input_encodings;
+        let key = encodings.key.decode(&self.input.key, "key")?;
+        let nonce = encodings
+            .iv
+            .decode(self.input.iv.as_deref().unwrap_or_default(), "nonce")?;
+        let aad = self.input.associated_data.clone().unwrap_or_default();
+
+        match self.input.operation {
+            CryptoOperation::Encrypt => {
+                let plaintext = encodings
+                    .plaintext
+                    .decode(&self.input.input_text, "plaintext")?;
+                let mut encrypted = aead_encrypt(cipher, &key, &nonce, aad.as_bytes(), &plaintext)?;
+                if self.input.detached_tag {
+                    let tag = encrypted.split_off(encrypted.len() - cipher.tag_size());
+                    self.input.tag = Some(self.encode_output(&tag));
+                }
+                Ok(self.encode_output(&encrypted))
+            }
+            CryptoOperation::Decrypt => {
+                let ciphertext = encodings
+                    .ciphertext
+                    .decode(&self.input.input_text, "ciphertext")?;
+                let decrypted = if self.input.detached_tag {
+                    let tag = self
+                        .input
+                        .tag
+                        .as_deref()
+                        .ok_or_else(|| anyhow!("Authentication tag required for decryption"))?;
+                    let tag = encodings.ciphertext.decode(tag, "tag")?;
+                    aead_decrypt_detached(cipher, &key, &nonce, aad.as_bytes(), &ciphertext, &tag)?
+                } else {
+                    aead_decrypt(cipher, &key, &nonce, aad.as_bytes(), &ciphertext)?
+                };
+
+                Ok(encodings.plaintext.encode(&decrypted))
+            }
+            _ => Err(anyhow!("Invalid operation for {}", self.input.algorithm)),
+        }
+    }
+
     fn process_rsa(&self) -> Result<String> {
         match self.input.operation {
             CryptoOperation::Encrypt => {
@@ -398,7 +735,8 @@ impl CryptographyProcessor {
                     .public_key
                     .as_ref()
                     .ok_or_else(|| anyhow!("Public key required for RSA encryption"))?;
-                let encrypted_bytes = rsa_encrypt(&self.input.input_text, public_key)?;
+                let encrypted_bytes =
+                    rsa_encrypt(&self.input.input_text, public_key, &self.input.rsa_options)?;
 
                 match self.input.encoding {
                     OutputEncoding::Hex => Ok(hex::encode(encrypted_bytes)),
@@ -413,7 +751,7 @@ impl CryptographyProcessor {
                     .private_key
                     .as_ref()
                     .ok_or_else(|| anyhow!("Private key required for RSA decryption"))?;
-                rsa_decrypt(&self.input.input_text, private_key)
+                rsa_decrypt(&self.input.input_text, private_key, &self.input.rsa_options)
             }
             CryptoOperation::Sign => {
                 let private_key = self
@@ -421,7 +759,8 @@ impl CryptographyProcessor {
                     .private_key
                     .as_ref()
                     .ok_or_else(|| anyhow!("Private key required for RSA signing"))?;
-                let signed_bytes = rsa_sign(&self.input.input_text, private_key)?;
+                let signed_bytes =
+                    rsa_sign(&self.input.input_text, private_key, &self.input.rsa_options)?;
 
                 match self.input.encoding {
                     OutputEncoding::Hex => Ok(hex::encode(signed_bytes)),
@@ -441,7 +780,18 @@ impl CryptographyProcessor {
                     .signature
                     .as_ref()
                     .ok_or_else(|| anyhow!("Signature required for RSA verification"))?;
-                let is_valid = rsa_verify(&self.input.input_text, signature, public_key)?;
+                // the signature is expected in the encoding signatures are written in
+                let signature = match self.input.encoding {
+                    OutputEncoding::Hex => InputEncoding::Hex,
+                    OutputEncoding::Base64 => InputEncoding::Base64,
+                }
+                .decode(signature, "signature")?;
+                let is_valid = rsa_verify_bytes(
+                    &self.input.input_text,
+                    &signature,
+                    public_key,
+                    &self.input.rsa_options,
+                )?;
                 Ok(format!("Signature valid: {is_valid}"))
             }
         }
@@ -452,29 +802,35 @@ impl CryptographyProcessor {
     }
 
     pub fn generate_random_key(&mut self) -> Result<()> {
+        if let Some(cipher) = self.aead_cipher() {
+            self.input.key = generate_aead_key(cipher, self.input.input_encodings.key);
+            return Ok(());
+        }
         match self.input.algorithm {
             CryptoAlgorithm::AES => {
                 let key = generate_aes_key(
                     self.input
                         .aes_key_size
                         .ok_or(anyhow!("Key size is required for AES"))?,
+                    self.input.input_encodings.key,
                 );
                 self.input.key = key;
                 Ok(())
             }
             CryptoAlgorithm::DES => {
-                let key = generate_des_key();
+                let key = generate_des_key(self.input.input_encodings.key);
                 self.input.key = key;
                 Ok(())
             }
             CryptoAlgorithm::TripleDES => {
-                let key = generate_triple_des_key();
+                let key = generate_triple_des_key(self.input.input_encodings.key);
                 self.input.key = key;
                 Ok(())
             }
             CryptoAlgorithm::RSA => {
                 self.start_async_key_generation()
             }
+            _ => unreachable!("AEAD keys are generated above"),
         }
     }
 
@@ -551,12 +907,85 @@ impl CryptographyProcessor {
         self.key_generation_receiver = None;
     }
 
+    /// Encrypts or decrypts raw bytes with the symmetric algorithm, key and IV of the input.
+    pub fn process_bytes(&self, data: &[u8]) -> Result<Vec<u8>> {
+        CipherJob::from_input(&self.input)?.process_bytes(data)
+    }
+
+    /// Encrypts or decrypts the `input` file into `output` chunk by chunk on a background thread.
+    /// Invalid keys and IVs are reported right away, the progress is picked up by
+    /// `check_file_processing_progress`.
+    pub fn start_file_processing(&mut self, input: PathBuf, output: PathBuf) -> Result<()> {
+        // If already processing, don't start another thread
+        if self.is_file_processing_in_progress() {
+            return Ok(());
+        }
+
+        let job = CipherJob::from_input(&self.input)?;
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        self.file_processing_state = FileProcessingState::Processing {
+            processed: 0,
+            total: 0,
+        };
+        self.file_processing_receiver = Some(Arc::new(Mutex::new(receiver)));
+
+        std::thread::spawn(move || {
+            let result = job.process_file(&input, &output, |processed, total| {
+                let _ = sender.send(FileProcessingState::Processing { processed, total });
+            });
+            let _ = sender.send(match result {
+                Ok(written) => FileProcessingState::Completed { written },
+                Err(e) => FileProcessingState::Failed(format!("{e:#}")),
+            });
+        });
+
+        Ok(())
+    }
+
+    pub fn check_file_processing_progress(&mut self) {
+        if let Some(receiver_arc) = &self.file_processing_receiver {
+            if let Ok(receiver) = receiver_arc.try_lock() {
+                loop {
+                    match receiver.try_recv() {
+                        Ok(state) => self.file_processing_state = state,
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            if self.is_file_processing_in_progress() {
+                                self.file_processing_state = FileProcessingState::Failed(
+                                    "File processing stopped unexpectedly".to_string(),
+                                );
+                            }
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        if !self.is_file_processing_in_progress() {
+            self.file_processing_receiver = None;
+        }
+    }
+
+    pub fn is_file_processing_in_progress(&self) -> bool {
+        matches!(
+            self.file_processing_state,
+            FileProcessingState::Processing { .. }
+        )
+    }
+
     pub fn generate_random_iv(&mut self) -> Result<()> {
-        if self.input.mode == Some(CipherMode::CBC) {
+        let encoding = self.input.input_encodings.iv;
+        if let Some(cipher) = self.aead_cipher() {
+            self.input.iv = Some(generate_random_bytes(cipher.nonce_size(), encoding));
+            return Ok(());
+        }
+        if self.input.mode.is_some_and(|mode| mode.uses_iv()) {
             let iv = match self.input.algorithm {
-                CryptoAlgorithm::AES => generate_aes_iv(),
-                CryptoAlgorithm::DES => generate_des_iv(),
-                CryptoAlgorithm::TripleDES => generate_des_iv(),
+                CryptoAlgorithm::AES => generate_aes_iv(encoding),
+                CryptoAlgorithm::DES => generate_des_iv(encoding),
+                CryptoAlgorithm::TripleDES => generate_des_iv(encoding),
                 _ => return Err(anyhow!("IV not required for this algorithm")),
             };
             self.input.iv = Some(iv);
@@ -0,0 +1,196 @@
+//! Byte oriented encryption and decryption with the symmetric algorithms, for binary data and
+//! files too large to hold in memory.
+//!
+//! Block ciphers read the input in chunks of [`CHUNK_SIZE`] bytes. AEAD algorithms authenticate
+//! the whole message with a single tag, so their input is read completely before it is processed.
+
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Read, Write},
+    path::Path,
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use super::{
+    symmetric::{
+        aead::{aead_decrypt, aead_decrypt_detached, aead_encrypt},
+        aes::{aes_decrypt_stream, aes_encrypt_stream, AesKeySize},
+        decode_iv,
+        des::{des_decrypt_stream, des_encrypt_stream},
+        tdes::{triple_des_decrypt_stream, triple_des_encrypt_stream},
+    },
+    CipherMode, CryptoAlgorithm, CryptoInput, CryptoOperation, Padding,
+};
+
+pub use super::symmetric::modes::CHUNK_SIZE;
+
+/// A symmetric encryption or decryption with its key and IV already decoded, so it can be moved
+/// to a background thread and run on any reader.
+#[derive(Debug, Clone)]
+pub struct CipherJob {
+    pub algorithm: CryptoAlgorithm,
+    pub operation: CryptoOperation,
+    pub mode: CipherMode,
+    pub padding: Padding,
+    pub aes_key_size: AesKeySize,
+    pub key: Vec<u8>,
+    pub iv: Option<Vec<u8>>, // IV or AEAD nonce
+    pub associated_data: Vec<u8>,
+    pub tag: Option<Vec<u8>>, // AEAD tag to decrypt with when it is not appended to the ciphertext
+}
+
+impl CipherJob {
+    /// Decodes the key, IV and detached tag of `input` with their input encodings.
+    pub fn from_input(input: &CryptoInput) -> Result<Self> {
+        if !input.algorithm.is_symmetric() {
+            return Err(anyhow!(
+                "{} can't process binary data, use a symmetric algorithm",
+                input.algorithm
+            ));
+        }
+        if !matches!(
+            input.operation,
+            CryptoOperation::Encrypt | CryptoOperation::Decrypt
+        ) {
+            return Err(anyhow!("Invalid operation for {}", input.algorithm));
+        }
+
+        let mode = input.mode.unwrap_or(CipherMode::CBC);
+        let encodings = input.input_encodings;
+        let iv = if input.algorithm.is_aead() {
+            // The nonce is needed whatever the block cipher mode
+            let nonce = input.iv.as_deref().unwrap_or_default();
+            Some(encodings.iv.decode(nonce, "nonce")?)
+        } else {
+            decode_iv(mode, input.iv.as_deref(), encodings.iv)?
+        };
+        let tag = if input.algorithm.is_aead()
+            && input.detached_tag
+            && input.operation == CryptoOperation::Decrypt
+        {
+            let tag = input
+                .tag
+                .as_deref()
+                .ok_or_else(|| anyhow!("Authentication tag required for decryption"))?;
+            Some(encodings.ciphertext.decode(tag, "tag")?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            algorithm: input.algorithm,
+            operation: input.operation.clone(),
+            mode,
+            padding: input.padding,
+            aes_key_size: input.aes_key_size.unwrap_or(AesKeySize::Aes128),
+            key: encodings.key.decode(&input.key, "key")?,
+            iv,
+            associated_data: input
+                .associated_data
+                .clone()
+                .unwrap_or_default()
+                .into_bytes(),
+            tag,
+        })
+    }
+
+    /// Encrypts or decrypts everything read from `reader` into `writer`, returns the number of
+    /// bytes written.
+    pub fn process(&self, reader: &mut dyn Read, writer: &mut dyn Write) -> Result<u64> {
+        let encrypt = self.operation == CryptoOperation::Encrypt;
+        let (key, mode, padding, iv) = (&self.key, self.mode, self.padding, self.iv.as_deref());
+        match (self.algorithm, encrypt) {
+            (CryptoAlgorithm::AES, true) => {
+                aes_encrypt_stream(reader, writer, key, self.aes_key_size, mode, padding, iv)
+            }
+            (CryptoAlgorithm::AES, false) => {
+                aes_decrypt_stream(reader, writer, key, self.aes_key_size, mode, padding, iv)
+            }
+            (CryptoAlgorithm::DES, true) => {
+                des_encrypt_stream(reader, writer, key, mode, padding, iv)
+            }
+            (CryptoAlgorithm::DES, false) => {
+                des_decrypt_stream(reader, writer, key, mode, padding, iv)
+            }
+            (CryptoAlgorithm::TripleDES, true) => {
+                triple_des_encrypt_stream(reader, writer, key, mode, padding, iv)
+            }
+            (CryptoAlgorithm::TripleDES, false) => {
+                triple_des_decrypt_stream(reader, writer, key, mode, padding, iv)
+            }
+            (algorithm, _) => {
+                let cipher = algorithm
+                    .aead_cipher(self.aes_key_size)
+                    .ok_or_else(|| anyhow!("{algorithm} can't process binary data"))?;
+                let mut data = Vec::new();
+                reader.read_to_end(&mut data)?;
+
+                let nonce = iv.unwrap_or_default();
+                let aad = &self.associated_data;
+                let output = match &self.tag {
+                    _ if encrypt => aead_encrypt(cipher, key, nonce, aad, &data)?,
+                    Some(tag) => aead_decrypt_detached(cipher, key, nonce, aad, &data, tag)?,
+                    None => aead_decrypt(cipher, key, nonce, aad, &data)?,
+                };
+                writer.write_all(&output)?;
+                Ok(output.len() as u64)
+            }
+        }
+    }
+
+    /// Encrypts or decrypts `data` in memory.
+    pub fn process_bytes(&self, mut data: &[u8]) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        self.process(&mut data, &mut output)?;
+        Ok(output)
+    }
+
+    /// Encrypts or decrypts the `input` file into `output`. `progress` is called with the bytes
+    /// read so far and the size of the input after every chunk. A partly written output file is
+    /// removed when processing fails.
+    pub fn process_file(
+        &self,
+        input: &Path,
+        output: &Path,
+        progress: impl FnMut(u64, u64),
+    ) -> Result<u64> {
+        let file = File::open(input).with_context(|| format!("Failed to open {:?}", input))?;
+        let total = file.metadata()?.len();
+        let mut reader = ProgressReader {
+            inner: file,
+            read: 0,
+            total,
+            progress,
+        };
+        let mut writer = BufWriter::new(
+            File::create(output).with_context(|| format!("Failed to create {:?}", output))?,
+        );
+
+        let result = self
+            .process(&mut reader, &mut writer)
+            .and_then(|written| Ok(writer.flush().map(|_| written)?));
+        if result.is_err() {
+            drop(writer);
+            let _ = fs::remove_file(output);
+        }
+        result
+    }
+}
+
+/// Reports the number of bytes read from `inner` to `progress`.
+struct ProgressReader<R, F> {
+    inner: R,
+    read: u64,
+    total: u64,
+    progress: F,
+}
+
+impl<R: Read, F: FnMut(u64, u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.read += read as u64;
+        (self.progress)(self.read, self.total);
+        Ok(read)
+    }
+}
@@ -0,0 +1,249 @@
+//! Authenticated encryption with associated data.
+//!
+//! Ciphertexts carry the authentication tag appended to the encrypted data, which is the layout
+//! used by the RFCs and by most libraries. Decryption fails unless the tag matches the key, the
+//! nonce, the associated data and the ciphertext.
+
+use std::ops::RangeInclusive;
+
+use aes::{Aes128, Aes192, Aes256};
+use aes_gcm::{
+    aead::{consts::*, Aead, KeyInit, Nonce, Payload},
+    AesGcm,
+};
+use aes_gcm_siv::AesGcmSiv;
+use anyhow::{anyhow, Result};
+use ccm::Ccm;
+use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use super::aes::AesKeySize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AeadCipher {
+    AesGcm(AesKeySize),
+    /// Nonce misuse resistant AES-GCM-SIV from RFC 8452, only defined for 128 and 256 bit keys.
+    AesGcmSiv(AesKeySize),
+    /// AES-CCM with a 128 bit tag.
+    AesCcm(AesKeySize),
+    /// AES-CCM with a 64 bit tag as used by the `CCM_8` TLS cipher suites and RFC 3610.
+    AesCcm8(AesKeySize),
+    ChaCha20Poly1305,
+    XChaCha20Poly1305,
+}
+
+impl std::fmt::Display for AeadCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AeadCipher::AesGcm(size) => write!(f, "{size}-GCM"),
+            AeadCipher::AesGcmSiv(size) => write!(f, "{size}-GCM-SIV"),
+            AeadCipher::AesCcm(size) => write!(f, "{size}-CCM"),
+            AeadCipher::AesCcm8(size) => write!(f, "{size}-CCM-8"),
+            AeadCipher::ChaCha20Poly1305 => write!(f, "ChaCha20-Poly1305"),
+            AeadCipher::XChaCha20Poly1305 => write!(f, "XChaCha20-Poly1305"),
+        }
+    }
+}
+
+impl AeadCipher {
+    /// Returns the key length in bytes.
+    pub fn key_size(&self) -> usize {
+        match self {
+            AeadCipher::AesGcm(size)
+            | AeadCipher::AesGcmSiv(size)
+            | AeadCipher::AesCcm(size)
+            | AeadCipher::AesCcm8(size) => match size {
+                AesKeySize::Aes128 => 16,
+                AesKeySize::Aes192 => 24,
+                AesKeySize::Aes256 => 32,
+            },
+            AeadCipher::ChaCha20Poly1305 | AeadCipher::XChaCha20Poly1305 => 32,
+        }
+    }
+
+    /// Returns the accepted nonce lengths in bytes. CCM trades the nonce length for the maximum
+    /// message length, every other cipher uses a fixed nonce length.
+    pub fn nonce_sizes(&self) -> RangeInclusive<usize> {
+        match self {
+            AeadCipher::AesCcm(_) | AeadCipher::AesCcm8(_) => 7..=13,
+            AeadCipher::XChaCha20Poly1305 => 24..=24,
+            _ => 12..=12,
+        }
+    }
+
+    /// Returns the nonce length in bytes used for generated nonces.
+    pub fn nonce_size(&self) -> usize {
+        match self {
+            AeadCipher::XChaCha20Poly1305 => 24,
+            _ => 12,
+        }
+    }
+
+    /// Returns the length in bytes of the tag appended to ciphertexts.
+    pub fn tag_size(&self) -> usize {
+        match self {
+            AeadCipher::AesCcm8(_) => 8,
+            _ => 16,
+        }
+    }
+
+    fn validate(&self, key: &[u8], nonce: &[u8]) -> Result<()> {
+        if let AeadCipher::AesGcmSiv(AesKeySize::Aes192) = self {
+            return Err(anyhow!(
+                "AES-GCM-SIV is only defined for 128 and 256 bit keys"
+            ));
+        }
+        if key.len() != self.key_size() {
+            return Err(anyhow!(
+                "{self} requires a {}-byte key, got {} bytes",
+                self.key_size(),
+                key.len()
+            ));
+        }
+        let sizes = self.nonce_sizes();
+        if !sizes.contains(&nonce.len()) {
+            let expected = if sizes.start() == sizes.end() {
+                sizes.start().to_string()
+            } else {
+                format!("{} to {}", sizes.start(), sizes.end())
+            };
+            return Err(anyhow!(
+                "{self} requires a {expected}-byte nonce, got {} bytes",
+                nonce.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Generates a random nonce of the default length for `cipher`.
+pub fn generate_aead_nonce(cipher: AeadCipher) -> Vec<u8> {
+    let mut nonce = vec![0u8; cipher.nonce_size()];
+    thread_rng().fill_bytes(&mut nonce);
+    nonce
+}
+
+/// Encrypts `plaintext` and returns the ciphertext followed by the authentication tag.
+pub fn aead_encrypt(
+    cipher: AeadCipher,
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>> {
+    cipher.validate(key, nonce)?;
+    apply(cipher, true, key, nonce, aad, plaintext)
+}
+
+/// Verifies and decrypts `ciphertext` that ends with the authentication tag.
+pub fn aead_decrypt(
+    cipher: AeadCipher,
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>> {
+    cipher.validate(key, nonce)?;
+    if ciphertext.len() < cipher.tag_size() {
+        return Err(anyhow!(
+            "Ciphertext is shorter than the {}-byte authentication tag",
+            cipher.tag_size()
+        ));
+    }
+    apply(cipher, false, key, nonce, aad, ciphertext)
+}
+
+/// Like [`aead_decrypt`] with the authentication `tag` kept apart from the `ciphertext`.
+pub fn aead_decrypt_detached(
+    cipher: AeadCipher,
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+    ciphertext: &[u8],
+    tag: &[u8],
+) -> Result<Vec<u8>> {
+    if tag.len() != cipher.tag_size() {
+        return Err(anyhow!(
+            "{cipher} requires a {}-byte tag, got {} bytes",
+            cipher.tag_size(),
+            tag.len()
+        ));
+    }
+    let mut data = Vec::with_capacity(ciphertext.len() + tag.len());
+    data.extend_from_slice(ciphertext);
+    data.extend_from_slice(tag);
+    aead_decrypt(cipher, key, nonce, aad, &data)
+}
+
+fn apply(
+    cipher: AeadCipher,
+    encrypt: bool,
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    macro_rules! ccm {
+        ($aes:ty, $tag:ty) => {
+            match nonce.len() {
+                7 => run::<Ccm<$aes, $tag, U7>>(encrypt, key, nonce, aad, data),
+                8 => run::<Ccm<$aes, $tag, U8>>(encrypt, key, nonce, aad, data),
+                9 => run::<Ccm<$aes, $tag, U9>>(encrypt, key, nonce, aad, data),
+                10 => run::<Ccm<$aes, $tag, U10>>(encrypt, key, nonce, aad, data),
+                11 => run::<Ccm<$aes, $tag, U11>>(encrypt, key, nonce, aad, data),
+                12 => run::<Ccm<$aes, $tag, U12>>(encrypt, key, nonce, aad, data),
+                _ => run::<Ccm<$aes, $tag, U13>>(encrypt, key, nonce, aad, data),
+            }
+        };
+    }
+
+    match cipher {
+        AeadCipher::AesGcm(AesKeySize::Aes128) => {
+            run::<AesGcm<Aes128, U12>>(encrypt, key, nonce, aad, data)
+        }
+        AeadCipher::AesGcm(AesKeySize::Aes192) => {
+            run::<AesGcm<Aes192, U12>>(encrypt, key, nonce, aad, data)
+        }
+        AeadCipher::AesGcm(AesKeySize::Aes256) => {
+            run::<AesGcm<Aes256, U12>>(encrypt, key, nonce, aad, data)
+        }
+        AeadCipher::AesGcmSiv(AesKeySize::Aes128) => {
+            run::<AesGcmSiv<Aes128>>(encrypt, key, nonce, aad, data)
+        }
+        AeadCipher::AesGcmSiv(AesKeySize::Aes192) => unreachable!("rejected by validate"),
+        AeadCipher::AesGcmSiv(AesKeySize::Aes256) => {
+            run::<AesGcmSiv<Aes256>>(encrypt, key, nonce, aad, data)
+        }
+        AeadCipher::AesCcm(AesKeySize::Aes128) => ccm!(Aes128, U16),
+        AeadCipher::AesCcm(AesKeySize::Aes192) => ccm!(Aes192, U16),
+        AeadCipher::AesCcm(AesKeySize::Aes256) => ccm!(Aes256, U16),
+        AeadCipher::AesCcm8(AesKeySize::Aes128) => ccm!(Aes128, U8),
+        AeadCipher::AesCcm8(AesKeySize::Aes192) => ccm!(Aes192, U8),
+        AeadCipher::AesCcm8(AesKeySize::Aes256) => ccm!(Aes256, U8),
+        AeadCipher::ChaCha20Poly1305 => run::<ChaCha20Poly1305>(encrypt, key, nonce, aad, data),
+        AeadCipher::XChaCha20Poly1305 => run::<XChaCha20Poly1305>(encrypt, key, nonce, aad, data),
+    }
+}
+
+/// `key` and `nonce` must have the lengths expected by `C`.
+fn run<C: Aead + KeyInit>(
+    encrypt: bool,
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>> {
+    let cipher = C::new_from_slice(key).map_err(|_| anyhow!("Invalid key length"))?;
+    let nonce = Nonce::<C>::from_slice(nonce);
+    let payload = Payload { msg: data, aad };
+    if encrypt {
+        cipher
+            .encrypt(nonce, payload)
+            .map_err(|_| anyhow!("Encryption failed"))
+    } else {
+        cipher.decrypt(nonce, payload).map_err(|_| {
+            anyhow!("Authentication failed - the tag doesn't match the key, nonce, associated data or ciphertext")
+        })
+    }
+}
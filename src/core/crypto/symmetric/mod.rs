@@ -1,30 +1,61 @@
+pub mod aead;
 pub mod aes;
 pub mod des;
+pub mod modes;
 pub mod tdes;
 
+use aead::AeadCipher;
 use aes::AesKeySize;
-use rand::{thread_rng, RngCore};
+use base64::Engine;
+use rand::{distributions::Alphanumeric, thread_rng, Rng, RngCore};
+
+use super::{CipherMode, InputEncoding};
+
+/// Decodes the IV of the modes that use one, ECB ignores it.
+pub(crate) fn decode_iv(
+    mode: CipherMode,
+    iv: Option<&str>,
+    encoding: InputEncoding,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    iv.filter(|_| mode.uses_iv())
+        .map(|iv| encoding.decode(iv, "IV"))
+        .transpose()
+}
+
+/// Generates `len` random bytes as text in `encoding`. Arbitrary bytes are not valid UTF-8, so
+/// random alphanumeric characters are generated for UTF-8 instead.
+pub fn generate_random_bytes(len: usize, encoding: InputEncoding) -> String {
+    if encoding == InputEncoding::Utf8 {
+        return thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(len)
+            .map(char::from)
+            .collect();
+    }
+
+    let mut bytes = vec![0u8; len];
+    thread_rng().fill_bytes(&mut bytes);
+    match encoding {
+        InputEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(bytes),
+        _ => hex::encode(bytes),
+    }
+}
 
 // Key generation functions
-pub fn generate_aes_key(key_size: AesKeySize) -> String {
+pub fn generate_aes_key(key_size: AesKeySize, encoding: InputEncoding) -> String {
     let key_length = match key_size {
         AesKeySize::Aes128 => 16,
         AesKeySize::Aes192 => 24,
         AesKeySize::Aes256 => 32,
     };
-    let mut key = vec![0u8; key_length / 2];
-
-    thread_rng().fill_bytes(&mut key);
+    generate_random_bytes(key_length, encoding)
+}
 
-    key.into_iter()
-        .map(|b| format!("{b:02x}"))
-        .collect::<String>()
+pub fn generate_aes_iv(encoding: InputEncoding) -> String {
+    generate_random_bytes(16, encoding)
 }
 
-pub fn generate_aes_iv() -> String {
-    let mut iv = [0u8; 16 / 2];
-    thread_rng().fill_bytes(&mut iv);
-    iv.into_iter()
-        .map(|b| format!("{b:02x}"))
-        .collect::<String>()
+/// Generates a random key for `cipher` in `encoding`.
+pub fn generate_aead_key(cipher: AeadCipher, encoding: InputEncoding) -> String {
+    generate_random_bytes(cipher.key_size(), encoding)
 }
@@ -0,0 +1,424 @@
+//! Block cipher modes of operation and padding schemes shared by AES, DES and Triple DES.
+//!
+//! ECB and CBC work on whole blocks so the plaintext is padded before encryption and the padding
+//! is removed after decryption. CTR, CFB, CFB8 and OFB turn the block cipher into a stream cipher,
+//! their ciphertext has the length of the plaintext and the padding is ignored.
+//!
+//! [`encrypt_stream`] and [`decrypt_stream`] do the same chunk by chunk, for inputs too large to
+//! hold in memory.
+
+use std::io::{ErrorKind, Read, Write};
+
+use anyhow::{anyhow, Result};
+use cipher::{
+    block_padding::NoPadding,
+    typenum::{IsLess, Le, NonZero, U256},
+    AsyncStreamCipher, BlockCipher, BlockDecryptMut, BlockEncryptMut, KeyInit, KeyIvInit,
+    StreamCipher, StreamCipherCoreWrapper,
+};
+use ctr::{flavors::CtrFlavor, CtrCore};
+use rand::{thread_rng, RngCore};
+
+use crate::core::crypto::{CipherMode, Padding};
+
+/// Size of the chunks read by [`encrypt_stream`] and [`decrypt_stream`].
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Encrypts `data` with the block cipher `C` in `mode`. `F` is the counter used in CTR mode,
+/// it should span the whole block.
+pub fn encrypt<C, F>(
+    mode: CipherMode,
+    padding: Padding,
+    key: &[u8],
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>>
+where
+    C: BlockCipher + BlockEncryptMut + BlockDecryptMut + KeyInit,
+    C::BlockSize: IsLess<U256>,
+    Le<C::BlockSize, U256>: NonZero,
+    F: CtrFlavor<C::BlockSize>,
+{
+    let mut buf = data.to_vec();
+    match mode {
+        CipherMode::ECB => {
+            let buf = pad(padding, data, C::block_size())?;
+            Ok(ecb::Encryptor::<C>::new_from_slice(key)
+                .map_err(|_| anyhow!("Invalid key length"))?
+                .encrypt_padded_vec_mut::<NoPadding>(&buf))
+        }
+        CipherMode::CBC => {
+            let buf = pad(padding, data, C::block_size())?;
+            Ok(cbc::Encryptor::<C>::new_from_slices(key, iv)
+                .map_err(|_| anyhow!("Invalid key or IV length"))?
+                .encrypt_padded_vec_mut::<NoPadding>(&buf))
+        }
+        CipherMode::CFB => {
+            cfb_mode::Encryptor::<C>::new_from_slices(key, iv)
+                .map_err(|_| anyhow!("Invalid key or IV length"))?
+                .encrypt(&mut buf);
+            Ok(buf)
+        }
+        CipherMode::CFB8 => {
+            cfb8::Encryptor::<C>::new_from_slices(key, iv)
+                .map_err(|_| anyhow!("Invalid key or IV length"))?
+                .encrypt(&mut buf);
+            Ok(buf)
+        }
+        CipherMode::CTR | CipherMode::OFB => {
+            apply_keystream::<C, F>(mode, key, iv, &mut buf)?;
+            Ok(buf)
+        }
+    }
+}
+
+/// Decrypts `data` with the block cipher `C` in `mode` and removes the padding.
+pub fn decrypt<C, F>(
+    mode: CipherMode,
+    padding: Padding,
+    key: &[u8],
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>>
+where
+    C: BlockCipher + BlockEncryptMut + BlockDecryptMut + KeyInit,
+    C::BlockSize: IsLess<U256>,
+    Le<C::BlockSize, U256>: NonZero,
+    F: CtrFlavor<C::BlockSize>,
+{
+    let block_size = C::block_size();
+    if mode.uses_padding() && !data.len().is_multiple_of(block_size) {
+        return Err(anyhow!(
+            "Ciphertext length must be a multiple of the {block_size}-byte block size in {mode} mode"
+        ));
+    }
+
+    let mut buf = data.to_vec();
+    match mode {
+        CipherMode::ECB => {
+            let buf = ecb::Decryptor::<C>::new_from_slice(key)
+                .map_err(|_| anyhow!("Invalid key length"))?
+                .decrypt_padded_vec_mut::<NoPadding>(data)
+                .map_err(|_| anyhow!("Decryption failed"))?;
+            unpad(padding, buf, block_size)
+        }
+        CipherMode::CBC => {
+            let buf = cbc::Decryptor::<C>::new_from_slices(key, iv)
+                .map_err(|_| anyhow!("Invalid key or IV length"))?
+                .decrypt_padded_vec_mut::<NoPadding>(data)
+                .map_err(|_| anyhow!("Decryption failed"))?;
+            unpad(padding, buf, block_size)
+        }
+        CipherMode::CFB => {
+            cfb_mode::Decryptor::<C>::new_from_slices(key, iv)
+                .map_err(|_| anyhow!("Invalid key or IV length"))?
+                .decrypt(&mut buf);
+            Ok(buf)
+        }
+        CipherMode::CFB8 => {
+            cfb8::Decryptor::<C>::new_from_slices(key, iv)
+                .map_err(|_| anyhow!("Invalid key or IV length"))?
+                .decrypt(&mut buf);
+            Ok(buf)
+        }
+        CipherMode::CTR | CipherMode::OFB => {
+            apply_keystream::<C, F>(mode, key, iv, &mut buf)?;
+            Ok(buf)
+        }
+    }
+}
+
+/// Encrypts everything read from `reader` into `writer`, returns the number of bytes written.
+pub fn encrypt_stream<C, F>(
+    mode: CipherMode,
+    padding: Padding,
+    key: &[u8],
+    iv: &[u8],
+    reader: &mut dyn Read,
+    writer: &mut dyn Write,
+) -> Result<u64>
+where
+    C: BlockCipher + BlockEncryptMut + BlockDecryptMut + KeyInit + 'static,
+    C::BlockSize: IsLess<U256>,
+    Le<C::BlockSize, U256>: NonZero,
+    F: CtrFlavor<C::BlockSize> + 'static,
+{
+    let block_size = C::block_size();
+    let mut cipher = chunk_cipher::<C, F>(true, mode, key, iv)?;
+    let mut pending = Vec::new();
+    let mut written = 0;
+    while read_chunk(reader, &mut pending)? {
+        // ECB and CBC encrypt whole blocks, the rest is padded at the end
+        let ready = if mode.uses_padding() {
+            pending.len() / block_size * block_size
+        } else {
+            pending.len()
+        };
+        let mut chunk: Vec<u8> = pending.drain(..ready).collect();
+        cipher(&mut chunk)?;
+        writer.write_all(&chunk)?;
+        written += chunk.len() as u64;
+    }
+
+    if mode.uses_padding() {
+        let mut last = pad(padding, &pending, block_size)?;
+        cipher(&mut last)?;
+        writer.write_all(&last)?;
+        written += last.len() as u64;
+    }
+    Ok(written)
+}
+
+/// Decrypts everything read from `reader` into `writer` and removes the padding, returns the
+/// number of bytes written.
+pub fn decrypt_stream<C, F>(
+    mode: CipherMode,
+    padding: Padding,
+    key: &[u8],
+    iv: &[u8],
+    reader: &mut dyn Read,
+    writer: &mut dyn Write,
+) -> Result<u64>
+where
+    C: BlockCipher + BlockEncryptMut + BlockDecryptMut + KeyInit + 'static,
+    C::BlockSize: IsLess<U256>,
+    Le<C::BlockSize, U256>: NonZero,
+    F: CtrFlavor<C::BlockSize> + 'static,
+{
+    let block_size = C::block_size();
+    let mut cipher = chunk_cipher::<C, F>(false, mode, key, iv)?;
+    let mut pending = Vec::new();
+    let mut written = 0;
+    while read_chunk(reader, &mut pending)? {
+        // The last block is held back until the end to remove its padding
+        let ready = if mode.uses_padding() {
+            pending.len().saturating_sub(1) / block_size * block_size
+        } else {
+            pending.len()
+        };
+        let mut chunk: Vec<u8> = pending.drain(..ready).collect();
+        cipher(&mut chunk)?;
+        writer.write_all(&chunk)?;
+        written += chunk.len() as u64;
+    }
+
+    if mode.uses_padding() {
+        if !pending.len().is_multiple_of(block_size) {
+            return Err(anyhow!(
+                "Ciphertext length must be a multiple of the {block_size}-byte block size in {mode} mode"
+            ));
+        }
+        cipher(&mut pending)?;
+        let last = unpad(padding, pending, block_size)?;
+        writer.write_all(&last)?;
+        written += last.len() as u64;
+    }
+    Ok(written)
+}
+
+/// Appends the next [`CHUNK_SIZE`] bytes of `reader` to `buf`, returns false at the end of the
+/// input.
+fn read_chunk(reader: &mut dyn Read, buf: &mut Vec<u8>) -> Result<bool> {
+    let start = buf.len();
+    let mut filled = start;
+    buf.resize(start + CHUNK_SIZE, 0);
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(read) => filled += read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => {
+                buf.truncate(filled);
+                return Err(e.into());
+            }
+        }
+    }
+    buf.truncate(filled);
+    Ok(filled > start)
+}
+
+/// Keeps the state of `mode` between chunks. ECB and CBC are only given whole blocks.
+type ChunkCipher = Box<dyn FnMut(&mut [u8]) -> Result<()>>;
+
+fn chunk_cipher<C, F>(encrypt: bool, mode: CipherMode, key: &[u8], iv: &[u8]) -> Result<ChunkCipher>
+where
+    C: BlockCipher + BlockEncryptMut + BlockDecryptMut + KeyInit + 'static,
+    C::BlockSize: IsLess<U256>,
+    Le<C::BlockSize, U256>: NonZero,
+    F: CtrFlavor<C::BlockSize> + 'static,
+{
+    let block_size = C::block_size();
+    let key_error = |_| anyhow!("Invalid key length");
+    let key_iv_error = |_| anyhow!("Invalid key or IV length");
+    Ok(match (mode, encrypt) {
+        (CipherMode::ECB, true) => {
+            let mut cipher = ecb::Encryptor::<C>::new_from_slice(key).map_err(key_error)?;
+            Box::new(move |buf| {
+                for block in buf.chunks_exact_mut(block_size) {
+                    cipher.encrypt_block_mut(block.into());
+                }
+                Ok(())
+            })
+        }
+        (CipherMode::ECB, false) => {
+            let mut cipher = ecb::Decryptor::<C>::new_from_slice(key).map_err(key_error)?;
+            Box::new(move |buf| {
+                for block in buf.chunks_exact_mut(block_size) {
+                    cipher.decrypt_block_mut(block.into());
+                }
+                Ok(())
+            })
+        }
+        (CipherMode::CBC, true) => {
+            let mut cipher = cbc::Encryptor::<C>::new_from_slices(key, iv).map_err(key_iv_error)?;
+            Box::new(move |buf| {
+                for block in buf.chunks_exact_mut(block_size) {
+                    cipher.encrypt_block_mut(block.into());
+                }
+                Ok(())
+            })
+        }
+        (CipherMode::CBC, false) => {
+            let mut cipher = cbc::Decryptor::<C>::new_from_slices(key, iv).map_err(key_iv_error)?;
+            Box::new(move |buf| {
+                for block in buf.chunks_exact_mut(block_size) {
+                    cipher.decrypt_block_mut(block.into());
+                }
+                Ok(())
+            })
+        }
+        (CipherMode::CFB, true) => {
+            let mut cipher =
+                cfb_mode::BufEncryptor::<C>::new_from_slices(key, iv).map_err(key_iv_error)?;
+            Box::new(move |buf| {
+                cipher.encrypt(buf);
+                Ok(())
+            })
+        }
+        (CipherMode::CFB, false) => {
+            let mut cipher =
+                cfb_mode::BufDecryptor::<C>::new_from_slices(key, iv).map_err(key_iv_error)?;
+            Box::new(move |buf| {
+                cipher.decrypt(buf);
+                Ok(())
+            })
+        }
+        // CFB8 works on blocks of a single byte
+        (CipherMode::CFB8, true) => {
+            let mut cipher =
+                cfb8::Encryptor::<C>::new_from_slices(key, iv).map_err(key_iv_error)?;
+            Box::new(move |buf| {
+                for byte in buf.chunks_exact_mut(1) {
+                    cipher.encrypt_block_mut(byte.into());
+                }
+                Ok(())
+            })
+        }
+        (CipherMode::CFB8, false) => {
+            let mut cipher =
+                cfb8::Decryptor::<C>::new_from_slices(key, iv).map_err(key_iv_error)?;
+            Box::new(move |buf| {
+                for byte in buf.chunks_exact_mut(1) {
+                    cipher.decrypt_block_mut(byte.into());
+                }
+                Ok(())
+            })
+        }
+        (CipherMode::CTR, _) => {
+            let mut cipher = StreamCipherCoreWrapper::<CtrCore<C, F>>::new_from_slices(key, iv)
+                .map_err(key_iv_error)?;
+            Box::new(move |buf| {
+                cipher
+                    .try_apply_keystream(buf)
+                    .map_err(|_| anyhow!("Input is too long for the {mode} keystream"))
+            })
+        }
+        (CipherMode::OFB, _) => {
+            let mut cipher = ofb::Ofb::<C>::new_from_slices(key, iv).map_err(key_iv_error)?;
+            Box::new(move |buf| {
+                cipher
+                    .try_apply_keystream(buf)
+                    .map_err(|_| anyhow!("Input is too long for the {mode} keystream"))
+            })
+        }
+    })
+}
+
+/// CTR and OFB encrypt and decrypt by xoring the same keystream.
+fn apply_keystream<C, F>(mode: CipherMode, key: &[u8], iv: &[u8], buf: &mut [u8]) -> Result<()>
+where
+    C: BlockCipher + BlockEncryptMut + KeyInit,
+    C::BlockSize: IsLess<U256>,
+    Le<C::BlockSize, U256>: NonZero,
+    F: CtrFlavor<C::BlockSize>,
+{
+    if mode == CipherMode::CTR {
+        StreamCipherCoreWrapper::<CtrCore<C, F>>::new_from_slices(key, iv)
+            .map_err(|_| anyhow!("Invalid key or IV length"))?
+            .try_apply_keystream(buf)
+    } else {
+        ofb::Ofb::<C>::new_from_slices(key, iv)
+            .map_err(|_| anyhow!("Invalid key or IV length"))?
+            .try_apply_keystream(buf)
+    }
+    .map_err(|_| anyhow!("Input is too long for the {mode} keystream"))
+}
+
+/// Pads `data` to a multiple of `block_size` bytes.
+pub fn pad(padding: Padding, data: &[u8], block_size: usize) -> Result<Vec<u8>> {
+    let remainder = data.len() % block_size;
+    let pad_len = block_size - remainder;
+    let mut buf = data.to_vec();
+    match padding {
+        Padding::Pkcs7 => buf.resize(data.len() + pad_len, pad_len as u8),
+        // zero padding doesn't add a block to data that is already aligned
+        Padding::Zero if remainder == 0 => {}
+        Padding::Zero => buf.resize(data.len() + pad_len, 0),
+        Padding::Iso10126 => {
+            let mut filler = vec![0u8; pad_len - 1];
+            thread_rng().fill_bytes(&mut filler);
+            buf.extend_from_slice(&filler);
+            buf.push(pad_len as u8);
+        }
+        Padding::AnsiX923 => {
+            buf.resize(data.len() + pad_len - 1, 0);
+            buf.push(pad_len as u8);
+        }
+        Padding::NoPadding if remainder == 0 => {}
+        Padding::NoPadding => {
+            return Err(anyhow!(
+                "Input length must be a multiple of the {block_size}-byte block size without padding, got {} bytes",
+                data.len()
+            ))
+        }
+    }
+    Ok(buf)
+}
+
+/// Removes the padding from the decrypted `data`. Zero padding can't be told apart from trailing
+/// zeros of the plaintext, so all of them are removed.
+pub fn unpad(padding: Padding, mut data: Vec<u8>, block_size: usize) -> Result<Vec<u8>> {
+    let invalid = || anyhow!("Invalid {padding} padding - check the key, IV and padding scheme");
+    let pad_len = match padding {
+        Padding::NoPadding => 0,
+        Padding::Zero => data.iter().rev().take_while(|b| **b == 0).count(),
+        Padding::Pkcs7 | Padding::Iso10126 | Padding::AnsiX923 => {
+            let pad_len = *data.last().ok_or_else(invalid)? as usize;
+            if pad_len == 0 || pad_len > block_size || pad_len > data.len() {
+                return Err(invalid());
+            }
+            let filler = &data[data.len() - pad_len..data.len() - 1];
+            let valid = match padding {
+                Padding::Pkcs7 => filler.iter().all(|b| *b as usize == pad_len),
+                Padding::AnsiX923 => filler.iter().all(|b| *b == 0),
+                _ => true,
+            };
+            if !valid {
+                return Err(invalid());
+            }
+            pad_len
+        }
+    };
+    data.truncate(data.len() - pad_len);
+    Ok(data)
+}
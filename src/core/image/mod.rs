@@ -3,9 +3,11 @@ use image::{DynamicImage, ImageFormat};
 use std::path::Path;
 
 pub mod processor;
+pub mod quantize;
 pub mod types;
 
 pub use processor::ImageProcessor;
+pub use quantize::DominantColor;
 pub use types::*;
 
 /// Load an image from file path
@@ -104,4 +106,3 @@ mod tests {
         );
     }
 }
-
@@ -1,9 +1,15 @@
-use anyhow::{Context, Result};
-use image::{codecs::jpeg::JpegEncoder, imageops, DynamicImage, ImageFormat};
+use anyhow::{anyhow, Context, Result};
+use eframe::egui::Color32;
+use image::{codecs::jpeg::JpegEncoder, imageops, DynamicImage, GenericImageView, ImageFormat};
 use std::io::Cursor;
 use std::path::Path;
 
-use super::types::{CompressionOptions, CropRect, ImageFormatType, ImageInfo, ResizeOptions};
+use crate::core::color::{cvd::ColorVisionDeficiency, Color};
+
+use super::quantize::DominantColor;
+use super::types::{
+    CompressionOptions, CropRect, ImageFormatType, ImageInfo, QuantizeMethod, ResizeOptions,
+};
 
 use super::{calculate_aspect_ratio_dimensions, get_dimensions, load_image, save_image};
 
@@ -203,12 +209,14 @@ impl ImageProcessor {
                 // Use custom JPEG encoder for quality control
                 let mut output = Vec::new();
                 let mut encoder = JpegEncoder::new_with_quality(&mut output, options.quality);
+                // JPEG has no alpha channel, so encode everything as 8-bit RGB
+                let rgb = image.to_rgb8();
                 encoder
                     .encode(
-                        image.as_bytes(),
-                        image.width(),
-                        image.height(),
-                        image.color().into(),
+                        rgb.as_raw(),
+                        rgb.width(),
+                        rgb.height(),
+                        image::ExtendedColorType::Rgb8,
                     )
                     .context("Failed to encode JPEG")?;
 
@@ -242,12 +250,14 @@ impl ImageProcessor {
             ImageFormat::Jpeg => {
                 let mut output = Vec::new();
                 let mut encoder = JpegEncoder::new_with_quality(&mut output, options.quality);
+                // JPEG has no alpha channel, so encode everything as 8-bit RGB
+                let rgb = image.to_rgb8();
                 encoder
                     .encode(
-                        image.as_bytes(),
-                        image.width(),
-                        image.height(),
-                        image.color().into(),
+                        rgb.as_raw(),
+                        rgb.width(),
+                        rgb.height(),
+                        image::ExtendedColorType::Rgb8,
                     )
                     .context("Failed to encode JPEG for size estimation")?;
                 Ok(output.len())
@@ -292,6 +302,69 @@ impl ImageProcessor {
         Ok(())
     }
 
+    /// Render the current image as seen with a color vision deficiency, leaving it unchanged
+    pub fn color_vision_preview(&self, deficiency: ColorVisionDeficiency) -> Result<DynamicImage> {
+        let image = self.current_image.as_ref().context("No image loaded")?;
+
+        Ok(simulate_color_vision(image, deficiency))
+    }
+
+    /// Apply a color vision deficiency simulation
+    pub fn simulate_color_vision(&mut self, deficiency: ColorVisionDeficiency) -> Result<()> {
+        let simulated = self.color_vision_preview(deficiency)?;
+        self.current_image = Some(simulated);
+        Ok(())
+    }
+
+    /// Find the `count` dominant colors of the current image, largest share first
+    pub fn dominant_colors(
+        &self,
+        count: usize,
+        method: QuantizeMethod,
+    ) -> Result<Vec<DominantColor>> {
+        let image = self.current_image.as_ref().context("No image loaded")?;
+
+        Ok(super::quantize::dominant_colors(image, count, method))
+    }
+
+    /// Get the color of the pixel at `x`, `y` of the current image. With `side` above 1 the colors
+    /// of the `side` by `side` square centered at the pixel are averaged, weighted by their alpha.
+    /// The square is clipped to the image bounds.
+    pub fn pick_color(&self, x: u32, y: u32, side: u32) -> Result<Color> {
+        let image = self.current_image.as_ref().context("No image loaded")?;
+        let (width, height) = get_dimensions(image);
+        if x >= width || y >= height {
+            return Err(anyhow!(
+                "Pixel ({x}, {y}) is outside of the {width}x{height} image"
+            ));
+        }
+
+        let half = side.max(1) / 2;
+        let (mut sum, mut alpha_sum, mut count) = ([0f32; 3], 0f32, 0u32);
+        for py in y.saturating_sub(half)..=(y + half).min(height - 1) {
+            for px in x.saturating_sub(half)..=(x + half).min(width - 1) {
+                let [r, g, b, a] = image.get_pixel(px, py).0;
+                let a = a as f32 / 255.;
+                sum[0] += r as f32 * a;
+                sum[1] += g as f32 * a;
+                sum[2] += b as f32 * a;
+                alpha_sum += a;
+                count += 1;
+            }
+        }
+
+        if alpha_sum == 0. {
+            return Ok(Color::from(Color32::TRANSPARENT));
+        }
+        let channel = |sum: f32| (sum / alpha_sum).round() as u8;
+        Ok(Color::from(Color32::from_rgba_unmultiplied(
+            channel(sum[0]),
+            channel(sum[1]),
+            channel(sum[2]),
+            (alpha_sum / count as f32 * 255.).round() as u8,
+        )))
+    }
+
     /// Apply blur effect
     pub fn blur(&mut self, sigma: f32) -> Result<()> {
         let image = self.current_image.as_ref().context("No image loaded")?;
@@ -302,6 +375,16 @@ impl ImageProcessor {
     }
 }
 
+fn simulate_color_vision(image: &DynamicImage, deficiency: ColorVisionDeficiency) -> DynamicImage {
+    let mut rgba_image = image.to_rgba8();
+    for pixel in rgba_image.pixels_mut() {
+        let [r, g, b, a] = pixel.0;
+        let [r, g, b] = deficiency.simulate_srgb8([r, g, b]);
+        pixel.0 = [r, g, b, a];
+    }
+    DynamicImage::ImageRgba8(rgba_image)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::image::FilterType;
@@ -387,6 +470,73 @@ mod tests {
         assert_eq!(original_dims, (rotated_dims.1, rotated_dims.0));
     }
 
+    #[test]
+    fn test_pick_color() {
+        let mut processor = ImageProcessor::new();
+        assert!(processor.pick_color(0, 0, 1).is_err());
+        processor.current_image = Some(create_test_image());
+
+        let pick = |x, y, side| processor.pick_color(x, y, side).unwrap().as_hex();
+        assert_eq!(pick(0, 0, 1), "#ffffff");
+        assert_eq!(pick(1, 0, 1), "#000000");
+        // 5 white and 4 black pixels of the checkerboard
+        assert_eq!(pick(1, 1, 3), "#8e8e8e");
+        // clipped to the 2 white and 2 black pixels in the corner
+        assert_eq!(pick(0, 0, 3), "#808080");
+        assert!(processor.pick_color(100, 0, 1).is_err());
+    }
+
+    #[test]
+    fn test_color_vision_simulation() {
+        let mut processor = ImageProcessor::new();
+        let red: ImageBuffer<Rgb<u8>, Vec<u8>> =
+            ImageBuffer::from_fn(4, 4, |_, _| Rgb([255, 0, 0]));
+        processor.current_image = Some(DynamicImage::ImageRgb8(red));
+
+        let preview = processor
+            .color_vision_preview(ColorVisionDeficiency::Achromatopsia)
+            .unwrap();
+        assert_eq!(preview.to_rgba8().get_pixel(0, 0).0, [127, 127, 127, 255]);
+        // the preview leaves the current image untouched
+        assert_eq!(
+            processor
+                .current_image()
+                .unwrap()
+                .to_rgb8()
+                .get_pixel(0, 0)
+                .0,
+            [255, 0, 0]
+        );
+
+        processor
+            .simulate_color_vision(ColorVisionDeficiency::Protanopia)
+            .unwrap();
+        assert_eq!(
+            processor
+                .current_image()
+                .unwrap()
+                .to_rgb8()
+                .get_pixel(3, 3)
+                .0,
+            [0x6d, 0x5f, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_dominant_colors() {
+        let mut processor = ImageProcessor::new();
+        assert!(processor
+            .dominant_colors(4, QuantizeMethod::KMeans)
+            .is_err());
+
+        processor.current_image = Some(create_test_image());
+        let colors = processor
+            .dominant_colors(4, QuantizeMethod::MedianCut)
+            .unwrap();
+        assert_eq!(colors.len(), 2);
+        assert_eq!(colors[0].share, 0.5);
+    }
+
     #[test]
     fn test_flip() {
         let mut processor = ImageProcessor::new();
@@ -407,4 +557,3 @@ mod tests {
         assert_eq!(original_dims, flipped_dims);
     }
 }
-
@@ -0,0 +1,282 @@
+//! Dominant color extraction by color quantization.
+//!
+//! Large images are downsampled with nearest neighbour sampling first so that the colors of flat
+//! areas, like the ones in logos, are not blended. Pixels that are mostly transparent are ignored.
+
+use image::{imageops::FilterType, DynamicImage};
+use std::collections::HashMap;
+
+use super::types::QuantizeMethod;
+use crate::core::color::{Color, Rgb, U8_MAX};
+
+/// Images with more pixels than this are downsampled before quantization.
+const MAX_SAMPLES: u32 = 256 * 256;
+/// Pixels with a lower alpha are left out.
+const MIN_ALPHA: u8 = 128;
+const MAX_KMEANS_ITERATIONS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DominantColor {
+    pub color: Color,
+    /// Fraction of the sampled pixels that belong to this color in the range of 0.0 ..= 1.0
+    pub share: f32,
+}
+
+/// Returns up to `count` dominant colors of `image` ordered by their share, largest first.
+pub fn dominant_colors(
+    image: &DynamicImage,
+    count: usize,
+    method: QuantizeMethod,
+) -> Vec<DominantColor> {
+    let histogram = histogram(image);
+    if histogram.is_empty() || count == 0 {
+        return vec![];
+    }
+
+    let clusters = match method {
+        QuantizeMethod::MedianCut => median_cut(histogram, count),
+        QuantizeMethod::KMeans => k_means(&histogram, count),
+    };
+
+    let total: u64 = clusters.iter().map(|cluster| cluster.weight).sum();
+    let mut colors: Vec<_> = clusters
+        .into_iter()
+        .filter(|cluster| cluster.weight > 0)
+        .map(|cluster| DominantColor {
+            color: Rgb::new(
+                cluster.mean[0] / U8_MAX,
+                cluster.mean[1] / U8_MAX,
+                cluster.mean[2] / U8_MAX,
+            )
+            .into(),
+            share: cluster.weight as f32 / total as f32,
+        })
+        .collect();
+    colors.sort_by(|a, b| b.share.total_cmp(&a.share));
+    colors
+}
+
+/// Distinct opaque colors of `image` with the number of pixels of each.
+type Histogram = Vec<([u8; 3], u64)>;
+
+struct Cluster {
+    mean: [f32; 3],
+    weight: u64,
+}
+
+impl Cluster {
+    fn from_entries(entries: &[([u8; 3], u64)]) -> Self {
+        let mut sum = [0f64; 3];
+        let mut weight = 0;
+        for (rgb, n) in entries {
+            for (sum, channel) in sum.iter_mut().zip(rgb) {
+                *sum += *channel as f64 * *n as f64;
+            }
+            weight += n;
+        }
+        let mean = if weight > 0 {
+            sum.map(|sum| (sum / weight as f64) as f32)
+        } else {
+            [0.; 3]
+        };
+        Self { mean, weight }
+    }
+}
+
+fn histogram(image: &DynamicImage) -> Histogram {
+    let pixels = image.width() as u64 * image.height() as u64;
+    let rgba = if pixels > MAX_SAMPLES as u64 {
+        let scale = (MAX_SAMPLES as f64 / pixels as f64).sqrt();
+        let width = ((image.width() as f64 * scale) as u32).max(1);
+        let height = ((image.height() as f64 * scale) as u32).max(1);
+        image
+            .resize_exact(width, height, FilterType::Nearest)
+            .to_rgba8()
+    } else {
+        image.to_rgba8()
+    };
+
+    let mut counts = HashMap::new();
+    for pixel in rgba.pixels() {
+        let [r, g, b, a] = pixel.0;
+        if a >= MIN_ALPHA {
+            *counts.entry([r, g, b]).or_insert(0u64) += 1;
+        }
+    }
+
+    let mut histogram: Histogram = counts.into_iter().collect();
+    // keep the result independent of the hash map iteration order
+    histogram.sort_unstable();
+    histogram
+}
+
+/// Repeatedly splits the box with the widest channel range at the weighted median of that channel.
+fn median_cut(histogram: Histogram, count: usize) -> Vec<Cluster> {
+    fn widest_channel(entries: &[([u8; 3], u64)]) -> (usize, u8) {
+        (0..3)
+            .map(|channel| {
+                let (min, max) = entries.iter().fold((u8::MAX, u8::MIN), |(min, max), e| {
+                    (min.min(e.0[channel]), max.max(e.0[channel]))
+                });
+                (channel, max - min)
+            })
+            .max_by_key(|(_, range)| *range)
+            .unwrap_or((0, 0))
+    }
+
+    let mut boxes = vec![histogram];
+    while boxes.len() < count {
+        let Some((idx, channel)) = boxes
+            .iter()
+            .enumerate()
+            .map(|(idx, entries)| (idx, widest_channel(entries)))
+            .filter(|(_, (_, range))| *range > 0)
+            .max_by_key(|(_, (_, range))| *range)
+            .map(|(idx, (channel, _))| (idx, channel))
+        else {
+            break;
+        };
+
+        let mut entries = boxes.swap_remove(idx);
+        entries.sort_unstable_by_key(|e| e.0[channel]);
+        let half = entries.iter().map(|e| e.1).sum::<u64>() / 2;
+        let mut acc = 0;
+        let mut split = entries
+            .iter()
+            .position(|e| {
+                acc += e.1;
+                acc > half
+            })
+            .unwrap_or(0)
+            .max(1);
+        // never split between two entries with the same channel value
+        while split < entries.len() && entries[split].0[channel] == entries[split - 1].0[channel] {
+            split += 1;
+        }
+        if split == entries.len() {
+            split = entries
+                .iter()
+                .rposition(|e| e.0[channel] != entries[entries.len() - 1].0[channel])
+                .map_or(1, |i| i + 1);
+        }
+        let upper = entries.split_off(split);
+        boxes.push(entries);
+        boxes.push(upper);
+    }
+
+    boxes
+        .iter()
+        .map(|entries| Cluster::from_entries(entries))
+        .collect()
+}
+
+/// Lloyd's algorithm in sRGB seeded with the median cut clusters, which keeps the result
+/// deterministic.
+fn k_means(histogram: &Histogram, count: usize) -> Vec<Cluster> {
+    let mut centroids: Vec<[f32; 3]> = median_cut(histogram.clone(), count)
+        .into_iter()
+        .map(|cluster| cluster.mean)
+        .collect();
+    let mut assignments = vec![usize::MAX; histogram.len()];
+
+    for _ in 0..MAX_KMEANS_ITERATIONS {
+        let mut changed = false;
+        for ((rgb, _), assignment) in histogram.iter().zip(assignments.iter_mut()) {
+            let rgb = rgb.map(|c| c as f32);
+            let nearest = centroids
+                .iter()
+                .enumerate()
+                .map(|(idx, centroid)| (idx, distance_squared(&rgb, centroid)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or(0, |(idx, _)| idx);
+            if *assignment != nearest {
+                *assignment = nearest;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![([0f64; 3], 0u64); centroids.len()];
+        for ((rgb, n), assignment) in histogram.iter().zip(&assignments) {
+            let (sum, weight) = &mut sums[*assignment];
+            for (sum, channel) in sum.iter_mut().zip(rgb) {
+                *sum += *channel as f64 * *n as f64;
+            }
+            *weight += n;
+        }
+        for (centroid, (sum, weight)) in centroids.iter_mut().zip(&sums) {
+            if *weight > 0 {
+                *centroid = sum.map(|sum| (sum / *weight as f64) as f32);
+            }
+        }
+    }
+
+    let mut weights = vec![0u64; centroids.len()];
+    for ((_, n), assignment) in histogram.iter().zip(&assignments) {
+        weights[*assignment] += n;
+    }
+    centroids
+        .into_iter()
+        .zip(weights)
+        .map(|(mean, weight)| Cluster { mean, weight })
+        .collect()
+}
+
+fn distance_squared(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgba};
+
+    /// Left 3/4 red, top right quarter blue, bottom right quarter half transparent green.
+    fn create_test_image() -> DynamicImage {
+        let img: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::from_fn(40, 40, |x, y| {
+            if x < 20 {
+                Rgba([255, 0, 0, 255])
+            } else if y < 20 {
+                Rgba([0, 0, 255, 255])
+            } else if x < 30 {
+                Rgba([255, 1, 0, 255])
+            } else {
+                Rgba([0, 255, 0, 64])
+            }
+        });
+        DynamicImage::ImageRgba8(img)
+    }
+
+    #[test]
+    fn extracts_dominant_colors() {
+        let image = create_test_image();
+        for method in QuantizeMethod::all() {
+            let colors = dominant_colors(&image, 2, method);
+            assert_eq!(colors.len(), 2, "{method:?}");
+            assert_eq!(colors[0].color.as_hex(), "#ff0000", "{method:?}");
+            assert_eq!(colors[1].color.as_hex(), "#0000ff", "{method:?}");
+            assert!((colors[0].share - 1000. / 1400.).abs() < 1e-4, "{method:?}");
+            assert!((colors[1].share - 400. / 1400.).abs() < 1e-4, "{method:?}");
+
+            // there are only three opaque colors
+            assert_eq!(dominant_colors(&image, 8, method).len(), 3, "{method:?}");
+        }
+    }
+
+    #[test]
+    fn handles_degenerate_images() {
+        let transparent: ImageBuffer<Rgba<u8>, Vec<u8>> =
+            ImageBuffer::from_fn(8, 8, |_, _| Rgba([255, 255, 255, 0]));
+        let transparent = DynamicImage::ImageRgba8(transparent);
+        assert!(dominant_colors(&transparent, 4, QuantizeMethod::KMeans).is_empty());
+        assert!(dominant_colors(&create_test_image(), 0, QuantizeMethod::MedianCut).is_empty());
+
+        let large: ImageBuffer<Rgba<u8>, Vec<u8>> =
+            ImageBuffer::from_fn(1000, 500, |x, _| Rgba([(x % 2 * 255) as u8, 0, 0, 255]));
+        let colors = dominant_colors(&DynamicImage::ImageRgba8(large), 4, QuantizeMethod::KMeans);
+        assert!(!colors.is_empty() && colors.len() <= 2);
+        assert!((colors.iter().map(|c| c.share).sum::<f32>() - 1.).abs() < 1e-4);
+    }
+}
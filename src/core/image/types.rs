@@ -128,6 +128,26 @@ impl Default for FilterType {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum QuantizeMethod {
+    #[default]
+    KMeans,
+    MedianCut,
+}
+
+impl QuantizeMethod {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            QuantizeMethod::KMeans => "K-means",
+            QuantizeMethod::MedianCut => "Median cut",
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![QuantizeMethod::KMeans, QuantizeMethod::MedianCut]
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CropRect {
     pub x: u32,
@@ -211,4 +231,3 @@ impl ImageInfo {
         }
     }
 }
-
@@ -1,5 +1,7 @@
 #[cfg(target_os = "macos")]
 pub mod macos;
+#[cfg(target_os = "linux")]
+pub mod portal;
 #[cfg(windows)]
 pub mod windows;
 #[cfg(target_os = "linux")]
@@ -18,18 +20,179 @@ pub use x11::DisplayPickerExt;
 pub trait DisplayPickerExt: DisplayPicker {}
 
 use anyhow::Result;
+use eframe::egui::{self, Color32};
+use serde::{Deserialize, Serialize};
 use std::{fmt::Debug, rc::Rc};
 
+/// Size of the square area around the cursor that is sampled when picking a color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SampleSize {
+    #[default]
+    Pixel,
+    Square3,
+    Square5,
+    Square11,
+}
+
+impl SampleSize {
+    pub fn all() -> [Self; 4] {
+        [Self::Pixel, Self::Square3, Self::Square5, Self::Square11]
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Pixel => "1×1",
+            Self::Square3 => "3×3",
+            Self::Square5 => "5×5",
+            Self::Square11 => "11×11",
+        }
+    }
+
+    /// Returns the width and height of the sampled area in pixels.
+    pub fn side(self) -> u16 {
+        match self {
+            Self::Pixel => 1,
+            Self::Square3 => 3,
+            Self::Square5 => 5,
+            Self::Square11 => 11,
+        }
+    }
+}
+
+/// The way the pixels of a sampled area are combined into a single color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SampleMethod {
+    #[default]
+    Average,
+    Median,
+}
+
+impl SampleMethod {
+    pub fn all() -> [Self; 2] {
+        [Self::Average, Self::Median]
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Average => "Average",
+            Self::Median => "Median",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PixelSampling {
+    pub size: SampleSize,
+    pub method: SampleMethod,
+}
+
+impl PixelSampling {
+    /// Combines `pixels` given as RGB triplets into one color. Each channel is reduced on its own,
+    /// returns `None` if there are no pixels.
+    pub fn combine(&self, pixels: &[(u8, u8, u8)]) -> Option<Color> {
+        if pixels.is_empty() {
+            return None;
+        }
+        let channel = |get: fn(&(u8, u8, u8)) -> u8| -> u8 {
+            match self.method {
+                SampleMethod::Average => {
+                    let sum: u32 = pixels.iter().map(|p| get(p) as u32).sum();
+                    (sum as f32 / pixels.len() as f32).round() as u8
+                }
+                SampleMethod::Median => {
+                    let mut values: Vec<u8> = pixels.iter().map(get).collect();
+                    values.sort_unstable();
+                    values[values.len() / 2]
+                }
+            }
+        };
+        Some(Color::from(Color32::from_rgb(
+            channel(|p| p.0),
+            channel(|p| p.1),
+            channel(|p| p.2),
+        )))
+    }
+}
+
 pub trait DisplayPicker: Debug {
     fn get_cursor_pos(&self) -> Result<(i32, i32)>;
     fn get_color_under_cursor(&self) -> Result<Color>;
+
+    /// Whether this picker reads more than the single pixel under the cursor, see
+    /// [`DisplayPicker::get_sampled_color_under_cursor`].
+    fn supports_sampling(&self) -> bool {
+        false
+    }
+
+    /// Returns the color of the area around the cursor described by `sampling`. Pickers that can
+    /// only read a single pixel ignore the sampling.
+    fn get_sampled_color_under_cursor(&self, _sampling: PixelSampling) -> Result<Color> {
+        self.get_color_under_cursor()
+    }
+
+    /// Called when color picking is turned off. Interactive pickers forget the picked color so
+    /// that the next pick asks the user again.
+    fn reset_pick(&self) {}
+
+    /// Returns the state of the pick of interactive pickers, where the user clicks a color on the
+    /// screen instead of the color under the cursor being followed. A pick is started if there is
+    /// none and `ctx` is repainted once it finishes. Other pickers return `None`.
+    fn interactive_pick(&self, _ctx: &egui::Context) -> Option<PickState> {
+        None
+    }
+}
+
+/// State of a pick of an interactive picker, see [`DisplayPicker::interactive_pick`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PickState {
+    Idle,
+    Pending,
+    Picked(Color),
+    Cancelled,
+    Failed(String),
+}
+
+/// Screen picker implementations available on Linux.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinuxBackend {
+    X11,
+    Portal,
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxBackend {
+    /// Returns the backends in the order they should be tried. On Wayland the X11 backend may
+    /// still connect through XWayland but only sees black for native windows, so the portal
+    /// goes first.
+    pub fn order(is_wayland: bool) -> [Self; 2] {
+        if is_wayland {
+            [Self::Portal, Self::X11]
+        } else {
+            [Self::X11, Self::Portal]
+        }
+    }
+
+    /// Whether the current session runs on a Wayland compositor.
+    pub fn is_wayland_session() -> bool {
+        std::env::var_os("WAYLAND_DISPLAY").is_some()
+            || std::env::var("XDG_SESSION_TYPE").is_ok_and(|session| session == "wayland")
+    }
+
+    pub fn connect(self) -> Result<Rc<dyn DisplayPickerExt>> {
+        match self {
+            Self::X11 => x11::X11Conn::new().map(|conn| Rc::new(conn) as Rc<dyn DisplayPickerExt>),
+            Self::Portal => portal::PortalPicker::connect()
+                .map(|picker| Rc::new(picker) as Rc<dyn DisplayPickerExt>),
+        }
+    }
 }
 
 pub fn init_display_picker() -> Option<Rc<dyn DisplayPickerExt>> {
     #[cfg(target_os = "linux")]
-    return x11::X11Conn::new()
-        .ok()
-        .map(|conn| Rc::new(conn) as Rc<dyn DisplayPickerExt>);
+    return LinuxBackend::order(LinuxBackend::is_wayland_session())
+        .into_iter()
+        .find_map(|backend| backend.connect().ok());
     #[cfg(windows)]
     return Some(Rc::new(windows::WinConn::new().ok()?) as Rc<dyn DisplayPickerExt>);
     #[cfg(target_os = "macos")]
@@ -37,3 +200,51 @@ pub fn init_display_picker() -> Option<Rc<dyn DisplayPickerExt>> {
     #[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
     return None;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combines_pixels() {
+        let pixels = [
+            (0, 0, 0),
+            (10, 200, 30),
+            (20, 200, 30),
+            (30, 200, 30),
+            (255, 0, 255),
+        ];
+
+        let average = PixelSampling {
+            size: SampleSize::Square3,
+            method: SampleMethod::Average,
+        };
+        assert_eq!(
+            average.combine(&pixels).unwrap().as_rgb_triplet_scaled(),
+            (63, 120, 69)
+        );
+
+        let median = PixelSampling {
+            method: SampleMethod::Median,
+            ..average
+        };
+        assert_eq!(
+            median.combine(&pixels).unwrap().as_rgb_triplet_scaled(),
+            (20, 200, 30)
+        );
+        assert!(median.combine(&[]).is_none());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn orders_linux_backends() {
+        assert_eq!(
+            LinuxBackend::order(true),
+            [LinuxBackend::Portal, LinuxBackend::X11]
+        );
+        assert_eq!(
+            LinuxBackend::order(false),
+            [LinuxBackend::X11, LinuxBackend::Portal]
+        );
+    }
+}
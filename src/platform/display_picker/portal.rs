@@ -0,0 +1,407 @@
+//! Color picking through the `org.freedesktop.portal.Screenshot` interface of
+//! xdg-desktop-portal.
+//!
+//! Wayland compositors don't let clients read the screen or the cursor position, instead the
+//! portal lets the user click anywhere on the screen and reports the color under the click. The
+//! portal is asked for a color the first time [`DisplayPicker::get_color_under_cursor`] or
+//! [`DisplayPicker::interactive_pick`] is called and the picked color is returned until
+//! [`DisplayPicker::reset_pick`] is called.
+#![cfg(target_os = "linux")]
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use eframe::egui;
+use x11rb::{
+    image::Image,
+    protocol::xproto::{Gcontext, Window},
+    NONE,
+};
+use zbus::{
+    blocking::{Connection, Proxy},
+    zvariant::{OwnedObjectPath, OwnedValue, Value},
+};
+
+use super::{x11::WindowType, DisplayPicker, DisplayPickerExt, PickState};
+use crate::core::color::{Color, Rgb};
+
+const PORTAL_DESTINATION: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const SCREENSHOT_INTERFACE: &str = "org.freedesktop.portal.Screenshot";
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+
+/// `PickColor` was added in the second version of the screenshot portal.
+const MIN_SCREENSHOT_VERSION: u32 = 2;
+
+/// The part of the screenshot portal used by [`PortalPicker`].
+pub trait ScreenshotPortal: Debug + Send + Sync + 'static {
+    /// Lets the user pick a color on the screen and blocks until they are done. Returns the red,
+    /// green and blue channels in the range of 0.0 ..= 1.0 or `None` if the user cancelled.
+    fn pick_color(&self) -> Result<Option<[f64; 3]>>;
+}
+
+/// The screenshot portal reached over the D-Bus session bus.
+#[derive(Debug)]
+pub struct DbusPortal {
+    conn: Connection,
+}
+
+impl DbusPortal {
+    /// Connects to the session bus and checks that the running portal can pick colors.
+    pub fn new() -> Result<Self> {
+        let conn = Connection::session().context("failed to connect to the session bus")?;
+        let version: u32 = Self::screenshot_proxy(&conn)?
+            .get_property("version")
+            .context("screenshot portal is not available")?;
+        if version < MIN_SCREENSHOT_VERSION {
+            bail!("screenshot portal version {version} doesn't support picking colors");
+        }
+
+        Ok(Self { conn })
+    }
+
+    fn screenshot_proxy(conn: &Connection) -> Result<Proxy<'static>> {
+        Proxy::new(conn, PORTAL_DESTINATION, PORTAL_PATH, SCREENSHOT_INTERFACE)
+            .context("failed to create screenshot portal proxy")
+    }
+
+    /// Returns the object path of the request that the portal creates for `token`, see the
+    /// documentation of `org.freedesktop.portal.Request`.
+    fn request_path(&self, token: &str) -> Result<String> {
+        let sender = self
+            .conn
+            .unique_name()
+            .context("session bus connection has no unique name")?
+            .trim_start_matches(':')
+            .replace('.', "_");
+        Ok(format!("{PORTAL_PATH}/request/{sender}/{token}"))
+    }
+}
+
+impl ScreenshotPortal for DbusPortal {
+    fn pick_color(&self) -> Result<Option<[f64; 3]>> {
+        static REQUEST_COUNTER: AtomicU32 = AtomicU32::new(0);
+        let token = format!(
+            "dev_tools_{}_{}",
+            std::process::id(),
+            REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+
+        // subscribing before the call makes sure that a quick response is not missed
+        let request_path = self.request_path(&token)?;
+        let request = Proxy::new(
+            &self.conn,
+            PORTAL_DESTINATION,
+            request_path.as_str(),
+            REQUEST_INTERFACE,
+        )
+        .context("failed to create portal request proxy")?;
+        let mut responses = request
+            .receive_signal("Response")
+            .context("failed to subscribe to portal responses")?;
+
+        let options = HashMap::from([("handle_token", Value::from(token.as_str()))]);
+        let _handle: OwnedObjectPath = Self::screenshot_proxy(&self.conn)?
+            .call("PickColor", &("", options))
+            .context("failed to call PickColor")?;
+
+        let message = responses
+            .next()
+            .context("portal closed the request without a response")?;
+        let (response, results): (u32, HashMap<String, OwnedValue>) = message
+            .body()
+            .deserialize()
+            .context("failed to deserialize portal response")?;
+
+        match response {
+            0 => {
+                let color = results
+                    .get("color")
+                    .context("portal response is missing the color")?
+                    .try_clone()
+                    .context("failed to read picked color")?;
+                let (r, g, b) = <(f64, f64, f64)>::try_from(color)
+                    .context("failed to deserialize picked color")?;
+                Ok(Some([r, g, b]))
+            }
+            1 => Ok(None),
+            _ => Err(anyhow!("portal failed to pick a color")),
+        }
+    }
+}
+
+/// A [`DisplayPicker`] that asks a [`ScreenshotPortal`] for colors on a background thread.
+#[derive(Debug)]
+pub struct PortalPicker<P: ScreenshotPortal> {
+    portal: Arc<P>,
+    state: Arc<Mutex<PickState>>,
+}
+
+impl PortalPicker<DbusPortal> {
+    pub fn connect() -> Result<Self> {
+        DbusPortal::new().map(Self::new)
+    }
+}
+
+impl<P: ScreenshotPortal> PortalPicker<P> {
+    pub fn new(portal: P) -> Self {
+        Self {
+            portal: Arc::new(portal),
+            state: Arc::new(Mutex::new(PickState::Idle)),
+        }
+    }
+
+    fn state(&self) -> PickState {
+        self.state
+            .lock()
+            .map(|state| state.clone())
+            .unwrap_or_else(|e| PickState::Failed(e.to_string()))
+    }
+
+    /// Asks the portal for a color on a background thread, `repaint` is repainted once the user
+    /// is done.
+    fn start_pick(&self, repaint: Option<egui::Context>) {
+        if let Ok(mut state) = self.state.lock() {
+            *state = PickState::Pending;
+        }
+        let portal = Arc::clone(&self.portal);
+        let state = Arc::clone(&self.state);
+        std::thread::spawn(move || {
+            let result = match portal.pick_color() {
+                Ok(Some([r, g, b])) => {
+                    PickState::Picked(Color::from(Rgb::new(r as f32, g as f32, b as f32)))
+                }
+                Ok(None) => PickState::Cancelled,
+                Err(e) => PickState::Failed(format!("{e:?}")),
+            };
+            if let Ok(mut state) = state.lock() {
+                *state = result;
+            }
+            if let Some(ctx) = repaint {
+                ctx.request_repaint();
+            }
+        });
+    }
+}
+
+impl<P: ScreenshotPortal> DisplayPicker for PortalPicker<P> {
+    fn get_cursor_pos(&self) -> Result<(i32, i32)> {
+        Err(anyhow!(
+            "cursor position is not available through the portal"
+        ))
+    }
+
+    fn get_color_under_cursor(&self) -> Result<Color> {
+        match self.state() {
+            PickState::Idle => {
+                self.start_pick(None);
+                Err(anyhow!("waiting for a color to be picked"))
+            }
+            PickState::Pending => Err(anyhow!("waiting for a color to be picked")),
+            PickState::Picked(color) => Ok(color),
+            PickState::Cancelled => Err(anyhow!("color picking was cancelled")),
+            PickState::Failed(e) => Err(anyhow!("failed to pick a color, {e}")),
+        }
+    }
+
+    fn reset_pick(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            if *state != PickState::Pending {
+                *state = PickState::Idle;
+            }
+        }
+    }
+
+    fn interactive_pick(&self, ctx: &egui::Context) -> Option<PickState> {
+        match self.state() {
+            PickState::Idle => {
+                self.start_pick(Some(ctx.clone()));
+                Some(PickState::Pending)
+            }
+            state => Some(state),
+        }
+    }
+}
+
+impl<P: ScreenshotPortal> DisplayPickerExt for PortalPicker<P> {
+    fn has_zoom_window(&self) -> bool {
+        false
+    }
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+    fn spawn_window(
+        &self,
+        _title: &str,
+        _x: i16,
+        _y: i16,
+        _width: u16,
+        _height: u16,
+        _screen_num: usize,
+        _window_type: WindowType,
+    ) -> Result<(Window, Gcontext)> {
+        Err(anyhow!("zoom window is not supported by the portal picker"))
+    }
+    fn destroy_window(&self, _window: Window) -> Result<()> {
+        Ok(())
+    }
+    fn update_window_pos(&self, _win_id: Window, _x: i32, _y: i32) -> Result<()> {
+        Ok(())
+    }
+    fn screen_num(&self) -> usize {
+        0
+    }
+    fn get_image(
+        &self,
+        _window: Window,
+        _x: i16,
+        _y: i16,
+        _width: u16,
+        _height: u16,
+    ) -> Result<Image<'_>> {
+        Err(anyhow!(
+            "screen contents are not available through the portal"
+        ))
+    }
+    fn put_image(&self, _window: Window, _gc: Gcontext, _image: &Image) -> Result<()> {
+        Ok(())
+    }
+    fn root_window(&self) -> Window {
+        NONE
+    }
+    fn draw_circle(
+        &self,
+        _window: Window,
+        _gc: Gcontext,
+        _x: i16,
+        _y: i16,
+        _diameter: u16,
+    ) -> Result<()> {
+        Ok(())
+    }
+    fn draw_rectangle(
+        &self,
+        _window: Window,
+        _gc: Gcontext,
+        _x: i16,
+        _y: i16,
+        _width: u16,
+        _height: u16,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::VecDeque, time::Duration};
+
+    /// An in-process portal answering with queued responses.
+    #[derive(Debug, Default)]
+    struct FakePortal {
+        responses: Mutex<VecDeque<Result<Option<[f64; 3]>>>>,
+    }
+
+    impl FakePortal {
+        fn with_responses(responses: impl IntoIterator<Item = Result<Option<[f64; 3]>>>) -> Self {
+            Self {
+                responses: Mutex::new(responses.into_iter().collect()),
+            }
+        }
+    }
+
+    impl ScreenshotPortal for FakePortal {
+        fn pick_color(&self) -> Result<Option<[f64; 3]>> {
+            self.responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or_else(|| Err(anyhow!("no more responses")))
+        }
+    }
+
+    fn wait_for_pick<P: ScreenshotPortal>(picker: &PortalPicker<P>) -> Result<Color> {
+        for _ in 0..100 {
+            if picker.state() != PickState::Pending {
+                return picker.get_color_under_cursor();
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        panic!("portal did not respond");
+    }
+
+    #[test]
+    fn picks_colors_through_portal() {
+        let picker = PortalPicker::new(FakePortal::with_responses([
+            Ok(Some([1., 0.5, 0.])),
+            Ok(None),
+            Err(anyhow!("portal crashed")),
+        ]));
+
+        // the first call starts the pick and the color is kept until reset
+        assert!(picker.get_color_under_cursor().is_err());
+        let color = wait_for_pick(&picker).unwrap();
+        assert_eq!(color.as_rgb_triplet_scaled(), (255, 128, 0));
+        assert_eq!(picker.get_color_under_cursor().unwrap(), color);
+
+        picker.reset_pick();
+        assert!(picker.get_color_under_cursor().is_err());
+        let err = wait_for_pick(&picker).unwrap_err();
+        assert!(err.to_string().contains("cancelled"), "{err}");
+
+        picker.reset_pick();
+        picker.get_color_under_cursor().unwrap_err();
+        let err = wait_for_pick(&picker).unwrap_err();
+        assert!(err.to_string().contains("portal crashed"), "{err}");
+
+        assert!(picker.get_cursor_pos().is_err());
+        assert!(!picker.has_zoom_window());
+        assert!(!picker.supports_sampling());
+    }
+
+    #[test]
+    fn repaints_when_interactive_pick_finishes() {
+        let picker = PortalPicker::new(FakePortal::with_responses([
+            Ok(Some([0., 0., 1.])),
+            Err(anyhow!("portal crashed")),
+        ]));
+        let ctx = egui::Context::default();
+        let (sender, repaints) = std::sync::mpsc::channel();
+        ctx.set_request_repaint_callback(move |_| {
+            let _ = sender.send(());
+        });
+        // the callback only fires for repaints requested after every pending one was painted
+        let settle = || {
+            while ctx.has_requested_repaint() {
+                let _ = ctx.run(Default::default(), |_| {});
+            }
+            while repaints.try_recv().is_ok() {}
+        };
+        let timeout = Duration::from_secs(5);
+
+        settle();
+        assert_eq!(picker.interactive_pick(&ctx), Some(PickState::Pending));
+        repaints.recv_timeout(timeout).unwrap();
+        assert!(matches!(
+            picker.interactive_pick(&ctx),
+            Some(PickState::Picked(color)) if color.as_rgb_triplet_scaled() == (0, 0, 255)
+        ));
+
+        settle();
+        picker.reset_pick();
+        assert_eq!(picker.interactive_pick(&ctx), Some(PickState::Pending));
+        repaints.recv_timeout(timeout).unwrap();
+        assert!(matches!(
+            picker.interactive_pick(&ctx),
+            Some(PickState::Failed(e)) if e.contains("portal crashed")
+        ));
+    }
+}
@@ -2,7 +2,7 @@
 use std::borrow::Cow;
 
 use crate::core::color::Color;
-use crate::platform::display_picker::DisplayPicker;
+use crate::platform::display_picker::{DisplayPicker, PixelSampling};
 use anyhow::{Context, Result};
 use eframe::egui::Color32;
 use image::{imageops, ImageBuffer, Rgba};
@@ -12,7 +12,7 @@ use x11rb::{
     image::Image,
     protocol::xproto::{
         Arc, AtomEnum, ConfigureWindowAux, ConnectionExt, CreateGCAux, CreateWindowAux, EventMask,
-        Gcontext, Gravity, PropMode, Screen, Window, WindowClass,
+        Gcontext, Gravity, PropMode, Rectangle, Screen, Window, WindowClass,
     },
     resource_manager::Database,
     rust_connection::RustConnection,
@@ -74,6 +74,15 @@ pub fn add_border<'a>(img: &'a Image, color: &Rgba<u8>, width: u32) -> Result<Im
     .context("failed to create a new image with border")
 }
 
+/// Extracts the RGB channels of a pixel read from the root window.
+fn pixel_rgb(pixel: u32) -> (u8, u8, u8) {
+    let red = (pixel >> 8) & 0xff;
+    let green = (pixel >> 16) & 0xff;
+    let blue = (pixel >> 24) & 0xff;
+
+    (red as u8, green as u8, blue as u8)
+}
+
 pub enum WindowType {
     Desktop,
     Dock,
@@ -103,7 +112,10 @@ impl WindowType {
 }
 
 pub trait DisplayPickerExt: DisplayPicker {
-    fn conn(&self) -> &RustConnection;
+    /// Whether this picker can show the zoomed window around the cursor.
+    fn has_zoom_window(&self) -> bool {
+        true
+    }
     fn flush(&self) -> Result<()>;
     #[allow(clippy::too_many_arguments)]
     fn spawn_window(
@@ -120,7 +132,8 @@ pub trait DisplayPickerExt: DisplayPicker {
     fn update_window_pos(&self, win_id: Window, x: i32, y: i32) -> Result<()>;
     fn screen_num(&self) -> usize;
     fn get_image(&self, window: Window, x: i16, y: i16, width: u16, height: u16) -> Result<Image>;
-    fn screen(&self) -> &Screen;
+    fn put_image(&self, window: Window, gc: Gcontext, image: &Image) -> Result<()>;
+    fn root_window(&self) -> Window;
     fn draw_circle(
         &self,
         window: Window,
@@ -129,6 +142,15 @@ pub trait DisplayPickerExt: DisplayPicker {
         y: i16,
         diameter: u16,
     ) -> Result<()>;
+    fn draw_rectangle(
+        &self,
+        window: Window,
+        gc: Gcontext,
+        x: i16,
+        y: i16,
+        width: u16,
+        height: u16,
+    ) -> Result<()>;
 }
 
 #[derive(Debug)]
@@ -185,13 +207,32 @@ impl X11Conn {
 
     pub fn get_color(&self, window: Window, x: i16, y: i16) -> Result<(u8, u8, u8)> {
         let img = self.get_image(window, x, y, 1, 1)?;
-        let pixel = img.get_pixel(0, 0);
-
-        let red = (pixel >> 8) & 0xff;
-        let green = (pixel >> 16) & 0xff;
-        let blue = (pixel >> 24) & 0xff;
+        Ok(pixel_rgb(img.get_pixel(0, 0)))
+    }
 
-        Ok((red as u8, green as u8, blue as u8))
+    /// Returns the colors of the `side` by `side` square centered at `x`, `y`. The square is moved
+    /// inside of the screen when the center is close to its edges.
+    pub fn get_colors(
+        &self,
+        screen: &Screen,
+        x: i16,
+        y: i16,
+        side: u16,
+    ) -> Result<Vec<(u8, u8, u8)>> {
+        let half = (side / 2) as i16;
+        let max_x = (screen.width_in_pixels.saturating_sub(side)) as i16;
+        let max_y = (screen.height_in_pixels.saturating_sub(side)) as i16;
+        let x = (x - half).clamp(0, max_x);
+        let y = (y - half).clamp(0, max_y);
+
+        let img = self.get_image(screen.root, x, y, side, side)?;
+        let mut pixels = Vec::with_capacity(side as usize * side as usize);
+        for y in 0..img.height() {
+            for x in 0..img.width() {
+                pixels.push(pixel_rgb(img.get_pixel(x, y)));
+            }
+        }
+        Ok(pixels)
     }
 
     pub fn get_color_for_screen(&self, screen: &Screen) -> Result<(u8, u8, u8)> {
@@ -397,6 +438,30 @@ impl X11Conn {
             .context("failed to draw a circle")
             .map(|_| ())
     }
+
+    pub fn draw_rectangle(
+        &self,
+        window: Window,
+        gc: Gcontext,
+        x: i16,
+        y: i16,
+        width: u16,
+        height: u16,
+    ) -> Result<()> {
+        self.conn
+            .poly_rectangle(
+                window,
+                gc,
+                &[Rectangle {
+                    x,
+                    y,
+                    width,
+                    height,
+                }],
+            )
+            .context("failed to draw a rectangle")
+            .map(|_| ())
+    }
 }
 
 impl DisplayPicker for X11Conn {
@@ -409,12 +474,26 @@ impl DisplayPicker for X11Conn {
         self.get_color_for_conn()
             .map(|color| Color32::from_rgb(color.0, color.1, color.2).into())
     }
+
+    fn supports_sampling(&self) -> bool {
+        true
+    }
+
+    fn get_sampled_color_under_cursor(&self, sampling: PixelSampling) -> Result<Color> {
+        let side = sampling.size.side();
+        if side == 1 {
+            return self.get_color_under_cursor();
+        }
+        let screen = self.screen();
+        let (x, y) = self.get_cursor_xy(screen.root)?;
+        let pixels = self.get_colors(screen, x, y, side)?;
+        sampling
+            .combine(&pixels)
+            .context("no pixels sampled under cursor")
+    }
 }
 
 impl DisplayPickerExt for X11Conn {
-    fn conn(&self) -> &RustConnection {
-        self.conn()
-    }
     fn flush(&self) -> Result<()> {
         self.flush()
     }
@@ -442,8 +521,14 @@ impl DisplayPickerExt for X11Conn {
     fn get_image(&self, window: Window, x: i16, y: i16, width: u16, height: u16) -> Result<Image> {
         self.get_image(window, x, y, width, height)
     }
-    fn screen(&self) -> &Screen {
-        self.screen()
+    fn put_image(&self, window: Window, gc: Gcontext, image: &Image) -> Result<()> {
+        image
+            .put(self.conn(), window, gc, 0, 0)
+            .context("failed to put image")
+            .map(|_| ())
+    }
+    fn root_window(&self) -> Window {
+        self.screen().root
     }
     fn draw_circle(
         &self,
@@ -455,4 +540,15 @@ impl DisplayPickerExt for X11Conn {
     ) -> Result<()> {
         self.draw_circle(window, gc, x, y, diameter)
     }
+    fn draw_rectangle(
+        &self,
+        window: Window,
+        gc: Gcontext,
+        x: i16,
+        y: i16,
+        width: u16,
+        height: u16,
+    ) -> Result<()> {
+        self.draw_rectangle(window, gc, x, y, width, height)
+    }
 }
@@ -5,12 +5,15 @@ use anyhow::{Context, Result};
 use eframe::Storage;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     path::{Path, PathBuf},
 };
 
-use crate::{core::color::*, ui::components::layout::HarmonyLayout, APP_NAME};
+use crate::{
+    core::color::*, platform::display_picker::PixelSampling, ui::components::layout::HarmonyLayout,
+    APP_NAME,
+};
 
 pub const DEFAULT_PIXELS_PER_POINT: f32 = 1.0;
 
@@ -77,25 +80,54 @@ pub struct Settings {
     pub color_display_format: ColorDisplayFmtEnum,
     #[serde(default)]
     pub color_clipboard_format: Option<ColorDisplayFmtEnum>,
-    // #[serde(default)]
-    // pub palette_clipboard_format: PaletteFormat,
+    #[serde(default)]
+    pub palette_clipboard_format: PaletteFormat,
     #[serde(default)]
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub saved_color_formats: HashMap<String, String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub saved_palette_formats: HashMap<String, CustomPaletteFormat>,
+    #[serde(default)]
+    pub harmony: harmony::ColorHarmony,
+    #[serde(default)]
+    pub harmony_layout: HarmonyLayout,
+    #[serde(default = "default_color_size")]
+    #[serde(skip_serializing_if = "is_default_color_size")]
+    pub harmony_color_size: f32,
+    #[serde(default)]
+    pub rgb_working_space: RgbWorkingSpace,
+    #[serde(default)]
+    pub illuminant: Illuminant,
+    #[serde(default)]
+    pub color_name_metric: delta_e::DeltaE,
     #[serde(default = "enabled")]
     #[serde(skip_serializing_if = "is_true")]
     pub is_dark_mode: bool,
     #[serde(default = "enabled")]
     #[serde(skip_serializing_if = "is_true")]
     pub cache_colors: bool,
+    #[serde(default = "default_history_limit")]
+    #[serde(skip_serializing_if = "is_default_history_limit")]
+    pub color_history_limit: usize,
     #[serde(default)]
     #[serde(skip_serializing_if = "is_false")]
     pub auto_copy_picked_color: bool,
+    #[serde(default)]
+    pub pixel_sampling: PixelSampling,
     #[serde(default = "default_pixels_per_point")]
     #[serde(skip_serializing_if = "is_default_pixels_per_point")]
     pub pixels_per_point: f32,
 }
 
+fn default_history_limit() -> usize {
+    history::DEFAULT_HISTORY_LIMIT
+}
+
+fn is_default_history_limit(limit: &usize) -> bool {
+    *limit == history::DEFAULT_HISTORY_LIMIT
+}
+
 fn default_pixels_per_point() -> f32 {
     DEFAULT_PIXELS_PER_POINT
 }
@@ -110,9 +142,19 @@ impl Default for Settings {
             is_dark_mode: true,
             color_display_format: ColorDisplayFmtEnum::default(),
             color_clipboard_format: None,
+            palette_clipboard_format: PaletteFormat::default(),
             saved_color_formats: HashMap::default(),
+            saved_palette_formats: HashMap::default(),
+            harmony: harmony::ColorHarmony::default(),
+            harmony_layout: HarmonyLayout::default(),
+            harmony_color_size: DEFAULT_COLOR_SIZE,
+            rgb_working_space: RgbWorkingSpace::default(),
+            illuminant: Illuminant::default(),
+            color_name_metric: delta_e::DeltaE::default(),
             cache_colors: true,
+            color_history_limit: history::DEFAULT_HISTORY_LIMIT,
             auto_copy_picked_color: false,
+            pixel_sampling: PixelSampling::default(),
             pixels_per_point: DEFAULT_PIXELS_PER_POINT,
         }
     }
@@ -153,6 +195,58 @@ impl Settings {
 
         None
     }
+
+    /// Returns a copy of the saved custom color and palette formats.
+    pub fn custom_formats(&self) -> CustomFormats {
+        CustomFormats {
+            color_formats: self.saved_color_formats.clone().into_iter().collect(),
+            palette_formats: self.saved_palette_formats.clone().into_iter().collect(),
+        }
+    }
+
+    /// Saves all formats from `formats`, replacing saved formats with the same name. Returns the
+    /// number of imported formats.
+    pub fn import_custom_formats(&mut self, formats: CustomFormats) -> usize {
+        let count = formats.color_formats.len() + formats.palette_formats.len();
+        self.saved_color_formats.extend(formats.color_formats);
+        self.saved_palette_formats.extend(formats.palette_formats);
+        count
+    }
+}
+
+/// A collection of named custom formats that can be shared as a YAML file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CustomFormats {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub color_formats: BTreeMap<String, String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub palette_formats: BTreeMap<String, CustomPaletteFormat>,
+}
+
+impl CustomFormats {
+    pub const FILE_NAME: &'static str = "formats.yaml";
+
+    pub fn from_yaml_str(yaml: &str) -> Result<Self> {
+        serde_yaml::from_str(yaml).context("failed to deserialize custom formats from YAML")
+    }
+
+    pub fn as_yaml_str(&self) -> Result<String> {
+        serde_yaml::to_string(&self).context("failed to serialize custom formats as YAML")
+    }
+
+    /// Loads the formats from the YAML file located at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let data = fs::read_to_string(path).context("failed to read custom formats file")?;
+        Self::from_yaml_str(&data)
+    }
+
+    /// Saves these formats as YAML file in the provided `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let yaml = self.as_yaml_str()?;
+        fs::write(path, yaml).context("failed to write custom formats to file")
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Default)]
@@ -166,6 +260,30 @@ pub enum ColorDisplayFmtEnum {
     CssRgb,
     #[serde(rename = "css-hsl")]
     CssHsl,
+    #[serde(rename = "css-lab")]
+    CssLab,
+    #[serde(rename = "css-lch")]
+    CssLch,
+    #[serde(rename = "css-oklab")]
+    CssOklab,
+    #[serde(rename = "css-oklch")]
+    CssOklch,
+    #[serde(rename = "swift-uicolor")]
+    SwiftUiColor,
+    #[serde(rename = "swiftui")]
+    SwiftUi,
+    #[serde(rename = "android-parse-color")]
+    AndroidParseColor,
+    #[serde(rename = "android-argb")]
+    AndroidArgb,
+    #[serde(rename = "jetpack-compose")]
+    JetpackCompose,
+    #[serde(rename = "flutter")]
+    Flutter,
+    #[serde(rename = "dotnet")]
+    DotNet,
+    #[serde(rename = "unity")]
+    Unity,
 }
 
 impl AsRef<str> for ColorDisplayFmtEnum {
@@ -176,12 +294,110 @@ impl AsRef<str> for ColorDisplayFmtEnum {
             HexUppercase => "hex uppercase",
             CssRgb => "css rgb",
             CssHsl => "css hsl",
+            CssLab => "css lab",
+            CssLch => "css lch",
+            CssOklab => "css oklab",
+            CssOklch => "css oklch",
+            SwiftUiColor => "swift uicolor",
+            SwiftUi => "swiftui",
+            AndroidParseColor => "android parseColor",
+            AndroidArgb => "android argb",
+            JetpackCompose => "jetpack compose",
+            Flutter => "flutter",
+            DotNet => ".net",
+            Unity => "unity",
         }
     }
 }
 
 impl ColorDisplayFmtEnum {
+    pub fn all() -> [Self; 16] {
+        use ColorDisplayFmtEnum::*;
+        [
+            Hex,
+            HexUppercase,
+            CssRgb,
+            CssHsl,
+            CssLab,
+            CssLch,
+            CssOklab,
+            CssOklch,
+            SwiftUiColor,
+            SwiftUi,
+            AndroidParseColor,
+            AndroidArgb,
+            JetpackCompose,
+            Flutter,
+            DotNet,
+            Unity,
+        ]
+    }
+
+    /// Returns the color format used to display colors in this format.
+    pub fn color_format(&self, degree_symbol: bool) -> ColorFormat {
+        use ColorDisplayFmtEnum::*;
+        match self {
+            Hex => ColorFormat::Hex,
+            HexUppercase => ColorFormat::HexUpercase,
+            CssRgb => ColorFormat::CssRgb,
+            CssHsl => ColorFormat::CssHsl { degree_symbol },
+            CssLab => ColorFormat::CssLab,
+            CssLch => ColorFormat::CssLch,
+            CssOklab => ColorFormat::CssOklab,
+            CssOklch => ColorFormat::CssOklch,
+            SwiftUiColor => ColorFormat::SwiftUiColor,
+            SwiftUi => ColorFormat::SwiftUi,
+            AndroidParseColor => ColorFormat::AndroidParseColor,
+            AndroidArgb => ColorFormat::AndroidArgb,
+            JetpackCompose => ColorFormat::JetpackCompose,
+            Flutter => ColorFormat::Flutter,
+            DotNet => ColorFormat::DotNet,
+            Unity => ColorFormat::Unity,
+        }
+    }
+
     pub fn default_display_format() -> ColorFormat {
         ColorFormat::Hex
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_custom_formats() {
+        let yaml = r#"
+color_formats:
+  rgb: "rgb({r255}, {g255}, {b255})"
+palette_formats:
+  list:
+    prefix: "["
+    entry_format: "'{r255:x}{g255:x}{b255:x}', "
+    suffix: "]"
+"#;
+        let formats = CustomFormats::from_yaml_str(yaml).unwrap();
+        assert_eq!(formats.color_formats["rgb"], "rgb({r255}, {g255}, {b255})");
+        assert_eq!(formats.palette_formats["list"].prefix, "[");
+
+        let mut settings = Settings::default();
+        settings
+            .saved_color_formats
+            .insert("rgb".into(), "{r} {g} {b}".into());
+        assert_eq!(settings.import_custom_formats(formats.clone()), 2);
+        assert_eq!(
+            settings.saved_color_formats["rgb"],
+            "rgb({r255}, {g255}, {b255})"
+        );
+        assert_eq!(settings.custom_formats(), formats);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CustomFormats::FILE_NAME);
+        formats.save(&path).unwrap();
+        assert_eq!(CustomFormats::load(&path).unwrap(), formats);
+        assert_eq!(
+            CustomFormats::from_yaml_str("{}").unwrap(),
+            CustomFormats::default()
+        );
+    }
+}
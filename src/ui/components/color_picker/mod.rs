@@ -10,7 +10,7 @@ use std::mem;
 
 use super::{slider_1d, slider_2d};
 use crate::{
-    core::color::{Cmyk, Color, Hsl, Hsv, Rgb, U8_MAX, U8_MIN},
+    core::color::{Cmyk, Color, Hsl, Hsv, Lab, LchAb, Oklab, Oklch, Rgb, U8_MAX, U8_MIN},
     utils::math,
 };
 
@@ -23,6 +23,14 @@ macro_rules! slider {
             $ui.label(format!("{}: ", $label));
             $ui.add(DragValue::new(&mut $it.sliders.$field));
     };
+    (fine $it:ident, $ui:ident, $field:ident, $label:literal, $range:expr, $($tt:tt)+) => {
+            let resp = slider_1d::color(&mut $ui, &mut $it.sliders.$field, $range, $($tt)+).on_hover_text($label);
+            if resp.changed() {
+                $it.check_for_change();
+            }
+            $ui.label(format!("{}: ", $label));
+            $ui.add(DragValue::new(&mut $it.sliders.$field).speed(0.001));
+    };
     (int $it:ident, $ui:ident, $field:ident, $label:literal, $range:expr, $($tt:tt)+) => {
             let resp = slider_1d::color(&mut $ui, &mut $it.sliders.$field, $range, $($tt)+).on_hover_text($label);
             if resp.changed() {
@@ -76,6 +84,20 @@ impl ColorPicker {
         }
     }
 
+    /// Returns the alpha of the sliders in the range of 0.0 ..= 1.0
+    fn alpha(&self) -> f32 {
+        self.sliders.alpha / 100.
+    }
+
+    fn alpha_changed(&mut self) -> bool {
+        if !math::eq_f32(self.sliders.alpha, self.current_color.alpha() * 100.) {
+            self.set_cur_color(self.current_color.with_alpha(self.alpha()));
+            true
+        } else {
+            false
+        }
+    }
+
     fn rgb_changed(&mut self) -> bool {
         let rgb = self.current_color.rgb();
         let r = self.sliders.r;
@@ -86,7 +108,9 @@ impl ColorPicker {
             || !math::eq_f32(b, rgb.b_scaled())
         {
             self.saved_sliders = None;
-            self.set_cur_color(Rgb::new(r / U8_MAX, g / U8_MAX, b / U8_MAX));
+            self.set_cur_color(
+                Rgb::new(r / U8_MAX, g / U8_MAX, b / U8_MAX).with_alpha(self.alpha()),
+            );
             true
         } else {
             false
@@ -105,12 +129,15 @@ impl ColorPicker {
             } else if self.sliders.k < 100. {
                 self.restore_sliders_if_saved();
             }
-            self.set_cur_color(Cmyk::new(
-                self.sliders.c / 100.,
-                self.sliders.m / 100.,
-                self.sliders.y / 100.,
-                self.sliders.k / 100.,
-            ));
+            self.set_cur_color(
+                Cmyk::new(
+                    self.sliders.c / 100.,
+                    self.sliders.m / 100.,
+                    self.sliders.y / 100.,
+                    self.sliders.k / 100.,
+                )
+                .with_alpha(self.alpha()),
+            );
             true
         } else {
             false
@@ -132,7 +159,7 @@ impl ColorPicker {
                 self.sliders.hue / 360.,
                 self.sliders.sat / 100.,
                 self.sliders.val / 100.,
-                1.,
+                self.alpha(),
             ));
             true
         } else {
@@ -146,11 +173,90 @@ impl ColorPicker {
             || !math::eq_f32(self.sliders.hsl_s, hsl.s_scaled())
             || !math::eq_f32(self.sliders.hsl_l, hsl.l_scaled())
         {
-            self.set_cur_color(Hsl::new(
-                self.sliders.hsl_h / 360.,
-                self.sliders.hsl_s / 100.,
-                self.sliders.hsl_l / 100.,
-            ));
+            self.set_cur_color(
+                Hsl::new(
+                    self.sliders.hsl_h / 360.,
+                    self.sliders.hsl_s / 100.,
+                    self.sliders.hsl_l / 100.,
+                )
+                .with_alpha(self.alpha()),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    fn lab_changed(&mut self) -> bool {
+        let lab = Lab::from(self.current_color);
+        if !math::eq_f32(self.sliders.lab_l, lab.l())
+            || !math::eq_f32(self.sliders.lab_a, lab.a())
+            || !math::eq_f32(self.sliders.lab_b, lab.b())
+        {
+            self.set_cur_color(
+                Lab::new(self.sliders.lab_l, self.sliders.lab_a, self.sliders.lab_b)
+                    .with_alpha(self.alpha()),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    fn lch_changed(&mut self) -> bool {
+        let lch = LchAb::from(self.current_color);
+        if !math::eq_f32(self.sliders.lch_ab_l, lch.l())
+            || !math::eq_f32(self.sliders.lch_ab_c, lch.c())
+            || !math::eq_f32(self.sliders.lch_ab_h, lch.h())
+        {
+            self.set_cur_color(
+                LchAb::new(
+                    self.sliders.lch_ab_l,
+                    self.sliders.lch_ab_c,
+                    self.sliders.lch_ab_h,
+                )
+                .with_alpha(self.alpha()),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    fn oklab_changed(&mut self) -> bool {
+        let oklab = self.current_color.oklab();
+        if !math::eq_f32(self.sliders.oklab_l, oklab.l_scaled())
+            || !math::eq_f32(self.sliders.oklab_a, oklab.a())
+            || !math::eq_f32(self.sliders.oklab_b, oklab.b())
+        {
+            self.set_cur_color(
+                Oklab::new(
+                    self.sliders.oklab_l / 100.,
+                    self.sliders.oklab_a,
+                    self.sliders.oklab_b,
+                )
+                .with_alpha(self.alpha()),
+            );
+            true
+        } else {
+            false
+        }
+    }
+
+    fn oklch_changed(&mut self) -> bool {
+        let oklch = self.current_color.oklch();
+        if !math::eq_f32(self.sliders.oklch_l, oklch.l_scaled())
+            || !math::eq_f32(self.sliders.oklch_c, oklch.c())
+            || !math::eq_f32(self.sliders.oklch_h, oklch.h())
+        {
+            self.set_cur_color(
+                Oklch::new(
+                    self.sliders.oklch_l / 100.,
+                    self.sliders.oklch_c,
+                    self.sliders.oklch_h,
+                )
+                .with_alpha(self.alpha()),
+            );
             true
         } else {
             false
@@ -158,6 +264,9 @@ impl ColorPicker {
     }
 
     fn color_changed(&mut self) -> bool {
+        if self.alpha_changed() {
+            return true;
+        }
         if self.rgb_changed() {
             return true;
         }
@@ -170,6 +279,18 @@ impl ColorPicker {
         if self.hsl_changed() {
             return true;
         }
+        if self.lab_changed() {
+            return true;
+        }
+        if self.lch_changed() {
+            return true;
+        }
+        if self.oklab_changed() {
+            return true;
+        }
+        if self.oklch_changed() {
+            return true;
+        }
         self.rgb_changed()
     }
 
@@ -260,7 +381,7 @@ impl ColorPicker {
                         });
                         ui.end_row();
                     });
-                slider_2d::color(
+                let resp = slider_2d::color(
                     ui,
                     &mut self.sliders.sat,
                     &mut self.sliders.val,
@@ -271,7 +392,10 @@ impl ColorPicker {
                         v /= 100.;
                         Hsv::new(opaque.h(), s, v).into()
                     },
-                )
+                );
+                if resp.changed() {
+                    self.check_for_change();
+                }
             });
     }
 
@@ -301,4 +425,118 @@ impl ColorPicker {
                     });
             });
     }
+
+    pub fn lab_sliders(&mut self, ui: &mut Ui) {
+        let opaque = Lab::from(self.current_color);
+        CollapsingHeader::new("Lab")
+            .default_open(false)
+            .show(ui, |ui| {
+                Grid::new("Lab sliders")
+                    .spacing((8., 8.))
+                    .show(ui, |mut ui| {
+                        slider!(self, ui, lab_l, "light", 0. ..=100., |l| {
+                            Lab::new(l, opaque.a(), opaque.b()).into()
+                        });
+                        ui.end_row();
+                        slider!(self, ui, lab_a, "a", -128. ..=127., |a| {
+                            Lab::new(opaque.l(), a, opaque.b()).into()
+                        });
+                        ui.end_row();
+                        slider!(self, ui, lab_b, "b", -128. ..=127., |b| {
+                            Lab::new(opaque.l(), opaque.a(), b).into()
+                        });
+                        ui.end_row();
+                    });
+            });
+    }
+
+    pub fn lch_sliders(&mut self, ui: &mut Ui) {
+        let opaque = LchAb::from(self.current_color);
+        CollapsingHeader::new("LCh")
+            .default_open(false)
+            .show(ui, |ui| {
+                Grid::new("LCh sliders")
+                    .spacing((8., 8.))
+                    .show(ui, |mut ui| {
+                        slider!(self, ui, lch_ab_l, "light", 0. ..=100., |l| {
+                            LchAb::new(l, opaque.c(), opaque.h()).into()
+                        });
+                        ui.end_row();
+                        slider!(self, ui, lch_ab_c, "chroma", 0. ..=150., |c| {
+                            LchAb::new(opaque.l(), c, opaque.h()).into()
+                        });
+                        ui.end_row();
+                        slider!(self, ui, lch_ab_h, "hue", 0. ..=360., |h| {
+                            LchAb::new(opaque.l(), opaque.c(), h).into()
+                        });
+                        ui.end_row();
+                    });
+            });
+    }
+
+    pub fn oklab_sliders(&mut self, ui: &mut Ui) {
+        let opaque = self.current_color.oklab();
+        CollapsingHeader::new("OKLab")
+            .default_open(false)
+            .show(ui, |ui| {
+                Grid::new("OKLab sliders")
+                    .spacing((8., 8.))
+                    .show(ui, |mut ui| {
+                        slider!(self, ui, oklab_l, "light", 0. ..=100., |mut l| {
+                            l /= 100.;
+                            Oklab::new(l, opaque.a(), opaque.b()).into()
+                        });
+                        ui.end_row();
+                        slider!(fine self, ui, oklab_a, "a", -0.4..=0.4, |a| {
+                            Oklab::new(opaque.l(), a, opaque.b()).into()
+                        });
+                        ui.end_row();
+                        slider!(fine self, ui, oklab_b, "b", -0.4..=0.4, |b| {
+                            Oklab::new(opaque.l(), opaque.a(), b).into()
+                        });
+                        ui.end_row();
+                    });
+            });
+    }
+
+    pub fn oklch_sliders(&mut self, ui: &mut Ui) {
+        let opaque = self.current_color.oklch();
+        CollapsingHeader::new("OKLCH")
+            .default_open(false)
+            .show(ui, |ui| {
+                Grid::new("OKLCH sliders")
+                    .spacing((8., 8.))
+                    .show(ui, |mut ui| {
+                        slider!(self, ui, oklch_l, "light", 0. ..=100., |mut l| {
+                            l /= 100.;
+                            Oklch::new(l, opaque.c(), opaque.h()).into()
+                        });
+                        ui.end_row();
+                        slider!(fine self, ui, oklch_c, "chroma", 0. ..=0.4, |c| {
+                            Oklch::new(opaque.l(), c, opaque.h()).into()
+                        });
+                        ui.end_row();
+                        slider!(self, ui, oklch_h, "hue", 0. ..=360., |h| {
+                            Oklch::new(opaque.l(), opaque.c(), h).into()
+                        });
+                        ui.end_row();
+                    });
+            });
+    }
+
+    pub fn alpha_sliders(&mut self, ui: &mut Ui) {
+        let opaque = self.current_color.rgb();
+        CollapsingHeader::new("Alpha")
+            .default_open(false)
+            .show(ui, |ui| {
+                Grid::new("Alpha sliders")
+                    .spacing((8., 8.))
+                    .show(ui, |mut ui| {
+                        slider!(self, ui, alpha, "alpha", 0. ..=100., |a| {
+                            opaque.with_alpha(a / 100.).into()
+                        });
+                        ui.end_row();
+                    });
+            });
+    }
 }
@@ -1,4 +1,4 @@
-use crate::core::color::Color;
+use crate::core::color::{Color, Lab, LchAb};
 
 use serde::{Deserialize, Serialize};
 
@@ -29,6 +29,13 @@ pub struct ColorSliders {
     pub lch_ab_l: f32,
     pub lch_ab_c: f32,
     pub lch_ab_h: f32,
+    pub oklab_l: f32,
+    pub oklab_a: f32,
+    pub oklab_b: f32,
+    pub oklch_l: f32,
+    pub oklch_c: f32,
+    pub oklch_h: f32,
+    pub alpha: f32,
 }
 
 impl Default for ColorSliders {
@@ -59,6 +66,13 @@ impl Default for ColorSliders {
             lch_ab_l: 0.,
             lch_ab_c: 0.,
             lch_ab_h: 0.,
+            oklab_l: 0.,
+            oklab_a: 0.,
+            oklab_b: 0.,
+            oklch_l: 0.,
+            oklch_c: 0.,
+            oklch_h: 0.,
+            alpha: 100.,
         }
     }
 }
@@ -82,6 +96,23 @@ impl ColorSliders {
         self.hsl_h = hsl.h_scaled();
         self.hsl_s = hsl.s_scaled();
         self.hsl_l = hsl.l_scaled();
+        let lab = Lab::from(color);
+        self.lab_l = lab.l();
+        self.lab_a = lab.a();
+        self.lab_b = lab.b();
+        let lch = LchAb::from(color);
+        self.lch_ab_l = lch.l();
+        self.lch_ab_c = lch.c();
+        self.lch_ab_h = lch.h();
+        let oklab = color.oklab();
+        self.oklab_l = oklab.l_scaled();
+        self.oklab_a = oklab.a();
+        self.oklab_b = oklab.b();
+        let oklch = color.oklch();
+        self.oklch_l = oklch.l_scaled();
+        self.oklch_c = oklch.c();
+        self.oklch_h = oklch.h();
+        self.alpha = color.alpha() * 100.;
     }
 
     pub fn restore(&mut self, other: Self) {
@@ -110,5 +141,12 @@ impl ColorSliders {
         self.lch_ab_l = other.lch_ab_l;
         self.lch_ab_c = other.lch_ab_c;
         self.lch_ab_h = other.lch_ab_h;
+        self.oklab_l = other.oklab_l;
+        self.oklab_a = other.oklab_a;
+        self.oklab_b = other.oklab_b;
+        self.oklch_l = other.oklch_l;
+        self.oklch_c = other.oklch_c;
+        self.oklch_h = other.oklch_h;
+        self.alpha = other.alpha;
     }
 }
@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Eq, Debug, Copy, Clone, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, Debug, Copy, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum HarmonyLayout {
     // [ ][ ]
     // [ ][ ]
+    #[default]
     Square,
     // [  ]
     // [  ]
@@ -18,3 +19,18 @@ pub enum HarmonyLayout {
     Line,
     Gradient,
 }
+
+impl HarmonyLayout {
+    pub fn all() -> [Self; 4] {
+        [Self::Square, Self::Stacked, Self::Line, Self::Gradient]
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Square => "Square",
+            Self::Stacked => "Stacked",
+            Self::Line => "Line",
+            Self::Gradient => "Gradient",
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::color::Color;
+use crate::{core::color::Color, utils::render::paint_checkerboard};
 use eframe::egui::{epaint::Mesh, lerp, remap_clamp, Shape, Stroke};
 use eframe::egui::{pos2, vec2, Color32, CursorIcon, Response, Sense, StrokeKind, Ui};
 use std::ops::{Neg, RangeInclusive};
@@ -30,10 +30,15 @@ pub fn color(
 
     if let Some(mpos) = response.interact_pointer_pos() {
         *value = remap_clamp(mpos.x, rect.left()..=rect.right(), range);
+        response.mark_changed();
     }
 
     let visuals = ui.style().interact(&response);
 
+    if !color_at(range_start).is_opaque() || !color_at(_range_end).is_opaque() {
+        paint_checkerboard(ui.painter(), rect);
+    }
+
     {
         // fill color:
         let mut mesh = Mesh::default();
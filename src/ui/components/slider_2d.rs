@@ -21,6 +21,7 @@ pub fn color(
     if let Some(mpos) = response.interact_pointer_pos() {
         *x_value = remap_clamp(mpos.x, rect.left()..=rect.right(), x_range.clone());
         *y_value = remap_clamp(mpos.y, rect.bottom()..=rect.top(), y_range.clone());
+        response.mark_changed();
     }
 
     let visuals = ui.style().interact(&response);
@@ -1,21 +1,93 @@
 use eframe::egui::{
-    show_tooltip_text, Color32, CursorIcon, Id, ScrollArea, Sense, Stroke, StrokeKind, Ui,
+    Align2, Button, CollapsingHeader, Color32, ComboBox, CursorIcon, FontId, Grid, Key, RichText,
+    ScrollArea, Sense, Stroke, StrokeKind, TextEdit, Ui, Vec2,
 };
 
 use crate::{
     app::{ADD_DESCRIPTION, CURRENT_COLOR_BOX_SIZE},
     context::FrameCtx,
+    core::color::{
+        contrast::{self, Contrast},
+        cvd::ColorVisionDeficiency,
+        delta_e::DeltaE,
+        harmony::{self, ColorHarmony},
+        history::{HistoryEntry, PickSource},
+        import::{import_palettes, PaletteFileFormat},
+        names::{ColorMatch, ColorNameDatabase},
+        palettes::Palettes,
+        ramp::ShadeRamp,
+        scale::{ColorScale, Easing, HuePath, InterpolationSpace},
+        Color, NamedPalette, Oklch, PaletteFormat,
+    },
     save_to_clipboard,
     types::error::append_global_error,
     ui::{
-        components::{colorbox::ColorBox, icon, HALF_SPACE, SPACE},
+        components::{colorbox::ColorBox, icon, layout::HarmonyLayout, HALF_SPACE, SPACE},
         traits::UiPanel,
     },
-    utils::zoom_picker::ZoomPicker,
+    utils::{render::paint_checkerboard, zoom_picker::ZoomPicker},
 };
 
+/// Nearest color names of the current color, looked up again only once the color, the metric or
+/// the palettes change.
+struct ColorNameCache {
+    builtin: ColorNameDatabase,
+    palettes: ColorNameDatabase,
+    /// The palettes `palettes` was built from.
+    source: Vec<NamedPalette>,
+    /// The color and metric of `matches`.
+    key: Option<(Color, DeltaE)>,
+    matches: Vec<ColorMatch>,
+}
+
+impl Default for ColorNameCache {
+    fn default() -> Self {
+        Self {
+            builtin: ColorNameDatabase::builtin(),
+            palettes: ColorNameDatabase::default(),
+            source: vec![],
+            key: None,
+            matches: vec![],
+        }
+    }
+}
+
+impl ColorNameCache {
+    const MATCHES: usize = 5;
+
+    fn nearest(&mut self, color: Color, metric: DeltaE, palettes: &Palettes) -> &[ColorMatch] {
+        if !palettes.iter().eq(self.source.iter()) {
+            self.source = palettes.iter().cloned().collect();
+            self.palettes = ColorNameDatabase::from_palettes(&self.source);
+            self.key = None;
+        }
+        if self.key != Some((color, metric)) {
+            self.key = Some((color, metric));
+            self.matches = self.builtin.nearest_n(&color, metric, Self::MATCHES);
+            self.matches
+                .extend(self.palettes.nearest_n(&color, metric, Self::MATCHES));
+            self.matches
+                .sort_by(|a, b| a.distance.total_cmp(&b.distance));
+            self.matches.truncate(Self::MATCHES);
+        }
+        &self.matches
+    }
+}
+
 pub struct ColorPickerPanel {
     pub zoom_picker: ZoomPicker,
+    color_input: String,
+    contrast_background: Color,
+    palette_export_format: PaletteFormat,
+    color_scale: ColorScale,
+    scale_steps: usize,
+    ramp_hue_shift: f32,
+    harmony_angle: f32,
+    /// The base color when a harmony drag started and the hue rotation dragged since.
+    harmony_drag: Option<(Oklch, f32)>,
+    color_names: ColorNameCache,
+    history_selection: Vec<HistoryEntry>,
+    slider_start_color: Option<Color>,
 }
 
 impl UiPanel for ColorPickerPanel {
@@ -24,6 +96,8 @@ impl UiPanel for ColorPickerPanel {
             ui.add_space(HALF_SPACE);
             ui.vertical(|ui| {
                 self.render_current_color_section(ctx, ui);
+                self.render_color_input(ctx, ui);
+                self.render_palette_section(ctx, ui);
                 self.zoom_picker.display(ctx, ui);
             });
         });
@@ -33,6 +107,13 @@ impl UiPanel for ColorPickerPanel {
         ScrollArea::vertical()
             .id_salt("picker scroll")
             .show(ui, |ui| {
+                self.render_color_sliders(ctx, ui);
+                self.render_harmonies(ctx, ui);
+                self.render_contrast_checker(ctx, ui);
+                self.render_color_vision_preview(ctx, ui);
+                self.render_color_scale(ctx, ui);
+                self.render_shade_ramp(ctx, ui);
+
                 // Display color picking history
                 self.render_color_picking_history(ctx, ui);
 
@@ -55,10 +136,21 @@ impl ColorPickerPanel {
     pub fn new() -> Self {
         Self {
             zoom_picker: ZoomPicker::default(),
+            color_input: String::new(),
+            contrast_background: Color::white(),
+            palette_export_format: PaletteFormat::Gimp,
+            color_scale: ColorScale::new([Color::black(), Color::white()]),
+            scale_steps: 9,
+            ramp_hue_shift: 0.,
+            harmony_angle: ColorHarmony::default().default_angle(),
+            harmony_drag: None,
+            color_names: ColorNameCache::default(),
+            history_selection: vec![],
+            slider_start_color: None,
         }
     }
 
-    fn render_current_color_section(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+    fn render_current_color_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         ui.horizontal(|ui| {
             ui.label("Current color: ");
             if ui
@@ -107,84 +199,888 @@ impl ColorPickerPanel {
 
         ui.horizontal(|ui| {
             cb.display(ctx, ui);
+            self.render_color_name(ctx, ui);
         });
     }
 
-    fn render_color_picking_history(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
-        // Check if we need to clear history first
-        let mut clear_history = false;
+    fn render_color_name(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let color = ctx.app.picker.current_color;
+        let metric = ctx.app.settings.color_name_metric;
+        let matches = self.color_names.nearest(color, metric, &ctx.app.palettes);
 
-        let history_len = ctx.app.color_picking_history.len();
-        if history_len > 0 {
-            ui.separator();
-            ui.label("Color Picking History:");
-            ui.add_space(HALF_SPACE);
+        let Some(nearest) = matches.first() else {
+            return;
+        };
+        ui.label(format!("≈ {}", nearest.name)).on_hover_ui(|ui| {
+            Grid::new("color name matches")
+                .spacing((8., 4.))
+                .show(ui, |ui| {
+                    ui.strong("Name");
+                    ui.strong("Source");
+                    ui.strong(format!("ΔE ({})", metric.display_name()));
+                    ui.end_row();
+                    for found in matches {
+                        ui.label(&found.name);
+                        ui.label(&found.source);
+                        ui.label(format!("{:.2}", found.distance));
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn render_palette_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(format!("Palette `{}`: ", ctx.app.palettes.current().name));
+            if ui
+                .button(icon::COPY)
+                .on_hover_text("Copy palette to clipboard using the palette clipboard format")
+                .on_hover_cursor(CursorIcon::Alias)
+                .clicked()
+            {
+                let palette = ctx.app.clipboard_palette(ctx.app.palettes.current());
+                if let Err(e) = save_to_clipboard(palette) {
+                    append_global_error(format!("Failed to save palette to clipboard - {}", e));
+                }
+            }
+            if ui
+                .button("Import")
+                .on_hover_text(
+                    "Import palettes from GIMP, Adobe ASE/ACO, Paint.NET, Sketch or CSS/SCSS variable files",
+                )
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                Self::import_palettes(ctx);
+            }
+            ComboBox::from_id_salt("palette_export_format")
+                .selected_text(self.palette_export_format.as_ref())
+                .show_ui(ui, |ui| {
+                    for format in PaletteFormat::builtin() {
+                        let text = format.as_ref().to_string();
+                        ui.selectable_value(&mut self.palette_export_format, format, text);
+                    }
+                });
+            if ui
+                .button(icon::EXPORT)
+                .on_hover_text("Export palette to a file")
+                .on_hover_cursor(CursorIcon::PointingHand)
+                .clicked()
+            {
+                self.export_palette(ctx);
+            }
+        });
+    }
 
-            // Create a list of colors with their display strings
-            let mut history_items = Vec::new();
-            for (index, &color) in ctx.app.color_picking_history.iter().enumerate().rev() {
-                let color_display = ctx.app.display_color(&color);
-                history_items.push((color, color_display, index));
+    fn export_palette(&self, ctx: &mut FrameCtx<'_>) {
+        let palette = ctx.app.palettes.current();
+        let format = &self.palette_export_format;
+        let extension = format.extension();
+        let path = match native_dialog::DialogBuilder::file()
+            .set_title("Export Palette")
+            .set_filename(format!("{}.{extension}", palette.slug()))
+            .add_filter(format.as_ref(), [extension])
+            .save_single_file()
+            .show()
+        {
+            Ok(Some(path)) => path,
+            Ok(None) => return,
+            Err(e) => {
+                append_global_error(format!("Failed to open save dialog - {}", e));
+                return;
             }
+        };
 
-            // Display colors in a grid layout
-            ui.horizontal_wrapped(|ui| {
-                for (color, color_display, index) in &history_items {
-                    let _history_box = ColorBox::builder()
-                        .size((30.0, 30.0))
-                        .color(*color)
-                        .label(false)
-                        .border(true)
-                        .hover_help(format!("#{}: {}", index + 1, color_display))
-                        .build();
+        let data = palette.export(
+            format,
+            ctx.app.settings.rgb_working_space,
+            ctx.app.settings.illuminant,
+        );
+        if let Err(e) = std::fs::write(&path, data) {
+            append_global_error(format!("Failed to export palette - {}", e));
+        }
+    }
 
-                    ui.horizontal(|ui| {
-                        // Create a simple color box without using the display method to avoid borrowing issues
-                        let color_rect = ui.allocate_response([30.0, 30.0].into(), Sense::click());
-                        let painter = ui.painter();
-                        painter.rect_filled(color_rect.rect, 2.0, color.color32());
-                        painter.rect_stroke(
-                            color_rect.rect,
-                            2.0,
-                            Stroke::new(1.0, Color32::BLACK),
-                            StrokeKind::Middle,
-                        );
+    fn import_palettes(ctx: &mut FrameCtx<'_>) {
+        let extensions: Vec<_> = PaletteFileFormat::all()
+            .into_iter()
+            .flat_map(PaletteFileFormat::extensions)
+            .copied()
+            .collect();
+        let path = match native_dialog::DialogBuilder::file()
+            .set_title("Import Palette")
+            .add_filter("Palette Files", &extensions)
+            .open_single_file()
+            .show()
+        {
+            Ok(Some(path)) => path,
+            Ok(None) => return,
+            Err(e) => {
+                append_global_error(format!("Failed to open file dialog - {}", e));
+                return;
+            }
+        };
 
-                        if color_rect.clicked() {
-                            ctx.app.picker.current_color = *color;
+        match import_palettes(&path) {
+            Ok(palettes) => {
+                for palette in palettes {
+                    ctx.app.palettes.append(palette);
+                }
+                ctx.app.palettes.move_to_last();
+                ctx.app.sidepanel.show = true;
+            }
+            Err(e) => append_global_error(format!("Failed to import palette - {}", e)),
+        }
+    }
+
+    fn render_color_input(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Paste color: ");
+            let resp = ui
+                .add(
+                    TextEdit::singleline(&mut self.color_input)
+                        .desired_width(CURRENT_COLOR_BOX_SIZE)
+                        .hint_text("#ff0000, rgb(255 0 0), oklch(...), red"),
+                )
+                .on_hover_text(
+                    "Any CSS color value, the current color updates as soon as it is valid",
+                );
+
+            if resp.changed() {
+                if let Ok(color) = Color::from_css(&self.color_input) {
+                    ctx.app.picker.set_cur_color(color);
+                }
+            }
+
+            if resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter)) {
+                if let Err(e) = Color::from_css(&self.color_input) {
+                    append_global_error(format!("Failed to parse color - {}", e));
+                }
+            }
+        });
+    }
+
+    fn render_color_sliders(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        let before = ctx.app.picker.current_color;
+        let sliders = ui
+            .scope(|ui| {
+                let picker = &mut ctx.app.picker;
+                picker.rgb_sliders(ui);
+                picker.cmyk_sliders(ui);
+                picker.hsv_sliders(ui);
+                picker.hsl_sliders(ui);
+                picker.lab_sliders(ui);
+                picker.lch_sliders(ui);
+                picker.oklab_sliders(ui);
+                picker.oklch_sliders(ui);
+                picker.alpha_sliders(ui);
+                // picks up values typed into the drag values next to the sliders
+                picker.check_for_change();
+            })
+            .response;
+
+        // a color adjusted with the sliders is recorded once the pointer is released
+        let (pressed, released) = ui.input(|i| (i.pointer.any_pressed(), i.pointer.any_released()));
+        if pressed && sliders.contains_pointer() {
+            self.slider_start_color = Some(before);
+        }
+        if released {
+            if let Some(start) = self.slider_start_color.take() {
+                let color = ctx.app.picker.current_color;
+                if color != start {
+                    ctx.app.add_to_history(color, PickSource::Slider);
+                }
+            }
+        }
+    }
+
+    fn render_contrast_checker(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        CollapsingHeader::new("Contrast")
+            .default_open(false)
+            .show(ui, |ui| {
+                let fg = ctx.app.picker.current_color;
+
+                ui.horizontal(|ui| {
+                    ui.label("Background: ");
+                    ComboBox::from_id_salt("contrast_background")
+                        .selected_text(ctx.app.display_color(&self.contrast_background))
+                        .show_ui(ui, |ui| {
+                            let named = [("White", Color::white()), ("Black", Color::black())];
+                            for (name, color) in named {
+                                ui.selectable_value(&mut self.contrast_background, color, name);
+                            }
+                            for color in ctx.app.palettes.current().palette.iter() {
+                                ui.selectable_value(
+                                    &mut self.contrast_background,
+                                    *color,
+                                    ctx.app.display_color(color),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text("The current color is checked as text over this color");
+                });
+                let bg = self.contrast_background;
+
+                let (rect, _) = ui.allocate_exact_size(
+                    [CURRENT_COLOR_BOX_SIZE * 6., CURRENT_COLOR_BOX_SIZE].into(),
+                    Sense::hover(),
+                );
+                let painter = ui.painter();
+                painter.rect_filled(rect, 2.0, bg.with_alpha(1.).color32());
+                painter.text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    "The quick brown fox",
+                    FontId::proportional(18.),
+                    fg.color32(),
+                );
+                ui.add_space(HALF_SPACE);
+
+                let result = Contrast::new(&fg, &bg);
+                let verdict = |passes: bool| {
+                    if passes {
+                        RichText::new("Pass").color(Color32::from_rgb(0x2e, 0xa0, 0x43))
+                    } else {
+                        RichText::new("Fail").color(Color32::from_rgb(0xd7, 0x3a, 0x49))
+                    }
+                };
+
+                Grid::new("contrast results")
+                    .spacing((8., 4.))
+                    .show(ui, |ui| {
+                        ui.label("WCAG ratio");
+                        ui.monospace(format!("{:.2}:1", result.ratio));
+                        ui.end_row();
+                        ui.label("APCA Lc");
+                        ui.monospace(format!("{:.1}", result.apca));
+                        ui.end_row();
+                        ui.label("AA normal text");
+                        ui.label(verdict(result.aa_normal()));
+                        ui.end_row();
+                        ui.label("AA large text");
+                        ui.label(verdict(result.aa_large()));
+                        ui.end_row();
+                        ui.label("AAA normal text");
+                        ui.label(verdict(result.aaa_normal()));
+                        ui.end_row();
+                        ui.label("AAA large text");
+                        ui.label(verdict(result.aaa_large()));
+                        ui.end_row();
+                    });
+
+                ui.horizontal(|ui| {
+                    let targets = [("AA", contrast::AA_NORMAL), ("AAA", contrast::AAA_NORMAL)];
+                    for (level, ratio) in targets {
+                        let nearest = contrast::nearest_passing(&fg, &bg, ratio);
+                        let resp = ui
+                            .add_enabled(
+                                nearest.is_some(),
+                                Button::new(format!("Nearest {level}")),
+                            )
+                            .on_hover_text(format!(
+                                "Set the current color to the closest lightness passing {level} for normal text"
+                            ));
+                        if let (true, Some(color)) = (resp.clicked(), nearest) {
+                            ctx.app.picker.set_cur_color(color);
+                        }
+                    }
+                });
+            });
+    }
+
+    fn render_color_vision_preview(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        const SWATCH_SIZE: f32 = 20.;
+
+        CollapsingHeader::new("Color vision")
+            .default_open(false)
+            .show(ui, |ui| {
+                let current = ctx.app.picker.current_color;
+                let palette = &ctx.app.palettes.current().palette;
+                let swatches = |ui: &mut Ui, colors: &mut dyn Iterator<Item = Color>| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.spacing_mut().item_spacing.x = 2.;
+                        for color in colors {
+                            let (rect, _) =
+                                ui.allocate_exact_size(Vec2::splat(SWATCH_SIZE), Sense::hover());
+                            if !color.is_opaque() {
+                                paint_checkerboard(ui.painter(), rect);
+                            }
+                            ui.painter().rect_filled(rect, 2.0, color.color32());
                         }
+                    });
+                };
+
+                Grid::new("color vision preview")
+                    .spacing((8., 4.))
+                    .show(ui, |ui| {
+                        ui.label("Normal vision");
+                        swatches(
+                            ui,
+                            &mut std::iter::once(current).chain(palette.iter().copied()),
+                        );
+                        ui.end_row();
 
-                        if color_rect.hovered() {
-                            show_tooltip_text(
-                                ui.ctx(),
-                                ui.layer_id(),
-                                Id::new(format!("history_{}", index + 1)),
-                                format!("#{}: {}", index + 1, color_display),
+                        for deficiency in ColorVisionDeficiency::all() {
+                            ui.label(deficiency.display_name())
+                                .on_hover_text(deficiency.description());
+                            let simulated = deficiency.simulate_palette(palette);
+                            swatches(
+                                ui,
+                                &mut std::iter::once(deficiency.simulate(&current))
+                                    .chain(simulated.iter().copied()),
                             );
+                            ui.end_row();
                         }
                     });
+            });
+    }
+
+    fn render_color_scale(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        const SWATCH_SIZE: f32 = 20.;
+
+        let swatch = |ui: &mut Ui, color: &Color| {
+            let (rect, response) = ui.allocate_exact_size(Vec2::splat(SWATCH_SIZE), Sense::click());
+            if !color.is_opaque() {
+                paint_checkerboard(ui.painter(), rect);
+            }
+            ui.painter().rect_filled(rect, 2.0, color.color32());
+            response
+        };
 
-                    // Break line after every 10 colors for better layout
-                    if (index + 1) % 10 == 0 {
+        CollapsingHeader::new("Color scale")
+            .default_open(false)
+            .show(ui, |ui| {
+                let scale = &mut self.color_scale;
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Stops:");
+                    ui.spacing_mut().item_spacing.x = 2.;
+                    let mut removed = None;
+                    for (i, stop) in scale.stops.iter().enumerate() {
+                        if swatch(ui, stop)
+                            .on_hover_text(format!("{}\nClick to remove", stop.as_hex()))
+                            .on_hover_cursor(CursorIcon::PointingHand)
+                            .clicked()
+                        {
+                            removed = Some(i);
+                        }
+                    }
+                    if let Some(i) = removed {
+                        scale.stops.remove(i);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Add current color")
+                        .on_hover_cursor(CursorIcon::Copy)
+                        .clicked()
+                    {
+                        scale.stops.push(ctx.app.picker.current_color);
+                    }
+                    if ui
+                        .button("Use palette")
+                        .on_hover_text("Replace the stops with the colors of the current palette")
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        scale.stops = ctx.app.palettes.current().palette.iter().copied().collect();
+                    }
+                    if ui.button("Clear").clicked() {
+                        scale.stops.clear();
+                    }
+                });
+
+                Grid::new("color scale options")
+                    .spacing((8., 4.))
+                    .show(ui, |ui| {
+                        ui.label("Space");
+                        ComboBox::from_id_salt("scale_space")
+                            .selected_text(scale.space.display_name())
+                            .show_ui(ui, |ui| {
+                                for space in InterpolationSpace::all() {
+                                    ui.selectable_value(
+                                        &mut scale.space,
+                                        space,
+                                        space.display_name(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("Hue path");
+                        ui.add_enabled_ui(scale.space == InterpolationSpace::Oklch, |ui| {
+                            ComboBox::from_id_salt("scale_hue_path")
+                                .selected_text(scale.hue_path.display_name())
+                                .show_ui(ui, |ui| {
+                                    for path in HuePath::all() {
+                                        ui.selectable_value(
+                                            &mut scale.hue_path,
+                                            path,
+                                            path.display_name(),
+                                        );
+                                    }
+                                });
+                        });
                         ui.end_row();
+
+                        ui.label("Easing");
+                        ComboBox::from_id_salt("scale_easing")
+                            .selected_text(scale.easing.display_name())
+                            .show_ui(ui, |ui| {
+                                for easing in Easing::all() {
+                                    ui.selectable_value(
+                                        &mut scale.easing,
+                                        easing,
+                                        easing.display_name(),
+                                    );
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label("Steps");
+                        ui.add(eframe::egui::Slider::new(&mut self.scale_steps, 2..=32));
+                        ui.end_row();
+                    });
+
+                let colors = scale.colors(self.scale_steps);
+                if colors.is_empty() {
+                    ui.label("Add at least one stop");
+                    return;
+                }
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.;
+                    for color in &colors {
+                        swatch(ui, color).on_hover_text(color.as_hex());
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    let copy = |text: String| {
+                        if let Err(e) = save_to_clipboard(text) {
+                            append_global_error(format!(
+                                "Failed to save scale to clipboard - {}",
+                                e
+                            ));
+                        }
+                    };
+                    if ui
+                        .button("Copy CSS")
+                        .on_hover_text("Copy as CSS linear-gradient()")
+                        .on_hover_cursor(CursorIcon::Alias)
+                        .clicked()
+                    {
+                        copy(scale.as_css_linear_gradient(self.scale_steps));
+                    }
+                    if ui
+                        .button("Copy hex")
+                        .on_hover_cursor(CursorIcon::Alias)
+                        .clicked()
+                    {
+                        copy(scale.as_hex_list(self.scale_steps));
                     }
+                    if ui
+                        .button("Add as palette")
+                        .on_hover_cursor(CursorIcon::Copy)
+                        .clicked()
+                    {
+                        ctx.app
+                            .palettes
+                            .append(scale.as_palette(self.scale_steps, "scale"));
+                        ctx.app.palettes.move_to_last();
+                    }
+                });
+            });
+    }
+
+    fn render_shade_ramp(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        const SWATCH_SIZE: f32 = 20.;
+
+        CollapsingHeader::new("Shade ramp")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Hue shift");
+                    ui.add(
+                        eframe::egui::Slider::new(&mut self.ramp_hue_shift, 0.0..=30.0).suffix("°"),
+                    )
+                    .on_hover_text(
+                        "Rotate light shades towards warm and dark shades towards cool hues",
+                    );
+                });
+
+                let ramp = ShadeRamp::new(ctx.app.picker.current_color)
+                    .with_hue_shift(self.ramp_hue_shift);
+                let base_index = ramp.base_index();
+                let shades = ramp.shades();
+                let level = |contrast: &Contrast| {
+                    let (text, color) = if contrast.aaa_normal() {
+                        ("AAA", Color32::from_rgb(0x2e, 0xa0, 0x43))
+                    } else if contrast.aa_normal() {
+                        ("AA", Color32::from_rgb(0x2e, 0xa0, 0x43))
+                    } else if contrast.aa_large() {
+                        ("AA large", Color32::from_rgb(0xdb, 0xab, 0x09))
+                    } else {
+                        ("Fail", Color32::from_rgb(0xd7, 0x3a, 0x49))
+                    };
+                    RichText::new(format!("{:.2} {text}", contrast.ratio)).color(color)
+                };
+
+                Grid::new("shade ramp")
+                    .spacing((8., 4.))
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Step");
+                        ui.label("");
+                        ui.strong("Color");
+                        ui.strong("On white");
+                        ui.strong("On black");
+                        ui.end_row();
+
+                        for (i, shade) in shades.iter().enumerate() {
+                            let step = shade.step.to_string();
+                            if i == base_index {
+                                ui.strong(step).on_hover_text("Base color");
+                            } else {
+                                ui.label(step);
+                            }
+                            let (rect, response) =
+                                ui.allocate_exact_size(Vec2::splat(SWATCH_SIZE), Sense::click());
+                            if !shade.color.is_opaque() {
+                                paint_checkerboard(ui.painter(), rect);
+                            }
+                            ui.painter().rect_filled(rect, 2.0, shade.color.color32());
+                            if response
+                                .on_hover_text("Use as current color")
+                                .on_hover_cursor(CursorIcon::PointingHand)
+                                .clicked()
+                            {
+                                ctx.app.picker.set_cur_color(shade.color);
+                            }
+                            ui.monospace(shade.color.as_hex());
+                            ui.label(level(&shade.on_white));
+                            ui.label(level(&shade.on_black));
+                            ui.end_row();
+                        }
+                    });
+
+                if ui
+                    .button("Add as palette")
+                    .on_hover_cursor(CursorIcon::Copy)
+                    .clicked()
+                {
+                    ctx.app.palettes.append(ramp.as_palette("shades"));
+                    ctx.app.palettes.move_to_last();
                 }
             });
+    }
+
+    fn render_harmonies(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        /// Hue rotation in degrees for every point a harmony member is dragged by.
+        const DEGREES_PER_POINT: f32 = 0.5;
+        const GRADIENT_STEPS: usize = 64;
+
+        CollapsingHeader::new("Harmonies")
+            .default_open(false)
+            .show(ui, |ui| {
+                let settings = &mut ctx.app.settings;
+                ui.horizontal(|ui| {
+                    let previous = settings.harmony;
+                    ComboBox::from_id_salt("harmony")
+                        .selected_text(settings.harmony.display_name())
+                        .show_ui(ui, |ui| {
+                            for harmony in ColorHarmony::all() {
+                                ui.selectable_value(
+                                    &mut settings.harmony,
+                                    harmony,
+                                    harmony.display_name(),
+                                );
+                            }
+                        });
+                    if settings.harmony != previous {
+                        self.harmony_angle = settings.harmony.default_angle();
+                    }
+                    ComboBox::from_id_salt("harmony_layout")
+                        .selected_text(settings.harmony_layout.display_name())
+                        .show_ui(ui, |ui| {
+                            for layout in HarmonyLayout::all() {
+                                ui.selectable_value(
+                                    &mut settings.harmony_layout,
+                                    layout,
+                                    layout.display_name(),
+                                );
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    if settings.harmony.has_angle() {
+                        ui.label("Angle");
+                        ui.add(
+                            eframe::egui::Slider::new(&mut self.harmony_angle, 5.0..=90.0)
+                                .suffix("°"),
+                        );
+                    }
+                    ui.label("Size");
+                    ui.add(eframe::egui::Slider::new(
+                        &mut settings.harmony_color_size,
+                        40.0..=200.0,
+                    ));
+                });
+
+                let base = ctx.app.picker.current_color;
+                let colors = settings.harmony.colors(&base, self.harmony_angle);
+                let size = settings.harmony_color_size;
+                let layout = settings.harmony_layout;
+
+                let member = |ui: &mut Ui, color: &Color, size: Vec2| {
+                    let (rect, response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+                    if !color.is_opaque() {
+                        paint_checkerboard(ui.painter(), rect);
+                    }
+                    ui.painter().rect_filled(rect, 0., color.color32());
+                    response
+                        .on_hover_text(format!(
+                            "{}\nDrag to rotate the harmony, click to copy",
+                            color.as_hex()
+                        ))
+                        .on_hover_cursor(CursorIcon::Grab)
+                };
+
+                let mut rotation = 0.;
+                let (mut drag_started, mut dragged) = (false, false);
+                let mut copied = None;
+                let mut handle = |response: eframe::egui::Response, color: Option<&Color>| {
+                    drag_started |= response.drag_started();
+                    dragged |= response.dragged();
+                    rotation += response.drag_delta().x * DEGREES_PER_POINT;
+                    if response.clicked() {
+                        copied = color.copied();
+                    }
+                };
+
+                ui.add_space(HALF_SPACE);
+                match layout {
+                    HarmonyLayout::Square => {
+                        Grid::new("harmony square")
+                            .spacing((0., 0.))
+                            .show(ui, |ui| {
+                                for (i, color) in colors.iter().enumerate() {
+                                    handle(member(ui, color, Vec2::splat(size)), Some(color));
+                                    if i % 2 == 1 {
+                                        ui.end_row();
+                                    }
+                                }
+                            });
+                    }
+                    HarmonyLayout::Stacked => {
+                        ui.vertical(|ui| {
+                            ui.spacing_mut().item_spacing.y = 0.;
+                            for color in &colors {
+                                handle(
+                                    member(ui, color, Vec2::new(size * 2., size / 2.)),
+                                    Some(color),
+                                );
+                            }
+                        });
+                    }
+                    HarmonyLayout::Line => {
+                        ui.horizontal(|ui| {
+                            ui.spacing_mut().item_spacing.x = 0.;
+                            for color in &colors {
+                                handle(member(ui, color, Vec2::new(size / 2., size)), Some(color));
+                            }
+                        });
+                    }
+                    HarmonyLayout::Gradient => {
+                        let scale = ColorScale::new(colors.iter().copied());
+                        let width = size * 2.5;
+                        let (rect, response) = ui.allocate_exact_size(
+                            Vec2::new(width, size / 2.),
+                            Sense::click_and_drag(),
+                        );
+                        let step = width / GRADIENT_STEPS as f32;
+                        for (i, color) in scale.colors(GRADIENT_STEPS).iter().enumerate() {
+                            let min = rect.min + Vec2::new(i as f32 * step, 0.);
+                            let stripe = eframe::egui::Rect::from_min_size(
+                                min,
+                                Vec2::new(step + 0.5, rect.height()),
+                            )
+                            .intersect(rect);
+                            ui.painter().rect_filled(stripe, 0., color.color32());
+                        }
+                        handle(
+                            response
+                                .on_hover_text("Drag to rotate the harmony")
+                                .on_hover_cursor(CursorIcon::Grab),
+                            None,
+                        );
+                    }
+                }
 
-            ui.add_space(SPACE);
+                // rotating from where the drag started keeps the chroma that fitting the rotated
+                // color into the gamut cuts off on the way
+                if drag_started {
+                    self.harmony_drag = Some((base.oklch(), 0.));
+                }
+                if let Some((start, total)) = &mut self.harmony_drag {
+                    if rotation != 0. {
+                        *total += rotation;
+                        ctx.app
+                            .picker
+                            .set_cur_color(harmony::rotate_hue(&Color::Oklch(*start), *total));
+                    }
+                }
+                if !dragged {
+                    self.harmony_drag = None;
+                }
+                if let Some(color) = copied {
+                    if let Err(e) = save_to_clipboard(ctx.app.clipboard_color(&color)) {
+                        append_global_error(format!("Failed to save color to clipboard - {}", e));
+                    }
+                }
+
+                let conflicts = harmony::color_blind_conflicts(&colors);
+                if !conflicts.is_empty() {
+                    ui.add_space(HALF_SPACE);
+                    ui.colored_label(
+                        Color32::from_rgb(0xdb, 0xab, 0x09),
+                        "Some members are hard to tell apart with color vision deficiencies:",
+                    );
+                    for conflict in conflicts {
+                        ui.label(format!(
+                            "{} and {} - {}",
+                            colors[conflict.first].as_hex(),
+                            colors[conflict.second].as_hex(),
+                            conflict.deficiency.display_name()
+                        ));
+                    }
+                }
 
-            // Add clear history button
-            ui.horizontal(|ui| {
-                if ui.button("Clear History").clicked() {
-                    clear_history = true;
+                if ui
+                    .button("Add as palette")
+                    .on_hover_cursor(CursorIcon::Copy)
+                    .clicked()
+                {
+                    ctx.app.palettes.append(NamedPalette {
+                        name: ctx.app.settings.harmony.display_name().to_lowercase(),
+                        palette: colors.into_iter().collect(),
+                    });
+                    ctx.app.palettes.move_to_last();
                 }
-                ui.label(format!("({} colors)", history_len));
             });
+    }
+
+    fn render_color_picking_history(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        // the limit can be lowered in the settings at any time
+        ctx.app
+            .color_picking_history
+            .truncate(ctx.app.settings.color_history_limit);
+        let entries = ctx.app.color_picking_history.entries().to_vec();
+        self.history_selection
+            .retain(|selected| entries.contains(selected));
+        if entries.is_empty() {
+            return;
         }
 
-        // Clear history outside of the borrow scope
+        let mut clear_history = false;
+        let mut remove = None;
+
+        ui.separator();
+        ui.label("Color Picking History:");
+        ui.add_space(HALF_SPACE);
+
+        Grid::new("color_picking_history")
+            .num_columns(5)
+            .spacing((SPACE, HALF_SPACE))
+            .show(ui, |ui| {
+                for (index, entry) in entries.iter().enumerate().rev() {
+                    let mut selected = self.history_selection.contains(entry);
+                    if ui.checkbox(&mut selected, "").changed() {
+                        if selected {
+                            self.history_selection.push(*entry);
+                        } else {
+                            self.history_selection.retain(|it| it != entry);
+                        }
+                    }
+
+                    let color = entry.color;
+                    let color_display = ctx.app.display_color(&color);
+                    let color_rect = ui.allocate_response([30.0, 30.0].into(), Sense::click());
+                    let painter = ui.painter();
+                    if !color.is_opaque() {
+                        paint_checkerboard(painter, color_rect.rect);
+                    }
+                    painter.rect_filled(color_rect.rect, 2.0, color.color32());
+                    painter.rect_stroke(
+                        color_rect.rect,
+                        2.0,
+                        Stroke::new(1.0, Color32::BLACK),
+                        StrokeKind::Middle,
+                    );
+                    if color_rect
+                        .on_hover_text(format!("#{}: {}", index + 1, color_display))
+                        .on_hover_cursor(CursorIcon::PointingHand)
+                        .clicked()
+                    {
+                        ctx.app.picker.set_cur_color(color);
+                    }
+
+                    ui.monospace(color_display);
+                    ui.label(entry.source.display_name());
+                    ui.horizontal(|ui| {
+                        ui.weak(entry.display_time());
+                        if ui
+                            .small_button(icon::DELETE)
+                            .on_hover_text("Remove from history")
+                            .clicked()
+                        {
+                            remove = Some(index);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        ui.add_space(SPACE);
+
+        ui.horizontal(|ui| {
+            if ui.button("Select all").clicked() {
+                self.history_selection = entries.clone();
+            }
+            if ui
+                .add_enabled(
+                    !self.history_selection.is_empty(),
+                    Button::new("Create palette"),
+                )
+                .on_hover_text("Create a palette from the selected colors")
+                .clicked()
+            {
+                let indices = self
+                    .history_selection
+                    .iter()
+                    .filter_map(|selected| entries.iter().position(|entry| entry == selected));
+                ctx.app
+                    .palettes
+                    .append(ctx.app.color_picking_history.as_palette(indices, "history"));
+                ctx.app.palettes.move_to_last();
+                self.history_selection.clear();
+            }
+            if ui.button("Clear History").clicked() {
+                clear_history = true;
+            }
+            ui.label(format!(
+                "({} of {} colors)",
+                entries.len(),
+                ctx.app.settings.color_history_limit
+            ));
+        });
+
+        if let Some(index) = remove {
+            ctx.app.color_picking_history.remove(index);
+        }
         if clear_history {
             ctx.app.color_picking_history.clear();
+            self.history_selection.clear();
         }
     }
 }
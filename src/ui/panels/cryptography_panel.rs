@@ -1,10 +1,17 @@
-use eframe::egui::{Align, Button, Color32, ComboBox, Layout, Resize, ScrollArea, TextEdit, Ui, Spinner};
+use std::path::PathBuf;
+
+use eframe::egui::{
+    Align, Button, Color32, ComboBox, DragValue, Layout, ProgressBar, Resize, ScrollArea, Spinner,
+    TextEdit, Ui,
+};
 
 use crate::{
     context::FrameCtx,
     core::crypto::{
-        symmetric::aes::AesKeySize, CipherMode, CryptoAlgorithm, CryptoOperation, OutputEncoding,
-        RsaKeySize,
+        asymmetric::rsa::{RsaEncryptionPadding, RsaHash, RsaSignaturePadding},
+        symmetric::aes::AesKeySize,
+        CipherMode, CryptoAlgorithm, CryptoOperation, FileProcessingState, InputEncoding,
+        OutputEncoding, Padding, RsaKeySize,
     },
     ui::{
         components::{input_output_box::InputOutputBox, DOUBLE_SPACE, HALF_SPACE, SPACE},
@@ -13,7 +20,10 @@ use crate::{
 };
 
 #[derive(Debug, Default)]
-pub struct CryptographyPanel;
+pub struct CryptographyPanel {
+    input_file: Option<PathBuf>,
+    output_file: Option<PathBuf>,
+}
 
 impl CryptographyPanel {
     pub fn new() -> Self {
@@ -90,30 +100,52 @@ impl CryptographyPanel {
     fn render_mode_selection(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         if ctx.app.crypto.input.algorithm.is_symmetric() {
             ui.horizontal(|ui| {
-                ui.label("Mode:");
-                let current_mode = ctx.app.crypto.input.mode.unwrap_or(CipherMode::CBC);
-                ComboBox::from_id_salt("crypto_mode")
-                    .selected_text(current_mode.to_string())
-                    .show_ui(ui, |ui| {
-                        for mode in CipherMode::variants() {
-                            if ui
-                                .selectable_value(
-                                    &mut ctx.app.crypto.input.mode,
-                                    Some(*mode),
-                                    mode.to_string(),
-                                )
-                                .clicked()
-                            {
-                                // Clear IV when switching to ECB mode
-                                if *mode == CipherMode::ECB {
-                                    ctx.app.crypto.input.iv = None;
+                if ctx.app.crypto.input.algorithm.is_aead() {
+                    ui.checkbox(&mut ctx.app.crypto.input.detached_tag, "Detached tag")
+                        .on_hover_text("Keep the authentication tag apart from the ciphertext");
+                } else {
+                    ui.label("Mode:");
+                    let current_mode = ctx.app.crypto.input.mode.unwrap_or(CipherMode::CBC);
+                    ComboBox::from_id_salt("crypto_mode")
+                        .selected_text(current_mode.to_string())
+                        .show_ui(ui, |ui| {
+                            for mode in CipherMode::variants() {
+                                if ui
+                                    .selectable_value(
+                                        &mut ctx.app.crypto.input.mode,
+                                        Some(*mode),
+                                        mode.to_string(),
+                                    )
+                                    .clicked()
+                                {
+                                    // Clear IV when switching to ECB mode
+                                    if !mode.uses_iv() {
+                                        ctx.app.crypto.input.iv = None;
+                                    }
                                 }
                             }
-                        }
-                    });
+                        });
+
+                    // Stream modes encrypt any length, padding only applies to ECB and CBC
+                    if current_mode.uses_padding() {
+                        ui.add_space(SPACE);
+                        ui.label("Padding:");
+                        ComboBox::from_id_salt("crypto_padding")
+                            .selected_text(ctx.app.crypto.input.padding.to_string())
+                            .show_ui(ui, |ui| {
+                                for padding in Padding::variants() {
+                                    ui.selectable_value(
+                                        &mut ctx.app.crypto.input.padding,
+                                        *padding,
+                                        padding.to_string(),
+                                    );
+                                }
+                            });
+                    }
+                }
 
-                // Add AES key size selection for AES algorithm
-                if matches!(ctx.app.crypto.input.algorithm, CryptoAlgorithm::AES) {
+                // Add AES key size selection for AES based algorithms
+                if ctx.app.crypto.input.algorithm.uses_aes_key_size() {
                     ui.add_space(SPACE);
                     ui.label("Key Size:");
                     let current_key_size = ctx
@@ -144,12 +176,12 @@ impl CryptographyPanel {
                 }
             });
         } else if matches!(ctx.app.crypto.input.algorithm, CryptoAlgorithm::RSA) {
-            // Add RSA key size selection and cipher type display
+            // Add RSA key size, padding and hash selection
+            let encryption = matches!(
+                ctx.app.crypto.input.operation,
+                CryptoOperation::Encrypt | CryptoOperation::Decrypt
+            );
             ui.horizontal(|ui| {
-                ui.label("Cipher Type:");
-                ui.label("RSA/ECB/PKCS1");
-                
-                ui.add_space(SPACE);
                 ui.label("Key Size:");
                 let current_key_size = ctx
                     .app
@@ -168,21 +200,129 @@ impl CryptographyPanel {
                             );
                         }
                     });
+
+                let options = &mut ctx.app.crypto.input.rsa_options;
+                ui.add_space(SPACE);
+                ui.label("Padding:");
+                if encryption {
+                    ComboBox::from_id_salt("rsa_encryption_padding")
+                        .selected_text(options.encryption_padding.to_string())
+                        .show_ui(ui, |ui| {
+                            for padding in RsaEncryptionPadding::variants() {
+                                ui.selectable_value(
+                                    &mut options.encryption_padding,
+                                    *padding,
+                                    padding.to_string(),
+                                );
+                            }
+                        });
+                } else {
+                    ComboBox::from_id_salt("rsa_signature_padding")
+                        .selected_text(options.signature_padding.to_string())
+                        .show_ui(ui, |ui| {
+                            for padding in RsaSignaturePadding::variants() {
+                                ui.selectable_value(
+                                    &mut options.signature_padding,
+                                    *padding,
+                                    padding.to_string(),
+                                );
+                            }
+                        });
+                }
+
+                // PKCS#1 v1.5 encryption doesn't hash anything
+                if !encryption || options.encryption_padding == RsaEncryptionPadding::Oaep {
+                    ui.add_space(SPACE);
+                    ui.label("Hash:");
+                    ComboBox::from_id_salt("rsa_hash")
+                        .selected_text(options.hash.to_string())
+                        .show_ui(ui, |ui| {
+                            for hash in RsaHash::variants() {
+                                ui.selectable_value(&mut options.hash, *hash, hash.to_string());
+                            }
+                        });
+                }
             });
+
+            let options = &mut ctx.app.crypto.input.rsa_options;
+            if encryption && options.encryption_padding == RsaEncryptionPadding::Oaep {
+                ui.add_space(HALF_SPACE);
+                ui.horizontal(|ui| {
+                    ui.label("Label:");
+                    let mut label = options.oaep_label.clone().unwrap_or_default();
+                    ui.add(TextEdit::singleline(&mut label).hint_text("Optional"));
+                    options.oaep_label = if label.is_empty() { None } else { Some(label) };
+                });
+            } else if !encryption && options.signature_padding == RsaSignaturePadding::Pss {
+                ui.add_space(HALF_SPACE);
+                ui.horizontal(|ui| {
+                    ui.label("Salt Length:");
+                    let mut salt_length = options
+                        .pss_salt_length
+                        .unwrap_or_else(|| options.hash.output_size());
+                    if ui
+                        .add(DragValue::new(&mut salt_length).range(0..=512))
+                        .on_hover_text("Bytes of random salt, defaults to the hash length")
+                        .changed()
+                    {
+                        options.pss_salt_length = Some(salt_length);
+                    }
+                });
+            }
         }
     }
 
+    fn render_input_encoding(id_salt: &str, encoding: &mut InputEncoding, ui: &mut Ui) {
+        ComboBox::from_id_salt(id_salt)
+            .selected_text(encoding.to_string())
+            .show_ui(ui, |ui| {
+                for variant in InputEncoding::variants() {
+                    ui.selectable_value(encoding, *variant, variant.to_string());
+                }
+            });
+    }
+
     fn render_encoding_selection(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
-        // Only show encoding selection for encrypt and sign operations
+        // Symmetric algorithms read the input text and write decrypted text in a chosen encoding
+        if ctx.app.crypto.input.algorithm.is_symmetric() {
+            let encodings = &mut ctx.app.crypto.input.input_encodings;
+            match ctx.app.crypto.input.operation {
+                CryptoOperation::Encrypt => {
+                    ui.horizontal(|ui| {
+                        ui.label("Input Format:");
+                        Self::render_input_encoding(
+                            "crypto_plaintext_encoding",
+                            &mut encodings.plaintext,
+                            ui,
+                        );
+                    });
+                }
+                CryptoOperation::Decrypt => {
+                    ui.horizontal(|ui| {
+                        ui.label("Input Format:");
+                        Self::render_input_encoding(
+                            "crypto_ciphertext_encoding",
+                            &mut encodings.ciphertext,
+                            ui,
+                        );
+                        ui.add_space(SPACE);
+                        ui.label("Output Format:");
+                        Self::render_input_encoding(
+                            "crypto_plaintext_encoding",
+                            &mut encodings.plaintext,
+                            ui,
+                        );
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        // Only show encoding selection for encrypt and sign operations, and for the signature to
+        // verify
         if matches!(
             ctx.app.crypto.input.operation,
-            CryptoOperation::Encrypt | CryptoOperation::Sign
-        ) && matches!(
-            ctx.app.crypto.input.algorithm,
-            CryptoAlgorithm::AES
-                | CryptoAlgorithm::DES
-                | CryptoAlgorithm::TripleDES
-                | CryptoAlgorithm::RSA
+            CryptoOperation::Encrypt | CryptoOperation::Sign | CryptoOperation::Verify
         ) {
             ui.horizontal(|ui| {
                 ui.label("Output Format:");
@@ -203,6 +343,11 @@ impl CryptographyPanel {
         if ctx.app.crypto.input.algorithm.is_symmetric() {
             ui.horizontal(|ui| {
                 ui.label("Key :");
+                Self::render_input_encoding(
+                    "crypto_key_encoding",
+                    &mut ctx.app.crypto.input.input_encodings.key,
+                    ui,
+                );
                 ui.add_space(HALF_SPACE);
                 if ui.button("Generate").clicked() {
                     if let Err(e) = ctx.app.crypto.generate_random_key() {
@@ -214,16 +359,25 @@ impl CryptographyPanel {
             let key_hint = match ctx.app.crypto.input.algorithm {
                 CryptoAlgorithm::AES => match ctx.app.crypto.input.aes_key_size {
                     Some(size) => match size {
-                        AesKeySize::Aes128 => "16 bytes characters",
-                        AesKeySize::Aes192 => "24 bytes characters",
-                        AesKeySize::Aes256 => "32 bytes characters",
+                        AesKeySize::Aes128 => "16 bytes",
+                        AesKeySize::Aes192 => "24 bytes",
+                        AesKeySize::Aes256 => "32 bytes",
                     },
                     None => "Select key size first",
                 },
-                CryptoAlgorithm::DES => "8 bytes characters",
-                CryptoAlgorithm::TripleDES => "24 bytes characters",
+                CryptoAlgorithm::DES => "8 bytes",
+                CryptoAlgorithm::TripleDES => "24 bytes",
                 _ => "",
             };
+            let aead_cipher = ctx.app.crypto.input.algorithm.aead_cipher(
+                ctx.app
+                    .crypto
+                    .input
+                    .aes_key_size
+                    .unwrap_or(AesKeySize::Aes128),
+            );
+            let aead_key_hint = aead_cipher.map(|cipher| format!("{} bytes", cipher.key_size()));
+            let key_hint = aead_key_hint.as_deref().unwrap_or(key_hint);
 
             Resize::default()
                 .id_salt("key_input")
@@ -242,11 +396,21 @@ impl CryptographyPanel {
                         });
                 });
 
-            // IV for CBC mode
-            if ctx.app.crypto.input.mode == Some(CipherMode::CBC) {
+            // IV for every mode but ECB or nonce for AEAD algorithms
+            let uses_iv = ctx.app.crypto.input.mode.is_some_and(|mode| mode.uses_iv());
+            if aead_cipher.is_some() || uses_iv {
                 ui.add_space(HALF_SPACE);
                 ui.horizontal(|ui| {
-                    ui.label("IV (hex):");
+                    ui.label(if aead_cipher.is_some() {
+                        "Nonce:"
+                    } else {
+                        "IV:"
+                    });
+                    Self::render_input_encoding(
+                        "crypto_iv_encoding",
+                        &mut ctx.app.crypto.input.input_encodings.iv,
+                        ui,
+                    );
                     ui.add_space(HALF_SPACE);
                     if ui.button("Generate").clicked() {
                         if let Err(e) = ctx.app.crypto.generate_random_iv() {
@@ -260,6 +424,8 @@ impl CryptographyPanel {
                     CryptoAlgorithm::DES | CryptoAlgorithm::TripleDES => "8 bytes",
                     _ => "",
                 };
+                let nonce_hint = aead_cipher.map(|cipher| format!("{} bytes", cipher.nonce_size()));
+                let iv_hint = nonce_hint.as_deref().unwrap_or(iv_hint);
 
                 Resize::default()
                     .id_salt("iv_input")
@@ -278,6 +444,10 @@ impl CryptographyPanel {
                             });
                     });
             }
+
+            if aead_cipher.is_some() {
+                self.render_aead_inputs(ctx, ui);
+            }
         } else {
             // Asymmetric key inputs
             ui.horizontal(|ui| {
@@ -401,11 +571,15 @@ impl CryptographyPanel {
                 // Signature input for verification
                 if ctx.app.crypto.input.operation == CryptoOperation::Verify {
                     ui.add_space(HALF_SPACE);
-                    ui.label("Signature (hex):");
+                    let encoding = ctx.app.crypto.input.encoding;
+                    ui.label(format!(
+                        "Signature ({}):",
+                        encoding.to_string().to_lowercase()
+                    ));
                     let mut signature = ctx.app.crypto.input.signature.clone().unwrap_or_default();
                     ui.add(
                         TextEdit::multiline(&mut signature)
-                            .hint_text("Hex encoded signature")
+                            .hint_text(format!("{encoding} encoded signature"))
                             .desired_width(ui.available_width()),
                     );
                     ctx.app.crypto.input.signature = if signature.is_empty() {
@@ -418,6 +592,35 @@ impl CryptographyPanel {
         }
     }
 
+    fn render_aead_inputs(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        ui.add_space(HALF_SPACE);
+        ui.label("Associated data:");
+        let mut aad = ctx
+            .app
+            .crypto
+            .input
+            .associated_data
+            .clone()
+            .unwrap_or_default();
+        ui.add(
+            TextEdit::singleline(&mut aad).hint_text("Optional, authenticated but not encrypted"),
+        );
+        ctx.app.crypto.input.associated_data = if aad.is_empty() { None } else { Some(aad) };
+
+        if ctx.app.crypto.input.detached_tag {
+            ui.add_space(HALF_SPACE);
+            ui.label("Tag:");
+            let mut tag = ctx.app.crypto.input.tag.clone().unwrap_or_default();
+            ui.add(TextEdit::singleline(&mut tag).hint_text(
+                match ctx.app.crypto.input.operation {
+                    CryptoOperation::Encrypt => "Filled in after encryption",
+                    _ => "Hex or base64 encoded tag",
+                },
+            ));
+            ctx.app.crypto.input.tag = if tag.is_empty() { None } else { Some(tag) };
+        }
+    }
+
     fn render_process_button(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         ui.add_space(SPACE);
         ui.horizontal(|ui| {
@@ -444,6 +647,111 @@ impl CryptographyPanel {
         });
     }
 
+    fn render_file_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        // Files are encrypted as raw bytes with the symmetric algorithms
+        let operation = ctx.app.crypto.input.operation.clone();
+        if !ctx.app.crypto.input.algorithm.is_symmetric()
+            || !matches!(
+                operation,
+                CryptoOperation::Encrypt | CryptoOperation::Decrypt
+            )
+        {
+            return;
+        }
+        ctx.app.crypto.check_file_processing_progress();
+
+        ui.add_space(SPACE);
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.strong("File");
+                ui.add_space(HALF_SPACE);
+
+                ui.horizontal(|ui| {
+                    if ui.button("📁 Input File").clicked() {
+                        match native_dialog::DialogBuilder::file()
+                            .set_title("Select File")
+                            .open_single_file()
+                            .show()
+                        {
+                            Ok(Some(path)) => self.input_file = Some(path),
+                            Ok(None) => {}
+                            Err(e) => {
+                                ctx.app.crypto.error =
+                                    Some(format!("Failed to open file dialog: {e}"))
+                            }
+                        }
+                    }
+                    ui.label(file_label(&self.input_file));
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("💾 Output File").clicked() {
+                        match native_dialog::DialogBuilder::file()
+                            .set_title("Save Output As")
+                            .save_single_file()
+                            .show()
+                        {
+                            Ok(Some(path)) => self.output_file = Some(path),
+                            Ok(None) => {}
+                            Err(e) => {
+                                ctx.app.crypto.error =
+                                    Some(format!("Failed to open file dialog: {e}"))
+                            }
+                        }
+                    }
+                    ui.label(file_label(&self.output_file));
+                });
+                ui.add_space(HALF_SPACE);
+
+                if let FileProcessingState::Processing { processed, total } =
+                    ctx.app.crypto.file_processing_state
+                {
+                    let fraction = if total > 0 {
+                        processed as f32 / total as f32
+                    } else {
+                        0.0
+                    };
+                    ui.add(ProgressBar::new(fraction).show_percentage());
+
+                    // Request repaint to keep the progress moving
+                    ctx.egui.request_repaint();
+                    return;
+                }
+
+                let label = match operation {
+                    CryptoOperation::Encrypt => "Encrypt File",
+                    _ => "Decrypt File",
+                };
+                let (input, output) = (self.input_file.clone(), self.output_file.clone());
+                let ready = input.is_some() && output.is_some() && input != output;
+                if ui
+                    .add_enabled(ready, Button::new(label).min_size([120.0, 30.0].into()))
+                    .clicked()
+                {
+                    if let (Some(input), Some(output)) = (input, output) {
+                        ctx.app.crypto.error = None;
+                        if let Err(e) = ctx.app.crypto.start_file_processing(input, output) {
+                            ctx.app.crypto.error = Some(format!("File processing failed: {e}"));
+                        }
+                    }
+                }
+
+                match &ctx.app.crypto.file_processing_state {
+                    FileProcessingState::Completed { written } => {
+                        ui.colored_label(
+                            Color32::from_rgb(0, 180, 0),
+                            format!("✓ {written} bytes written"),
+                        );
+                    }
+                    FileProcessingState::Failed(error) => {
+                        ui.colored_label(Color32::RED, format!("File processing failed: {error}"));
+                    }
+                    _ => {}
+                }
+            });
+        });
+    }
+
     fn render_error_display(&self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         if let Some(error) = &ctx.app.crypto.error {
             ui.add_space(HALF_SPACE);
@@ -452,6 +760,12 @@ impl CryptographyPanel {
     }
 }
 
+fn file_label(path: &Option<PathBuf>) -> String {
+    path.as_ref()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "No file selected".to_string())
+}
+
 impl UiPanel for CryptographyPanel {
     fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         ScrollArea::vertical()
@@ -478,20 +792,19 @@ impl UiPanel for CryptographyPanel {
                     // Input/Output
                     let input_label = match ctx.app.crypto.input.operation {
                         CryptoOperation::Encrypt => "Plaintext",
-                        //TODO: select input format for decryption
-                        CryptoOperation::Decrypt => "Ciphertext (hex or base64)",
+                        CryptoOperation::Decrypt => "Ciphertext",
                         CryptoOperation::Sign => "Message to sign",
                         CryptoOperation::Verify => "Original message",
                     };
 
                     let output_label = match ctx.app.crypto.input.operation {
                         CryptoOperation::Encrypt => match ctx.app.crypto.input.algorithm {
-                            CryptoAlgorithm::AES
-                            | CryptoAlgorithm::DES
-                            | CryptoAlgorithm::TripleDES => match ctx.app.crypto.input.encoding {
-                                OutputEncoding::Hex => "Ciphertext (hex)",
-                                OutputEncoding::Base64 => "Ciphertext (base64)",
-                            },
+                            algorithm if algorithm.is_symmetric() => {
+                                match ctx.app.crypto.input.encoding {
+                                    OutputEncoding::Hex => "Ciphertext (hex)",
+                                    OutputEncoding::Base64 => "Ciphertext (base64)",
+                                }
+                            }
                             _ => "Ciphertext",
                         },
                         CryptoOperation::Decrypt => "Plaintext",
@@ -503,7 +816,7 @@ impl UiPanel for CryptographyPanel {
                     };
 
                     let input_hint = match ctx.app.crypto.input.operation {
-                        CryptoOperation::Decrypt => "Enter ciphertext (hex or base64 format)",
+                        CryptoOperation::Decrypt => "Enter ciphertext to decrypt",
                         CryptoOperation::Sign => "Enter message to sign",
                         CryptoOperation::Verify => "Enter original message that was signed",
                         _ => "Enter text to encrypt",
@@ -518,6 +831,7 @@ impl UiPanel for CryptographyPanel {
                         );
 
                     self.render_process_button(ctx, ui);
+                    self.render_file_section(ctx, ui);
                     self.render_error_display(ctx, ui);
 
                     ui.add_space(DOUBLE_SPACE);
@@ -1,8 +1,11 @@
 use crate::{
     context::FrameCtx,
+    core::color::{cvd::ColorVisionDeficiency, history::PickSource, NamedPalette},
     core::image::{
-        CompressionOptions, CropRect, FilterType, ImageFormatType, ImageProcessor, ResizeOptions,
+        CompressionOptions, CropRect, DominantColor, FilterType, ImageFormatType, ImageProcessor,
+        QuantizeMethod, ResizeOptions,
     },
+    platform::display_picker::SampleSize,
     types::error::append_global_error,
     ui::{
         components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
@@ -34,11 +37,24 @@ pub struct ImagePanel {
     crop_end: Option<Pos2>,
     crop_rect: Option<CropRect>,
 
+    // Eyedropper state
+    is_picking_color: bool,
+    pick_size: SampleSize,
+
     // Adjustment controls
     brightness_value: i32,
     contrast_value: f32,
     blur_sigma: f32,
 
+    // Color vision deficiency previewed in place of the current image
+    color_vision: Option<ColorVisionDeficiency>,
+
+    // Dominant color extraction
+    dominant_count: usize,
+    quantize_method: QuantizeMethod,
+    dominant_colors: Vec<DominantColor>,
+    image_name: Option<String>,
+
     // Display scaling
     display_scale: f32,
 
@@ -71,10 +87,16 @@ impl UiPanel for ImagePanel {
                         ui.add_space(SPACE);
                         self.render_crop_section(ui);
                         ui.add_space(SPACE);
+                        self.render_eyedropper_section(ui);
+                        ui.add_space(SPACE);
                         self.render_transform_section(ui);
                         ui.add_space(SPACE);
                         self.render_adjustment_section(ui);
                         ui.add_space(SPACE);
+                        self.render_color_vision_section(ui);
+                        ui.add_space(SPACE);
+                        self.render_dominant_colors_section(ctx, ui);
+                        ui.add_space(SPACE);
                         self.render_compression_section(ui);
                         ui.add_space(SPACE);
                         self.render_action_buttons(ui);
@@ -85,7 +107,7 @@ impl UiPanel for ImagePanel {
 
             // Right panel - image display
             ui.vertical(|ui| {
-                self.render_image_display(ui);
+                self.render_image_display(ctx, ui);
             });
         });
     }
@@ -106,9 +128,16 @@ impl ImagePanel {
             crop_start: None,
             crop_end: None,
             crop_rect: None,
+            is_picking_color: false,
+            pick_size: SampleSize::Pixel,
             brightness_value: 0,
             contrast_value: 1.0,
             blur_sigma: 0.0,
+            color_vision: None,
+            dominant_count: 6,
+            quantize_method: QuantizeMethod::KMeans,
+            dominant_colors: Vec::new(),
+            image_name: None,
             display_scale: 1.0,
             estimated_size: None,
         }
@@ -132,6 +161,10 @@ impl ImagePanel {
                     {
                         Ok(Some(path)) => match self.processor.load_from_file(&path) {
                             Ok(()) => {
+                                self.image_name = path
+                                    .file_stem()
+                                    .map(|stem| stem.to_string_lossy().into_owned());
+                                self.dominant_colors.clear();
                                 self.update_texture(ui.ctx());
                                 self.update_ui_from_image();
                                 self.estimated_size = None; // Clear estimate when new image is loaded
@@ -281,6 +314,7 @@ impl ImagePanel {
 
                 if ui.button("✂️ Enable Crop Mode").clicked() {
                     self.is_cropping = true;
+                    self.is_picking_color = false;
                     self.crop_start = None;
                     self.crop_end = None;
                 }
@@ -316,6 +350,42 @@ impl ImagePanel {
         });
     }
 
+    fn render_eyedropper_section(&mut self, ui: &mut Ui) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.strong("Eyedropper");
+                ui.add_space(HALF_SPACE);
+
+                if ui
+                    .toggle_value(&mut self.is_picking_color, "💧 Pick Color")
+                    .on_hover_text("Click on the image to pick a color")
+                    .clicked()
+                    && self.is_picking_color
+                {
+                    self.is_cropping = false;
+                    self.crop_rect = None;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Sample:");
+                    egui::ComboBox::from_id_salt("eyedropper_size")
+                        .selected_text(self.pick_size.display_name())
+                        .show_ui(ui, |ui| {
+                            for size in SampleSize::all() {
+                                ui.selectable_value(&mut self.pick_size, size, size.display_name());
+                            }
+                        })
+                        .response
+                        .on_hover_text("Average of the pixels around the pointer");
+                });
+
+                if self.is_picking_color {
+                    ui.label("Click on the image to set the current color");
+                }
+            });
+        });
+    }
+
     fn render_transform_section(&mut self, ui: &mut Ui) {
         ui.group(|ui| {
             ui.vertical(|ui| {
@@ -444,6 +514,124 @@ impl ImagePanel {
         });
     }
 
+    fn render_color_vision_section(&mut self, ui: &mut Ui) {
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.strong("Color Vision");
+                ui.add_space(HALF_SPACE);
+
+                let selected_text = self
+                    .color_vision
+                    .map_or("Normal vision", ColorVisionDeficiency::display_name);
+                let previous = self.color_vision;
+                ui.horizontal(|ui| {
+                    ui.label("Preview:");
+                    egui::ComboBox::from_id_salt("color_vision")
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.color_vision, None, "Normal vision");
+                            for deficiency in ColorVisionDeficiency::all() {
+                                ui.selectable_value(
+                                    &mut self.color_vision,
+                                    Some(deficiency),
+                                    deficiency.display_name(),
+                                )
+                                .on_hover_text(deficiency.description());
+                            }
+                        });
+                });
+                if self.color_vision != previous {
+                    self.update_texture(ui.ctx());
+                }
+
+                if let Some(deficiency) = self.color_vision {
+                    if ui
+                        .button("🎨 Apply Simulation")
+                        .on_hover_text("Replace the image with the simulated one")
+                        .clicked()
+                    {
+                        if let Err(e) = self.processor.simulate_color_vision(deficiency) {
+                            append_global_error(format!("Color vision simulation failed: {}", e));
+                        } else {
+                            self.color_vision = None;
+                            self.update_texture(ui.ctx());
+                        }
+                    }
+                }
+            });
+        });
+    }
+
+    fn render_dominant_colors_section(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
+        const SWATCH_SIZE: f32 = 20.;
+
+        ui.group(|ui| {
+            ui.vertical(|ui| {
+                ui.strong("Dominant Colors");
+                ui.add_space(HALF_SPACE);
+
+                ui.horizontal(|ui| {
+                    ui.label("Colors:");
+                    ui.add(egui::Slider::new(&mut self.dominant_count, 2..=16));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Method:");
+                    egui::ComboBox::from_id_salt("quantize_method")
+                        .selected_text(self.quantize_method.display_name())
+                        .show_ui(ui, |ui| {
+                            for method in QuantizeMethod::all() {
+                                ui.selectable_value(
+                                    &mut self.quantize_method,
+                                    method,
+                                    method.display_name(),
+                                );
+                            }
+                        });
+                });
+
+                if ui.button("🎨 Extract Colors").clicked() {
+                    match self
+                        .processor
+                        .dominant_colors(self.dominant_count, self.quantize_method)
+                    {
+                        Ok(colors) => self.dominant_colors = colors,
+                        Err(e) => append_global_error(format!("Color extraction failed: {}", e)),
+                    }
+                }
+
+                if self.dominant_colors.is_empty() {
+                    return;
+                }
+
+                ui.add_space(HALF_SPACE);
+                for dominant in &self.dominant_colors {
+                    ui.horizontal(|ui| {
+                        let (rect, _) =
+                            ui.allocate_exact_size(Vec2::splat(SWATCH_SIZE), Sense::hover());
+                        ui.painter()
+                            .rect_filled(rect, 2.0, dominant.color.color32());
+                        ui.monospace(dominant.color.as_hex());
+                        ui.label(format!("{:.1}%", dominant.share * 100.));
+                    });
+                }
+
+                if ui
+                    .button("➕ Add as Palette")
+                    .on_hover_text("Add the extracted colors as a new palette")
+                    .clicked()
+                {
+                    let name = self.image_name.as_deref().unwrap_or("image");
+                    ctx.app.palettes.append(NamedPalette {
+                        name: name.to_string(),
+                        palette: self.dominant_colors.iter().map(|c| c.color).collect(),
+                    });
+                    ctx.app.palettes.move_to_last();
+                }
+            });
+        });
+    }
+
     fn render_compression_section(&mut self, ui: &mut Ui) {
         ui.group(|ui| {
             ui.vertical(|ui| {
@@ -552,12 +740,15 @@ impl ImagePanel {
                 self.brightness_value = 0;
                 self.contrast_value = 1.0;
                 self.blur_sigma = 0.0;
+                self.color_vision = None;
+                self.dominant_colors.clear();
+                self.image_name = None;
                 self.estimated_size = None; // Clear estimate when clearing all
             }
         });
     }
 
-    fn render_image_display(&mut self, ui: &mut Ui) {
+    fn render_image_display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         if let Some(texture) = self.texture_handle.clone() {
             let available_size = ui.available_size();
             let texture_size = texture.size_vec2();
@@ -602,6 +793,10 @@ impl ImagePanel {
                         self.handle_crop_interaction(&image_response, display_size, texture_size);
                     }
 
+                    if self.is_picking_color && has_image {
+                        self.handle_eyedropper_interaction(ctx, &image_response, texture_size);
+                    }
+
                     // Draw crop selection overlay
                     if let (Some(start), Some(end)) = (crop_start, crop_end) {
                         let painter = ui.painter();
@@ -626,6 +821,49 @@ impl ImagePanel {
         }
     }
 
+    fn handle_eyedropper_interaction(
+        &mut self,
+        ctx: &mut FrameCtx<'_>,
+        response: &Response,
+        texture_size: Vec2,
+    ) {
+        let Some(pos) = response.hover_pos() else {
+            return;
+        };
+
+        // Convert display coordinates to image coordinates
+        let rect = response.rect;
+        let relative_pos = pos - rect.min;
+        let x = (relative_pos.x * texture_size.x / rect.width()).clamp(0.0, texture_size.x - 1.0);
+        let y = (relative_pos.y * texture_size.y / rect.height()).clamp(0.0, texture_size.y - 1.0);
+
+        let side = self.pick_size.side() as u32;
+        let color = match self.processor.pick_color(x as u32, y as u32, side) {
+            Ok(color) => color,
+            Err(e) => {
+                append_global_error(format!("Color picking failed: {}", e));
+                return;
+            }
+        };
+
+        let response = response
+            .clone()
+            .on_hover_cursor(CursorIcon::Crosshair)
+            .on_hover_ui_at_pointer(|ui| {
+                ui.horizontal(|ui| {
+                    let (swatch, _) = ui.allocate_exact_size(Vec2::splat(16.), Sense::hover());
+                    ui.painter().rect_filled(swatch, 2.0, color.color32());
+                    ui.monospace(ctx.app.display_color(&color));
+                });
+                ui.weak(format!("{}, {}", x as u32, y as u32));
+            });
+
+        if response.clicked() {
+            ctx.app.picker.set_cur_color(color);
+            ctx.app.add_to_history(color, PickSource::Image);
+        }
+    }
+
     fn handle_crop_interaction(
         &mut self,
         response: &Response,
@@ -690,7 +928,10 @@ impl ImagePanel {
     }
 
     fn update_texture(&mut self, ctx: &Context) {
-        if let Some(image) = self.processor.current_image() {
+        let preview = self
+            .color_vision
+            .and_then(|deficiency| self.processor.color_vision_preview(deficiency).ok());
+        if let Some(image) = preview.as_ref().or(self.processor.current_image()) {
             let color_image = dynamic_image_to_color_image(image);
             self.texture_handle =
                 Some(ctx.load_texture("current_image", color_image, TextureOptions::default()));
@@ -1,4 +1,6 @@
-use eframe::egui::{self, Color32, ComboBox, Ui, Window};
+use eframe::egui::{
+    self, text::LayoutJob, Color32, ComboBox, Stroke, TextEdit, TextFormat, TextStyle, Ui, Window,
+};
 use std::fmt::Display;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -8,9 +10,13 @@ use std::fs;
 
 use crate::{
     context::{AppCtx, FrameCtx},
-    settings::{ColorDisplayFmtEnum, Settings},
+    core::color::{
+        delta_e::DeltaE, Color, CustomColorFormat, CustomPaletteFormat, FormatError, Illuminant,
+        NamedPalette, PaletteFormat, RgbWorkingSpace,
+    },
+    settings::{ColorDisplayFmtEnum, CustomFormats, Settings},
     ui::{
-        components::{DOUBLE_SPACE, HALF_SPACE, SPACE},
+        components::{icon, DOUBLE_SPACE, HALF_SPACE, SPACE},
         traits::UiWindow,
     },
     APP_NAME,
@@ -25,6 +31,8 @@ pub struct SettingsWindow {
     pub show: bool,
     pub error: Option<String>,
     pub message: Option<String>,
+    pub custom_formats_window: CustomFormatsWindow,
+    pub palette_formats_window: PaletteFormatsWindow,
 }
 
 impl UiWindow for SettingsWindow {
@@ -61,8 +69,12 @@ impl UiWindow for SettingsWindow {
                     self.ui_scale_slider(ctx.app, ui);
                     ui.add_space(HALF_SPACE);
                     self.color_formats(ctx.app, ui);
+                    ui.add_space(HALF_SPACE);
+                    self.color_spaces(ctx.app, ui);
                     ui.add_space(SPACE);
                     ui.checkbox(&mut ctx.app.settings.cache_colors, "Cache colors");
+                    ui.add_space(HALF_SPACE);
+                    self.history_limit(ctx.app, ui);
                     ui.add_space(DOUBLE_SPACE);
 
                     self.save_settings_btn(ctx.app, ui);
@@ -74,6 +86,8 @@ impl UiWindow for SettingsWindow {
                 self.clear_message();
             }
         }
+        self.custom_formats_window.display(ctx);
+        self.palette_formats_window.display(ctx);
     }
 }
 
@@ -143,40 +157,143 @@ impl SettingsWindow {
                 );
                 color_format_selection_fill(&mut app_ctx.settings.color_clipboard_format, ui);
             });
-        // ComboBox::from_label("Palette clipboard format")
-        //     .selected_text(app_ctx.settings.palette_clipboard_format.as_ref())
-        //     .show_ui(ui, |ui| {
-        //         ui.selectable_value(
-        //             &mut app_ctx.settings.palette_clipboard_format,
-        //             PaletteFormat::Gimp,
-        //             PaletteFormat::Gimp.as_ref(),
-        //         );
-        //         ui.selectable_value(
-        //             &mut app_ctx.settings.palette_clipboard_format,
-        //             PaletteFormat::HexList,
-        //             PaletteFormat::HexList.as_ref(),
-        //         );
-        //         for (name, fmt) in app_ctx.settings.saved_palette_formats.clone() {
-        //             ui.selectable_value(
-        //                 &mut app_ctx.settings.palette_clipboard_format,
-        //                 PaletteFormat::Custom(name.clone(), fmt),
-        //                 name,
-        //             );
-        //         }
-        //     });
+        ui.add_space(HALF_SPACE);
+        ComboBox::from_label("Palette clipboard format")
+            .selected_text(app_ctx.settings.palette_clipboard_format.as_ref())
+            .show_ui(ui, |ui| {
+                let mut custom: Vec<_> = app_ctx
+                    .settings
+                    .saved_palette_formats
+                    .iter()
+                    .map(|(name, fmt)| PaletteFormat::Custom(name.clone(), fmt.clone()))
+                    .collect();
+                custom.sort_by(|a, b| a.as_ref().cmp(b.as_ref()));
+                for format in PaletteFormat::builtin().into_iter().chain(custom) {
+                    if format.is_binary() {
+                        continue;
+                    }
+                    let text = format.as_ref().to_string();
+                    ui.selectable_value(
+                        &mut app_ctx.settings.palette_clipboard_format,
+                        format,
+                        text,
+                    );
+                }
+            });
+        ui.add_space(HALF_SPACE);
         ui.checkbox(
             &mut app_ctx.settings.auto_copy_picked_color,
             "Auto copy picked color",
         );
         ui.add_space(HALF_SPACE);
-        // ui.horizontal(|ui| {
-        //     if ui.button("Color formats …").clicked() {
-        //         self.custom_formats_window.show = true;
-        //     }
-        //     if ui.button("Palette formats …").clicked() {
-        //         self.palette_formats_window.show = true;
-        //     }
-        // });
+        ui.horizontal(|ui| {
+            if ui.button("Color formats …").clicked() {
+                self.custom_formats_window.show = true;
+            }
+            if ui.button("Palette formats …").clicked() {
+                self.palette_formats_window.show = true;
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                if ui
+                    .button("Import formats")
+                    .on_hover_text("Import color and palette formats from a YAML file")
+                    .clicked()
+                {
+                    self.import_formats(app_ctx);
+                }
+                if ui
+                    .button("Export formats")
+                    .on_hover_text("Export saved color and palette formats to a YAML file")
+                    .clicked()
+                {
+                    self.export_formats(app_ctx);
+                }
+            }
+        });
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn import_formats(&mut self, app_ctx: &mut AppCtx) {
+        let path = match native_dialog::DialogBuilder::file()
+            .set_title("Import Formats")
+            .add_filter("YAML", ["yaml", "yml"])
+            .open_single_file()
+            .show()
+        {
+            Ok(Some(path)) => path,
+            Ok(None) => return,
+            Err(e) => {
+                self.set_error(format!("Failed to open file dialog - {}", e));
+                return;
+            }
+        };
+
+        match CustomFormats::load(&path) {
+            Ok(formats) => {
+                let count = app_ctx.settings.import_custom_formats(formats);
+                self.set_message(format!("Imported {count} formats from {}", path.display()));
+            }
+            Err(e) => self.set_error(format!("Failed to import formats - {:?}", e)),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_formats(&mut self, app_ctx: &mut AppCtx) {
+        let path = match native_dialog::DialogBuilder::file()
+            .set_title("Export Formats")
+            .set_filename(CustomFormats::FILE_NAME)
+            .add_filter("YAML", ["yaml", "yml"])
+            .save_single_file()
+            .show()
+        {
+            Ok(Some(path)) => path,
+            Ok(None) => return,
+            Err(e) => {
+                self.set_error(format!("Failed to open save dialog - {}", e));
+                return;
+            }
+        };
+
+        if let Err(e) = app_ctx.settings.custom_formats().save(&path) {
+            self.set_error(format!("Failed to export formats - {:?}", e));
+        } else {
+            self.set_message(format!("Exported formats to {}", path.display()));
+        }
+    }
+
+    fn color_spaces(&mut self, app_ctx: &mut AppCtx, ui: &mut Ui) {
+        ComboBox::from_label("RGB working space")
+            .selected_text(app_ctx.settings.rgb_working_space.as_ref())
+            .show_ui(ui, |ui| {
+                for ws in RgbWorkingSpace::variants() {
+                    ui.selectable_value(&mut app_ctx.settings.rgb_working_space, *ws, ws.as_ref());
+                }
+            });
+        ui.add_space(HALF_SPACE);
+        ComboBox::from_label("Illuminant")
+            .selected_text(app_ctx.settings.illuminant.as_ref())
+            .show_ui(ui, |ui| {
+                for illuminant in Illuminant::variants() {
+                    ui.selectable_value(
+                        &mut app_ctx.settings.illuminant,
+                        *illuminant,
+                        illuminant.as_ref(),
+                    );
+                }
+            });
+        ui.add_space(HALF_SPACE);
+        ComboBox::from_label("Color name distance")
+            .selected_text(app_ctx.settings.color_name_metric.display_name())
+            .show_ui(ui, |ui| {
+                for metric in DeltaE::all() {
+                    ui.selectable_value(
+                        &mut app_ctx.settings.color_name_metric,
+                        metric,
+                        metric.display_name(),
+                    );
+                }
+            });
     }
 
     fn ui_scale_slider(&mut self, app_ctx: &mut AppCtx, ui: &mut Ui) {
@@ -190,6 +307,13 @@ impl SettingsWindow {
             }
         });
     }
+
+    fn history_limit(&mut self, app_ctx: &mut AppCtx, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Color history size");
+            ui.add(egui::DragValue::new(&mut app_ctx.settings.color_history_limit).range(1..=500));
+        });
+    }
 }
 
 /// Fill the values for a color format selection.
@@ -199,24 +323,279 @@ fn color_format_selection_fill<T: From<ColorDisplayFmtEnum> + PartialEq>(
     fmt_ref: &mut T,
     ui: &mut Ui,
 ) {
-    ui.selectable_value(
-        fmt_ref,
-        ColorDisplayFmtEnum::Hex.into(),
-        ColorDisplayFmtEnum::Hex.as_ref(),
-    );
-    ui.selectable_value(
-        fmt_ref,
-        ColorDisplayFmtEnum::HexUppercase.into(),
-        ColorDisplayFmtEnum::HexUppercase.as_ref(),
-    );
-    ui.selectable_value(
-        fmt_ref,
-        ColorDisplayFmtEnum::CssRgb.into(),
-        ColorDisplayFmtEnum::CssRgb.as_ref(),
-    );
-    ui.selectable_value(
-        fmt_ref,
-        ColorDisplayFmtEnum::CssHsl.into(),
-        ColorDisplayFmtEnum::CssHsl.as_ref(),
+    for format in ColorDisplayFmtEnum::all() {
+        let label = format.as_ref().to_string();
+        ui.selectable_value(fmt_ref, format.into(), label);
+    }
+}
+
+/// Editor of the custom color formats saved in the settings.
+#[derive(Debug, Default)]
+pub struct CustomFormatsWindow {
+    pub show: bool,
+    pub new_name: String,
+    pub new_format: String,
+}
+
+impl UiWindow for CustomFormatsWindow {
+    fn toggle(&mut self) {
+        self.show = !self.show;
+    }
+
+    fn is_open(&self) -> bool {
+        self.show
+    }
+
+    fn display(&mut self, ctx: &mut FrameCtx<'_>) {
+        if !self.is_open() {
+            return;
+        }
+        let offset = ctx.egui.style().spacing.slider_width * WINDOW_X_OFFSET;
+        let is_dark_mode = ctx.egui.style().visuals.dark_mode;
+        let color = ctx.app.picker.current_color;
+        let ws = ctx.app.settings.rgb_working_space;
+        let illuminant = ctx.app.settings.illuminant;
+        let preview = |ui: &mut Ui, format: &str| {
+            color_format_preview(ui, format, &color, ws, illuminant);
+        };
+
+        Window::new("color formats")
+            .frame(super::default_frame(is_dark_mode))
+            .open(&mut self.show)
+            .default_pos((offset * 2., WINDOW_Y_OFFSET))
+            .show(ctx.egui, |ui| {
+                super::apply_default_style(ui, is_dark_mode);
+                ui.label(format!("Previewing {}", color.as_hex()));
+                ui.add_space(HALF_SPACE);
+
+                let formats = &mut ctx.app.settings.saved_color_formats;
+                let mut names: Vec<_> = formats.keys().cloned().collect();
+                names.sort();
+                for name in names {
+                    ui.horizontal(|ui| {
+                        ui.strong(&name);
+                        if ui
+                            .small_button(icon::DELETE)
+                            .on_hover_text("Delete format")
+                            .clicked()
+                        {
+                            formats.remove(&name);
+                        }
+                    });
+                    if let Some(format) = formats.get_mut(&name) {
+                        format_text_edit(ui, format);
+                        preview(ui, format);
+                    }
+                    ui.add_space(HALF_SPACE);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut self.new_name);
+                });
+                format_text_edit(ui, &mut self.new_format);
+                if !self.new_format.is_empty() {
+                    preview(ui, &self.new_format);
+                }
+                let name = self.new_name.trim();
+                if ui
+                    .add_enabled(
+                        !name.is_empty() && !self.new_format.is_empty(),
+                        egui::Button::new(format!("{} Save format", icon::ADD)),
+                    )
+                    .clicked()
+                {
+                    formats.insert(name.to_string(), std::mem::take(&mut self.new_format));
+                    self.new_name.clear();
+                }
+            });
+    }
+}
+
+/// Editor of the custom palette formats saved in the settings.
+#[derive(Debug, Default)]
+pub struct PaletteFormatsWindow {
+    pub show: bool,
+    pub new_name: String,
+    pub new_format: CustomPaletteFormat,
+}
+
+impl UiWindow for PaletteFormatsWindow {
+    fn toggle(&mut self) {
+        self.show = !self.show;
+    }
+
+    fn is_open(&self) -> bool {
+        self.show
+    }
+
+    fn display(&mut self, ctx: &mut FrameCtx<'_>) {
+        if !self.is_open() {
+            return;
+        }
+        let offset = ctx.egui.style().spacing.slider_width * WINDOW_X_OFFSET;
+        let is_dark_mode = ctx.egui.style().visuals.dark_mode;
+        let palette = ctx.app.palettes.current().clone();
+        let ws = ctx.app.settings.rgb_working_space;
+        let illuminant = ctx.app.settings.illuminant;
+        let preview = |ui: &mut Ui, format: &CustomPaletteFormat| {
+            palette_format_preview(ui, format, &palette, ws, illuminant);
+        };
+
+        Window::new("palette formats")
+            .frame(super::default_frame(is_dark_mode))
+            .open(&mut self.show)
+            .default_pos((offset * 2., WINDOW_Y_OFFSET))
+            .show(ctx.egui, |ui| {
+                super::apply_default_style(ui, is_dark_mode);
+                ui.label(format!("Previewing palette {}", palette.name));
+                ui.add_space(HALF_SPACE);
+
+                let formats = &mut ctx.app.settings.saved_palette_formats;
+                let mut names: Vec<_> = formats.keys().cloned().collect();
+                names.sort();
+                for name in names {
+                    ui.horizontal(|ui| {
+                        ui.strong(&name);
+                        if ui
+                            .small_button(icon::DELETE)
+                            .on_hover_text("Delete format")
+                            .clicked()
+                        {
+                            formats.remove(&name);
+                        }
+                    });
+                    if let Some(format) = formats.get_mut(&name) {
+                        palette_format_fields(ui, &name, format);
+                        preview(ui, format);
+                    }
+                    ui.add_space(HALF_SPACE);
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    ui.text_edit_singleline(&mut self.new_name);
+                });
+                palette_format_fields(ui, "new palette format", &mut self.new_format);
+                if !self.new_format.entry_format.is_empty() {
+                    preview(ui, &self.new_format);
+                }
+                let name = self.new_name.trim();
+                if ui
+                    .add_enabled(
+                        !name.is_empty() && !self.new_format.entry_format.is_empty(),
+                        egui::Button::new(format!("{} Save format", icon::ADD)),
+                    )
+                    .clicked()
+                {
+                    formats.insert(name.to_string(), std::mem::take(&mut self.new_format));
+                    self.new_name.clear();
+                }
+            });
+    }
+}
+
+/// A single line text edit for a custom color format that underlines the characters at which
+/// [`CustomColorFormat::validate`] found errors.
+fn format_text_edit(ui: &mut Ui, format: &mut String) {
+    let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+        let mut job = highlight_errors(ui, text, &CustomColorFormat::validate(text));
+        job.wrap.max_width = wrap_width;
+        ui.fonts(|fonts| fonts.layout_job(job))
+    };
+    ui.add(
+        TextEdit::singleline(format)
+            .hint_text("rgb({r255}, {g255}, {b255})")
+            .layouter(&mut layouter),
     );
 }
+
+fn highlight_errors(ui: &Ui, text: &str, errors: &[FormatError]) -> LayoutJob {
+    let font = TextStyle::Monospace.resolve(ui.style());
+    let normal = TextFormat::simple(font, ui.visuals().text_color());
+    let error = TextFormat {
+        color: Color32::RED,
+        underline: Stroke::new(1., Color32::RED),
+        ..normal.clone()
+    };
+
+    let mut job = LayoutJob::default();
+    let mut buf = [0; 4];
+    for (i, ch) in text.chars().enumerate() {
+        let format = if errors.iter().any(|e| e.position == i) {
+            error.clone()
+        } else {
+            normal.clone()
+        };
+        job.append(ch.encode_utf8(&mut buf), 0., format);
+    }
+    job
+}
+
+fn palette_format_fields(ui: &mut Ui, id: &str, format: &mut CustomPaletteFormat) {
+    egui::Grid::new(("palette_format_fields", id))
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Prefix");
+            ui.add(TextEdit::multiline(&mut format.prefix).desired_rows(1));
+            ui.end_row();
+            ui.label("Entry");
+            format_text_edit(ui, &mut format.entry_format);
+            ui.end_row();
+            ui.label("Suffix");
+            ui.add(TextEdit::multiline(&mut format.suffix).desired_rows(1));
+            ui.end_row();
+        });
+}
+
+/// Shows `format` applied to `color` or the errors that prevent it from being used.
+fn color_format_preview(
+    ui: &mut Ui,
+    format: &str,
+    color: &Color,
+    ws: RgbWorkingSpace,
+    illuminant: Illuminant,
+) {
+    if format_errors(ui, &CustomColorFormat::validate(format)) {
+        return;
+    }
+    match CustomColorFormat::parse(format).and_then(|fmt| fmt.format_color(color, ws, illuminant)) {
+        Ok(text) => {
+            ui.monospace(text);
+        }
+        Err(e) => {
+            ui.colored_label(Color32::RED, e.to_string());
+        }
+    }
+}
+
+/// Shows `palette` formatted with `format` or the errors that prevent it from being used.
+fn palette_format_preview(
+    ui: &mut Ui,
+    format: &CustomPaletteFormat,
+    palette: &NamedPalette,
+    ws: RgbWorkingSpace,
+    illuminant: Illuminant,
+) {
+    if format_errors(ui, &format.validate()) {
+        return;
+    }
+    match format.format_palette(&palette.palette, ws, illuminant) {
+        Ok(text) => {
+            ui.monospace(text);
+        }
+        Err(e) => {
+            ui.colored_label(Color32::RED, e.to_string());
+        }
+    }
+}
+
+/// Lists `errors` and returns whether there were any.
+fn format_errors(ui: &mut Ui, errors: &[FormatError]) -> bool {
+    for error in errors {
+        ui.colored_label(Color32::RED, error.to_string());
+    }
+    !errors.is_empty()
+}
@@ -1,6 +1,6 @@
 use eframe::egui::{
-    pos2, widgets, Color32, ColorImage, CursorIcon, ImageButton, ImageData, Rect, Response,
-    TextureId, Ui, Vec2,
+    epaint::Mesh, pos2, vec2, widgets, Color32, ColorImage, CursorIcon, ImageButton, ImageData,
+    Painter, Rect, Response, Shape, TextureId, Ui, Vec2,
 };
 use std::{collections::HashMap, sync::Arc};
 
@@ -9,6 +9,27 @@ use crate::core::color::gradient::Gradient;
 pub type TextureAllocator =
     Option<Arc<eframe::egui::mutex::RwLock<eframe::epaint::TextureManager>>>;
 
+/// Size in points of a single square of the checkerboard drawn behind translucent colors.
+const CHECKER_SIZE: f32 = 6.;
+
+/// Paints a checkerboard over `rect` so that translucent colors drawn on top of it are visible.
+pub fn paint_checkerboard(painter: &Painter, rect: Rect) {
+    let mut mesh = Mesh::default();
+    mesh.add_colored_rect(rect, Color32::from_gray(204));
+
+    let cols = (rect.width() / CHECKER_SIZE).ceil() as usize;
+    let rows = (rect.height() / CHECKER_SIZE).ceil() as usize;
+    for row in 0..rows {
+        for col in (row % 2..cols).step_by(2) {
+            let min = rect.min + vec2(col as f32, row as f32) * CHECKER_SIZE;
+            let square = Rect::from_min_size(min, Vec2::splat(CHECKER_SIZE)).intersect(rect);
+            mesh.add_colored_rect(square, Color32::from_gray(153));
+        }
+    }
+
+    painter.add(Shape::mesh(mesh));
+}
+
 pub fn render_color(
     ui: &mut Ui,
     tex_allocator: &mut TextureAllocator,
@@ -19,7 +40,7 @@ pub fn render_color(
     border: bool,
 ) -> Option<Response> {
     let gradient = Gradient::one_color(color);
-    render_gradient(
+    let resp = render_gradient(
         ui,
         tex_allocator,
         tex_mngr,
@@ -27,7 +48,22 @@ pub fn render_color(
         size,
         on_hover,
         border,
-    )
+    )?;
+
+    if !color.is_opaque() {
+        // the button frame is filled behind the texture, so the checkerboard and the color are
+        // painted on top of it instead
+        let padding = if border {
+            Vec2::splat(ui.spacing().button_padding.x)
+        } else {
+            Vec2::ZERO
+        };
+        let rect = resp.rect.shrink2(padding);
+        paint_checkerboard(ui.painter(), rect);
+        ui.painter().rect_filled(rect, 0., color);
+    }
+
+    Some(resp)
 }
 
 pub fn render_gradient(
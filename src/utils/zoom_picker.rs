@@ -2,7 +2,10 @@
 use crate::{
     app::CURRENT_COLOR_BOX_SIZE,
     context::FrameCtx,
-    platform::display_picker::{self, DisplayPickerExt},
+    core::color::history::PickSource,
+    platform::display_picker::{
+        self, DisplayPickerExt, PickState, PixelSampling, SampleMethod, SampleSize,
+    },
     types::error::append_global_error,
     ui::components::{
         colorbox::{ColorBox, COLORBOX_PICK_TOOLTIP},
@@ -10,7 +13,7 @@ use crate::{
     },
 };
 
-use eframe::egui::{self, Button, CursorIcon, Ui};
+use eframe::egui::{self, Button, ComboBox, CursorIcon, Ui};
 use std::rc::Rc;
 
 #[cfg(target_os = "linux")]
@@ -67,11 +70,20 @@ impl ZoomPicker {
     pub fn display(&mut self, ctx: &mut FrameCtx<'_>, ui: &mut Ui) {
         if ctx.app.color_picking_enabled {
             if let Some(picker) = self.display_picker.clone() {
-                if let Ok(color) = picker.get_color_under_cursor() {
+                if let Some(state) = picker.interactive_pick(ui.ctx()) {
+                    Self::apply_interactive_pick(ctx, ui, state);
+                    return;
+                }
+                let sampling = ctx.app.settings.pixel_sampling;
+                let supports_sampling = picker.supports_sampling();
+                if let Ok(color) = picker.get_sampled_color_under_cursor(sampling) {
                     ctx.app.cursor_pick_color = color;
                     ui.horizontal(|ui| {
                         ui.label("Color at cursor: ");
                         self.zoom_picker_impl(ctx, ui, picker);
+                        if supports_sampling {
+                            Self::sampling_selection(&mut ctx.app.settings.pixel_sampling, ui);
+                        }
                     });
                     let cb = ColorBox::builder()
                         .size((CURRENT_COLOR_BOX_SIZE, CURRENT_COLOR_BOX_SIZE))
@@ -85,6 +97,51 @@ impl ZoomPicker {
                     });
                 }
             };
+        } else if let Some(picker) = &self.display_picker {
+            picker.reset_pick();
+        }
+    }
+
+    /// Applies the color picked on an interactive picker right away, there is no color under the
+    /// cursor to choose with a key press.
+    fn apply_interactive_pick(ctx: &mut FrameCtx<'_>, ui: &mut Ui, state: PickState) {
+        match state {
+            PickState::Idle | PickState::Pending => {
+                ui.label("Click anywhere on the screen to pick a color");
+            }
+            PickState::Picked(color) => {
+                ctx.app.picker.set_cur_color(color);
+                ctx.app.add_to_history(color, PickSource::Screen);
+                ctx.app.color_picking_enabled = false;
+            }
+            PickState::Cancelled => ctx.app.color_picking_enabled = false,
+            PickState::Failed(e) => {
+                append_global_error(format!("Failed to pick a color - {e}"));
+                ctx.app.color_picking_enabled = false;
+            }
+        }
+    }
+
+    fn sampling_selection(sampling: &mut PixelSampling, ui: &mut Ui) {
+        ComboBox::from_id_salt("pixel_sample_size")
+            .selected_text(sampling.size.display_name())
+            .width(60.)
+            .show_ui(ui, |ui| {
+                for size in SampleSize::all() {
+                    ui.selectable_value(&mut sampling.size, size, size.display_name());
+                }
+            })
+            .response
+            .on_hover_text("Area around the cursor that is sampled");
+        if sampling.size != SampleSize::Pixel {
+            ComboBox::from_id_salt("pixel_sample_method")
+                .selected_text(sampling.method.display_name())
+                .width(70.)
+                .show_ui(ui, |ui| {
+                    for method in SampleMethod::all() {
+                        ui.selectable_value(&mut sampling.method, method, method.display_name());
+                    }
+                });
         }
     }
 
@@ -141,11 +198,16 @@ impl ZoomPicker {
     }
 
     #[cfg(target_os = "linux")]
-    fn handle_zoom_picker(&mut self, _ui: &mut Ui, picker: Rc<dyn DisplayPickerExt>) {
+    fn handle_zoom_picker(
+        &mut self,
+        _ui: &mut Ui,
+        picker: Rc<dyn DisplayPickerExt>,
+        sampling: PixelSampling,
+    ) {
         if let Some((window, gc)) = self.picker_window {
             let cursor_pos = picker.get_cursor_pos().unwrap_or_default();
             if let Ok(img) = picker.get_image(
-                picker.screen().root,
+                picker.root_window(),
                 (cursor_pos.0 - ZOOM_IMAGE_X_OFFSET) as i16,
                 (cursor_pos.1 - ZOOM_IMAGE_Y_OFFSET) as i16,
                 ZOOM_IMAGE_WIDTH,
@@ -160,7 +222,7 @@ impl ZoomPicker {
                 let img = display_picker::x11::add_border(&img, black, ZOOM_WIN_BORDER_WIDTH / 2)
                     .unwrap();
 
-                if let Err(e) = img.put(picker.conn(), window, gc, 0, 0) {
+                if let Err(e) = picker.put_image(window, gc, &img) {
                     append_global_error(e);
                     return;
                 };
@@ -174,6 +236,19 @@ impl ZoomPicker {
                 ) {
                     append_global_error(e);
                 };
+
+                // outline the pixels that are averaged into the picked color
+                let side = sampling.size.side();
+                if side > 1 {
+                    let scaled_side = (side as f32 * ZOOM_SCALE) as u16;
+                    let offset = ZOOM_WIN_BORDER_WIDTH as i16
+                        + ((ZOOM_IMAGE_X_OFFSET - (side / 2) as i32) as f32 * ZOOM_SCALE) as i16;
+                    if let Err(e) =
+                        picker.draw_rectangle(window, gc, offset, offset, scaled_side, scaled_side)
+                    {
+                        append_global_error(e);
+                    }
+                }
             }
             if let Err(e) = picker.update_window_pos(
                 window,
@@ -190,7 +265,12 @@ impl ZoomPicker {
     }
 
     #[cfg(windows)]
-    fn handle_zoom_picker(&mut self, _ui: &mut Ui, picker: Rc<dyn DisplayPickerExt>) {
+    fn handle_zoom_picker(
+        &mut self,
+        _ui: &mut Ui,
+        picker: Rc<dyn DisplayPickerExt>,
+        _sampling: PixelSampling,
+    ) {
         if let Some(window) = self.picker_window {
             let cursor_pos = picker.get_cursor_pos().unwrap_or_default();
             match picker.get_screenshot(
@@ -239,6 +319,11 @@ impl ZoomPicker {
         ui: &mut Ui,
         picker: Rc<dyn DisplayPickerExt>,
     ) {
+        #[cfg(target_os = "linux")]
+        if !picker.has_zoom_window() {
+            return;
+        }
+
         let btn = Button::new(icon::ZOOM_PICKER).sense(egui::Sense::drag());
         let btn = ui
             .add(btn)
@@ -254,7 +339,8 @@ impl ZoomPicker {
             ctx.app.zoom_window_dragged = false;
         }
 
-        self.handle_zoom_picker(ui, picker);
+        let sampling = ctx.app.settings.pixel_sampling;
+        self.handle_zoom_picker(ui, picker, sampling);
     }
 
     #[cfg(not(any(target_os = "linux", windows)))]
@@ -0,0 +1,207 @@
+use dev_tools_rs::core::crypto::{
+    symmetric::{
+        aead::{
+            aead_decrypt, aead_decrypt_detached, aead_encrypt, generate_aead_nonce, AeadCipher,
+        },
+        aes::AesKeySize,
+    },
+    CryptoAlgorithm, CryptoOperation, CryptographyProcessor, InputEncoding, OutputEncoding,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUNSCREEN: &str = "Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    fn unhex(text: &str) -> Vec<u8> {
+        hex::decode(text).unwrap()
+    }
+
+    macro_rules! test_vector {
+        ($cipher:expr, key: $key:expr, nonce: $nonce:expr, aad: $aad:expr, pt: $pt:expr => $ct:expr) => {
+            let (key, nonce, aad, pt) = (unhex($key), unhex($nonce), unhex($aad), unhex($pt));
+            let encrypted = aead_encrypt($cipher, &key, &nonce, &aad, &pt).unwrap();
+            assert_eq!(hex::encode(&encrypted), $ct, "{}", $cipher);
+            let decrypted = aead_decrypt($cipher, &key, &nonce, &aad, &encrypted).unwrap();
+            assert_eq!(decrypted, pt, "{}", $cipher);
+        };
+    }
+
+    // Test cases 1-3, 7-8 and 13-14 from "The Galois/Counter Mode of Operation (GCM)" by McGrew and
+    // Viega, which are part of the NIST CAVP GCM vectors.
+    #[test]
+    fn test_aes_gcm_vectors() {
+        let zero = "00000000000000000000000000000000";
+        let nonce = "000000000000000000000000";
+
+        test_vector!(AeadCipher::AesGcm(AesKeySize::Aes128),
+            key: zero, nonce: nonce, aad: "", pt: ""
+            => "58e2fccefa7e3061367f1d57a4e7455a");
+        test_vector!(AeadCipher::AesGcm(AesKeySize::Aes128),
+            key: zero, nonce: nonce, aad: "", pt: zero
+            => "0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bddf");
+        test_vector!(AeadCipher::AesGcm(AesKeySize::Aes128),
+            key: "feffe9928665731c6d6a8f9467308308",
+            nonce: "cafebabefacedbaddecaf888",
+            aad: "",
+            pt: "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a721c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b391aafd255"
+            => "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091473f59854d5c2af327cd64a62cf35abd2ba6fab4");
+        test_vector!(AeadCipher::AesGcm(AesKeySize::Aes192),
+            key: "000000000000000000000000000000000000000000000000", nonce: nonce, aad: "", pt: ""
+            => "cd33b28ac773f74ba00ed1f312572435");
+        test_vector!(AeadCipher::AesGcm(AesKeySize::Aes192),
+            key: "000000000000000000000000000000000000000000000000", nonce: nonce, aad: "", pt: zero
+            => "98e7247c07f0fe411c267e4384b0f6002ff58d80033927ab8ef4d4587514f0fb");
+        test_vector!(AeadCipher::AesGcm(AesKeySize::Aes256),
+            key: "0000000000000000000000000000000000000000000000000000000000000000",
+            nonce: nonce, aad: "", pt: ""
+            => "530f8afbc74536b9a963b4f1c4cb738b");
+        test_vector!(AeadCipher::AesGcm(AesKeySize::Aes256),
+            key: "0000000000000000000000000000000000000000000000000000000000000000",
+            nonce: nonce, aad: "", pt: zero
+            => "cea7403d4d606b6e074ec5d3baf39d18d0d1c8a799996bf0265b98b5d48ab919");
+    }
+
+    // RFC 8452, appendix C.1 and C.2
+    #[test]
+    fn test_aes_gcm_siv_vectors() {
+        test_vector!(AeadCipher::AesGcmSiv(AesKeySize::Aes128),
+            key: "01000000000000000000000000000000",
+            nonce: "030000000000000000000000", aad: "", pt: ""
+            => "dc20e2d83f25705bb49e439eca56de25");
+        test_vector!(AeadCipher::AesGcmSiv(AesKeySize::Aes128),
+            key: "01000000000000000000000000000000",
+            nonce: "030000000000000000000000", aad: "", pt: "0100000000000000"
+            => "b5d839330ac7b786578782fff6013b815b287c22493a364c");
+        test_vector!(AeadCipher::AesGcmSiv(AesKeySize::Aes256),
+            key: "0100000000000000000000000000000000000000000000000000000000000000",
+            nonce: "030000000000000000000000", aad: "", pt: ""
+            => "07f5f4169bbf55a8400cd47ea6fd400f");
+
+        let err = aead_encrypt(
+            AeadCipher::AesGcmSiv(AesKeySize::Aes192),
+            &[0; 24],
+            &[0; 12],
+            b"",
+            b"",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("128 and 256"), "{err}");
+    }
+
+    // RFC 3610, packet vector #1
+    #[test]
+    fn test_aes_ccm_vectors() {
+        test_vector!(AeadCipher::AesCcm8(AesKeySize::Aes128),
+            key: "c0c1c2c3c4c5c6c7c8c9cacbcccdcecf",
+            nonce: "00000003020100a0a1a2a3a4a5",
+            aad: "0001020304050607",
+            pt: "08090a0b0c0d0e0f101112131415161718191a1b1c1d1e"
+            => "588c979a61c663d2f066d0c2c0f989806d5f6b61dac38417e8d12cfdf926e0");
+
+        let cipher = AeadCipher::AesCcm(AesKeySize::Aes256);
+        for nonce_len in cipher.nonce_sizes() {
+            let nonce = vec![7; nonce_len];
+            let encrypted = aead_encrypt(cipher, &[1; 32], &nonce, b"header", b"payload").unwrap();
+            assert_eq!(encrypted.len(), "payload".len() + 16);
+            let decrypted = aead_decrypt(cipher, &[1; 32], &nonce, b"header", &encrypted).unwrap();
+            assert_eq!(decrypted, b"payload");
+        }
+        assert!(aead_encrypt(cipher, &[1; 32], &[7; 14], b"", b"").is_err());
+    }
+
+    // RFC 8439, section 2.8.2 and draft-irtf-cfrg-xchacha-03, appendix A.3.1
+    #[test]
+    fn test_chacha20_poly1305_vectors() {
+        let key = "808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f";
+        let aad = "50515253c0c1c2c3c4c5c6c7";
+        let pt = hex::encode(SUNSCREEN);
+
+        test_vector!(AeadCipher::ChaCha20Poly1305,
+            key: key, nonce: "070000004041424344454647", aad: aad, pt: &pt
+            => "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d63dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b3692ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc3ff4def08e4b7a9de576d26586cec64b61161ae10b594f09e26a7e902ecbd0600691");
+        test_vector!(AeadCipher::XChaCha20Poly1305,
+            key: key, nonce: "404142434445464748494a4b4c4d4e4f5051525354555657", aad: aad, pt: &pt
+            => "bd6d179d3e83d43b9576579493c0e939572a1700252bfaccbed2902c21396cbb731c7f1b0b4aa6440bf3a82f4eda7e39ae64c6708c54c216cb96b72e1213b4522f8c9ba40db5d945b11b69b982c1bb9e3f3fac2bc369488f76b2383565d3fff921f9664c97637da9768812f615c68b13b52ec0875924c1c7987947deafd8780acf49");
+    }
+
+    #[test]
+    fn test_aead_rejects_tampering() {
+        let cipher = AeadCipher::ChaCha20Poly1305;
+        let (key, nonce) = ([3; 32], generate_aead_nonce(cipher));
+        let mut encrypted = aead_encrypt(cipher, &key, &nonce, b"aad", b"secret").unwrap();
+
+        assert!(aead_decrypt(cipher, &key, &nonce, b"other", &encrypted).is_err());
+        assert!(aead_decrypt(cipher, &[4; 32], &nonce, b"aad", &encrypted).is_err());
+        assert!(aead_decrypt(cipher, &key, &nonce, b"aad", &encrypted[..10]).is_err());
+
+        let tag = encrypted.split_off(encrypted.len() - cipher.tag_size());
+        let decrypted =
+            aead_decrypt_detached(cipher, &key, &nonce, b"aad", &encrypted, &tag).unwrap();
+        assert_eq!(decrypted, b"secret");
+
+        encrypted[0] ^= 1;
+        let err =
+            aead_decrypt_detached(cipher, &key, &nonce, b"aad", &encrypted, &tag).unwrap_err();
+        assert!(err.to_string().contains("Authentication failed"), "{err}");
+    }
+
+    #[test]
+    fn test_aead_validates_key_and_nonce() {
+        let cipher = AeadCipher::AesGcm(AesKeySize::Aes128);
+        let err = aead_encrypt(cipher, &[0; 32], &[0; 12], b"", b"").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "AES-128-GCM requires a 16-byte key, got 32 bytes"
+        );
+        let err = aead_encrypt(cipher, &[0; 16], &[0; 16], b"", b"").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "AES-128-GCM requires a 12-byte nonce, got 16 bytes"
+        );
+        assert_eq!(generate_aead_nonce(AeadCipher::XChaCha20Poly1305).len(), 24);
+    }
+
+    #[test]
+    fn test_crypto_processor_aead_round_trip() {
+        for algorithm in CryptoAlgorithm::variants()
+            .iter()
+            .filter(|algorithm| algorithm.is_aead())
+        {
+            for detached_tag in [false, true] {
+                let mut processor = CryptographyProcessor::new();
+                processor.input.algorithm = *algorithm;
+                processor.input.aes_key_size = Some(AesKeySize::Aes256);
+                processor.input.encoding = OutputEncoding::Base64;
+                processor.input.input_encodings.ciphertext = InputEncoding::Base64;
+                processor.input.detached_tag = detached_tag;
+                processor.input.associated_data = Some("header".to_string());
+                processor.input.input_text = "Hello, World!".to_string();
+                processor.generate_random_key().unwrap();
+                processor.generate_random_iv().unwrap();
+
+                let encrypted = processor.process().unwrap();
+                assert_eq!(processor.input.tag.is_some(), detached_tag, "{algorithm}");
+
+                processor.input.operation = CryptoOperation::Decrypt;
+                processor.input.input_text = encrypted;
+                assert_eq!(processor.process().unwrap(), "Hello, World!", "{algorithm}");
+
+                processor.input.associated_data = None;
+                assert!(processor.process().is_err(), "{algorithm}");
+                assert!(processor.error.is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn test_crypto_processor_aead_invalid_operation() {
+        let mut processor = CryptographyProcessor::new();
+        processor.input.algorithm = CryptoAlgorithm::AesGcm;
+        processor.input.operation = CryptoOperation::Sign;
+        processor.generate_random_key().unwrap();
+        processor.generate_random_iv().unwrap();
+        assert!(processor.process().is_err());
+    }
+}
@@ -0,0 +1,424 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn dev_tools(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dev-tools"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn dev-tools");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout_of(args: &[&str], stdin: &str) -> String {
+    let output = dev_tools(args, stdin);
+    assert!(
+        output.status.success(),
+        "dev-tools {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_base64() {
+        assert_eq!(
+            stdout_of(&["encode"], "Hello, World!"),
+            "SGVsbG8sIFdvcmxkIQ=="
+        );
+        assert_eq!(
+            stdout_of(&["decode"], "SGVsbG8sIFdvcmxkIQ==\n"),
+            "Hello, World!"
+        );
+    }
+
+    #[test]
+    fn test_encode_base32_and_url() {
+        assert_eq!(stdout_of(&["encode", "-t", "base32"], "hi"), "NBUQ====");
+        assert_eq!(stdout_of(&["encode", "-t", "url"], "a b&c"), "a+b%26c");
+    }
+
+    #[test]
+    fn test_jwt_encode_verify_decode() {
+        let token = stdout_of(&["jwt", "encode", "-s", "secret"], r#"{"sub":"42"}"#);
+        assert_eq!(token.split('.').count(), 3);
+
+        assert_eq!(
+            stdout_of(&["jwt", "verify", "-s", "secret"], &token),
+            "Signature valid"
+        );
+        assert!(!dev_tools(&["jwt", "verify", "-s", "wrong"], &token)
+            .status
+            .success());
+
+        let decoded = stdout_of(&["jwt", "decode"], &token);
+        assert!(decoded.contains("\"alg\": \"HS256\""));
+        assert!(decoded.contains("\"sub\": \"42\""));
+    }
+
+    #[test]
+    fn test_crypto_aes_round_trip() {
+        let key = "0123456789abcdef";
+        let iv = "fedcba9876543210";
+        let encrypted = stdout_of(
+            &["crypto", "encrypt", "-k", key, "--iv", iv],
+            "Hello, World!",
+        );
+        assert!(encrypted.chars().all(|c| c.is_ascii_hexdigit()));
+
+        let decrypted = stdout_of(&["crypto", "decrypt", "-k", key, "--iv", iv], &encrypted);
+        assert_eq!(decrypted, "Hello, World!");
+    }
+
+    #[test]
+    fn test_crypto_modes_and_padding() {
+        let key = "0123456789abcdef";
+        let iv = "fedcba9876543210";
+        let args = |operation, mode, padding| {
+            vec![
+                "crypto", operation, "-k", key, "--iv", iv, "-m", mode, "-p", padding,
+            ]
+        };
+
+        let encrypted = stdout_of(&args("encrypt", "ctr", "pkcs7"), "Hello, World!");
+        assert_eq!(encrypted.len(), "Hello, World!".len() * 2);
+        let decrypted = stdout_of(&args("decrypt", "ctr", "pkcs7"), &encrypted);
+        assert_eq!(decrypted, "Hello, World!");
+
+        let encrypted = stdout_of(&args("encrypt", "ecb", "ansix923"), "Hello, World!");
+        let decrypted = stdout_of(&args("decrypt", "ecb", "ansix923"), &encrypted);
+        assert_eq!(decrypted, "Hello, World!");
+        assert!(!dev_tools(&args("encrypt", "cbc", "none"), "Hello, World!")
+            .status
+            .success());
+    }
+
+    #[test]
+    fn test_crypto_input_encodings() {
+        let key = [
+            "-k",
+            "2b7e151628aed2a6abf7158809cf4f3c",
+            "--key-encoding",
+            "hex",
+        ];
+        let iv = [
+            "--iv",
+            "AAECAwQFBgcICQoLDA0ODw==",
+            "--iv-encoding",
+            "base64",
+        ];
+        let args = |extra: &[&'static str]| [&["crypto"][..], extra, &key, &iv].concat();
+
+        // NIST SP 800-38A, F.2.1 CBC-AES128
+        let encrypted = stdout_of(
+            &args(&["encrypt", "-p", "none", "--input-encoding", "hex"]),
+            "6bc1bee22e409f96e93d7e117393172a",
+        );
+        assert_eq!(encrypted, "7649abac8119b246cee98e9b12e9197d");
+
+        let encrypted = stdout_of(&args(&["encrypt", "-e", "base64"]), "Hello, World!");
+        let decrypted = stdout_of(
+            &args(&["decrypt", "--input-encoding", "base64"]),
+            &encrypted,
+        );
+        assert_eq!(decrypted, "Hello, World!");
+    }
+
+    #[test]
+    fn test_crypto_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.bin");
+        let encrypted = dir.path().join("plain.bin.enc");
+        let decrypted = dir.path().join("decrypted.bin");
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&plain, &data).unwrap();
+
+        let key = ["-k", "0123456789abcdef", "--iv", "fedcba9876543210"];
+        let path = |path: &std::path::Path| path.to_str().unwrap().to_string();
+        let output = stdout_of(
+            &[
+                &["crypto", "encrypt", "-o", &path(&encrypted), &path(&plain)],
+                &key[..],
+            ]
+            .concat(),
+            "",
+        );
+        assert!(output.starts_with("Wrote 100016 bytes"), "{output}");
+
+        stdout_of(
+            &[
+                &[
+                    "crypto",
+                    "decrypt",
+                    "-o",
+                    &path(&decrypted),
+                    &path(&encrypted),
+                ],
+                &key[..],
+            ]
+            .concat(),
+            "",
+        );
+        assert!(std::fs::read(&decrypted).unwrap() == data);
+    }
+
+    #[test]
+    fn test_crypto_aead_round_trip() {
+        let algorithm = ["-a", "chacha20-poly1305"];
+        let encodings = ["--key-encoding", "hex", "--iv-encoding", "base64"];
+        let key = stdout_of(
+            &[&["crypto", "keygen"], &algorithm[..], &encodings].concat(),
+            "",
+        );
+        let nonce = stdout_of(
+            &[&["crypto", "iv"], &algorithm[..], &encodings].concat(),
+            "",
+        );
+        assert_eq!((key.len(), nonce.len()), (64, 16));
+
+        let args = |operation, aad| {
+            let mut args = vec![
+                "crypto", operation, "-k", &key, "--iv", &nonce, "--aad", aad,
+            ];
+            args.extend(encodings);
+            args.extend(algorithm);
+            args
+        };
+        let encrypted = stdout_of(&args("encrypt", "v1"), "secret");
+        assert_eq!(stdout_of(&args("decrypt", "v1"), &encrypted), "secret");
+        assert!(!dev_tools(&args("decrypt", "v2"), &encrypted)
+            .status
+            .success());
+        let output = dev_tools(
+            &[&args("encrypt", "v1")[..], &["--tag", "00"]].concat(),
+            "secret",
+        );
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+
+        // The tag can be passed on its own when decrypting into a file too
+        let dir = tempfile::tempdir().unwrap();
+        let ciphertext = dir.path().join("secret.enc");
+        let decrypted = dir.path().join("secret.txt");
+        let (data, tag) = encrypted.split_at(encrypted.len() - 32);
+        std::fs::write(&ciphertext, hex::decode(data).unwrap()).unwrap();
+        let output = [
+            "-o",
+            decrypted.to_str().unwrap(),
+            ciphertext.to_str().unwrap(),
+        ];
+        stdout_of(
+            &[&args("decrypt", "v1")[..], &output, &["--tag", tag]].concat(),
+            "",
+        );
+        assert_eq!(std::fs::read_to_string(&decrypted).unwrap(), "secret");
+        assert!(!dev_tools(
+            &[
+                &args("decrypt", "v1")[..],
+                &output,
+                &["--tag", &"00".repeat(16)]
+            ]
+            .concat(),
+            ""
+        )
+        .status
+        .success());
+    }
+
+    #[test]
+    fn test_crypto_rsa_oaep_and_pss() {
+        let dir = tempfile::tempdir().unwrap();
+        let keys = stdout_of(&["crypto", "keygen", "-a", "rsa", "--rsa-bits", "1024"], "");
+        let split = keys.find("-----BEGIN PUBLIC KEY-----").unwrap();
+        let private_key = dir.path().join("private.pem");
+        let public_key = dir.path().join("public.pem");
+        std::fs::write(&private_key, &keys[..split]).unwrap();
+        std::fs::write(&public_key, &keys[split..]).unwrap();
+        let private_key = ["--private-key", private_key.to_str().unwrap()];
+        let public_key = ["--public-key", public_key.to_str().unwrap()];
+
+        let oaep = ["-a", "rsa", "--rsa-padding", "oaep", "--hash", "sha384"];
+        let label = ["--oaep-label", "v1"];
+        let encrypted = stdout_of(
+            &[&["crypto", "encrypt"], &oaep[..], &label, &public_key].concat(),
+            "Hello, World!",
+        );
+        let decrypted = stdout_of(
+            &[&["crypto", "decrypt"], &oaep[..], &label, &private_key].concat(),
+            &encrypted,
+        );
+        assert_eq!(decrypted, "Hello, World!");
+        assert!(!dev_tools(
+            &[&["crypto", "decrypt"], &oaep[..], &private_key].concat(),
+            &encrypted
+        )
+        .status
+        .success());
+
+        let pss = ["-a", "rsa", "--rsa-padding", "pss", "--salt-length", "10"];
+        let signature = stdout_of(
+            &[&["crypto", "sign"], &pss[..], &private_key].concat(),
+            "Hello, World!",
+        );
+        let verify = |args: &[&str]| {
+            stdout_of(
+                &[
+                    &["crypto", "verify", "--signature", &signature],
+                    args,
+                    &public_key,
+                ]
+                .concat(),
+                "Hello, World!",
+            )
+        };
+        assert_eq!(verify(&pss), "Signature valid: true");
+        assert_eq!(verify(&["-a", "rsa"]), "Signature valid: false");
+
+        // Signatures are read back in the encoding they were written in
+        let base64 = ["-a", "rsa", "-e", "base64"];
+        let signature = stdout_of(
+            &[&["crypto", "sign"], &base64[..], &private_key].concat(),
+            "Hello, World!",
+        );
+        let verify = |args: &[&str]| {
+            dev_tools(
+                &[
+                    &["crypto", "verify", "--signature", &signature],
+                    args,
+                    &public_key,
+                ]
+                .concat(),
+                "Hello, World!",
+            )
+        };
+        let output = verify(&base64);
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            "Signature valid: true"
+        );
+        assert!(!verify(&["-a", "rsa"]).status.success());
+
+        assert!(
+            !dev_tools(&[&["crypto", "sign"], &oaep[..], &private_key].concat(), "")
+                .status
+                .success()
+        );
+    }
+
+    #[test]
+    fn test_gen_count() {
+        let output = stdout_of(&["gen", "uuid", "-n", "3"], "");
+        assert_eq!(output.lines().count(), 3);
+
+        let password = stdout_of(&["gen", "password", "-l", "20", "--no-numbers"], "");
+        assert_eq!(password.len(), 20);
+        assert!(!password.chars().any(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_time_format_and_parse() {
+        assert_eq!(
+            stdout_of(&["time", "format", "1640995200"], ""),
+            "2022-01-01T00:00:00.000Z"
+        );
+        assert_eq!(
+            stdout_of(&["time", "parse"], "2022-01-01 00:00:00\n"),
+            "1640995200"
+        );
+        assert!(!dev_tools(&["time", "format", "not-a-number"], "")
+            .status
+            .success());
+    }
+
+    #[test]
+    fn test_regex_matches() {
+        let output = stdout_of(
+            &["regex", "mid(?<postfix>[a-z]+)"],
+            "aurora midsummer midnight earth",
+        );
+        assert_eq!(output, "7..16\tmidsummer\tsummer\n17..25\tmidnight\tnight");
+
+        let json = stdout_of(&["regex", "--json", "[0-9]+"], "a1b22");
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["matches"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_image_convert() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.png");
+        let output = dir.path().join("output.jpg");
+        image::RgbaImage::from_pixel(40, 20, image::Rgba([255, 0, 0, 128]))
+            .save(&input)
+            .unwrap();
+
+        stdout_of(
+            &[
+                "image",
+                "convert",
+                input.to_str().unwrap(),
+                output.to_str().unwrap(),
+                "--resize",
+                "20x",
+                "--rotate",
+                "90",
+                "-q",
+                "80",
+            ],
+            "",
+        );
+
+        let info = stdout_of(&["image", "info", output.to_str().unwrap()], "");
+        assert!(info.contains("Dimensions: 10x20"));
+        assert!(info.contains("Format: JPEG"));
+    }
+
+    #[test]
+    fn test_color_name() {
+        assert_eq!(
+            stdout_of(&["color", "name", "#fe6448", "-s", "css"], ""),
+            "tomato\tCSS\t#ff6347\t0.21"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let palette = dir.path().join("brand.gpl");
+        std::fs::write(
+            &palette,
+            "GIMP Palette\nName: Brand\n#\n 18  52  86\tNavy\n",
+        )
+        .unwrap();
+        let matches = stdout_of(
+            &[
+                "color",
+                "name",
+                "-n",
+                "2",
+                "-m",
+                "oklab",
+                "-p",
+                palette.to_str().unwrap(),
+            ],
+            "rgb(18 52 87)",
+        );
+        let lines: Vec<_> = matches.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("Navy\tBrand\t#123456"), "{matches}");
+    }
+}
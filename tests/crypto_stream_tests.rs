@@ -0,0 +1,299 @@
+use std::io::Read;
+
+use dev_tools_rs::core::crypto::{
+    hex_dump,
+    stream::{CipherJob, CHUNK_SIZE},
+    symmetric::{
+        aes::{aes_encrypt_bytes, AesKeySize},
+        des::{des_decrypt_stream, des_encrypt_bytes, des_encrypt_stream},
+        tdes::{triple_des_decrypt_bytes, triple_des_encrypt_stream},
+    },
+    CipherMode, CryptoAlgorithm, CryptoInput, CryptoOperation, CryptographyProcessor,
+    FileProcessingState, InputEncoding, Padding,
+};
+
+/// Binary data that is neither valid UTF-8 nor a multiple of any block size.
+fn binary_data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + i / 256) as u8).collect()
+}
+
+/// Hands out the data a few bytes at a time, so blocks are split across reads.
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(self.0.len()).min(7);
+        buf[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        Ok(len)
+    }
+}
+
+fn aes_input(operation: CryptoOperation) -> CryptoInput {
+    let mut input = CryptoInput {
+        algorithm: CryptoAlgorithm::AES,
+        operation,
+        aes_key_size: Some(AesKeySize::Aes256),
+        key: "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4".to_string(),
+        iv: Some("000102030405060708090a0b0c0d0e0f".to_string()),
+        ..Default::default()
+    };
+    input.input_encodings.key = InputEncoding::Hex;
+    input.input_encodings.iv = InputEncoding::Hex;
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_matches_one_shot() {
+        let data = binary_data(CHUNK_SIZE + 13);
+        let aligned = &data[..CHUNK_SIZE];
+        let (key, iv) = ([5u8; 32], [9u8; 16]);
+
+        for mode in CipherMode::variants() {
+            for padding in [Padding::Pkcs7, Padding::AnsiX923, Padding::NoPadding] {
+                let data = match padding {
+                    Padding::NoPadding => aligned,
+                    _ => &data[..],
+                };
+                let mut input = aes_input(CryptoOperation::Encrypt);
+                input.mode = Some(*mode);
+                input.padding = padding;
+                input.key = hex::encode(key);
+                input.iv = Some(hex::encode(iv));
+
+                let job = CipherJob::from_input(&input).unwrap();
+                let mut encrypted = Vec::new();
+                job.process(&mut Trickle(data), &mut encrypted).unwrap();
+                let expected =
+                    aes_encrypt_bytes(data, &key, AesKeySize::Aes256, *mode, padding, Some(&iv))
+                        .unwrap();
+                assert!(encrypted == expected, "{mode} {padding}");
+
+                input.operation = CryptoOperation::Decrypt;
+                let job = CipherJob::from_input(&input).unwrap();
+                let mut decrypted = Vec::new();
+                job.process(&mut Trickle(&encrypted), &mut decrypted)
+                    .unwrap();
+                assert!(decrypted == data, "{mode} {padding}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_des_streams() {
+        let data = binary_data(CHUNK_SIZE + 3);
+        let key = [1, 2, 3, 4, 5, 6, 7, 8];
+        let iv = [8u8; 8];
+
+        let mut encrypted = Vec::new();
+        let written = des_encrypt_stream(
+            &mut data.as_slice(),
+            &mut encrypted,
+            &key,
+            CipherMode::CBC,
+            Padding::Pkcs7,
+            Some(&iv),
+        )
+        .unwrap();
+        assert_eq!(written, encrypted.len() as u64);
+        assert_eq!(encrypted.len(), CHUNK_SIZE + 8);
+        assert_eq!(
+            encrypted,
+            des_encrypt_bytes(&data, &key, CipherMode::CBC, Padding::Pkcs7, Some(&iv)).unwrap()
+        );
+
+        let mut decrypted = Vec::new();
+        des_decrypt_stream(
+            &mut Trickle(&encrypted),
+            &mut decrypted,
+            &key,
+            CipherMode::CBC,
+            Padding::Pkcs7,
+            Some(&iv),
+        )
+        .unwrap();
+        assert!(decrypted == data);
+
+        // A truncated ciphertext is not a whole number of blocks
+        let result = des_decrypt_stream(
+            &mut &encrypted[..20],
+            &mut Vec::new(),
+            &key,
+            CipherMode::CBC,
+            Padding::Pkcs7,
+            Some(&iv),
+        );
+        assert!(result.is_err());
+
+        let key = [7u8; 24];
+        let mut encrypted = Vec::new();
+        triple_des_encrypt_stream(
+            &mut Trickle(&data),
+            &mut encrypted,
+            &key,
+            CipherMode::CFB8,
+            Padding::Pkcs7,
+            Some(&iv),
+        )
+        .unwrap();
+        assert_eq!(encrypted.len(), data.len());
+        let decrypted = triple_des_decrypt_bytes(
+            &encrypted,
+            &key,
+            CipherMode::CFB8,
+            Padding::Pkcs7,
+            Some(&iv),
+        )
+        .unwrap();
+        assert!(decrypted == data);
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let bytes = b"Hello, World!\x00\xff\x10 binary";
+        assert_eq!(
+            hex_dump(bytes),
+            "00000000  48 65 6c 6c 6f 2c 20 57  6f 72 6c 64 21 00 ff 10  |Hello, World!...|\n\
+             00000010  20 62 69 6e 61 72 79                              | binary|"
+        );
+        assert_eq!(hex_dump(&[]), "");
+
+        // Text is shown as it is, binary data as a hex dump
+        assert_eq!(InputEncoding::Utf8.encode(b"text"), "text");
+        assert_eq!(InputEncoding::Utf8.encode(bytes), hex_dump(bytes));
+    }
+
+    #[test]
+    fn test_processor_decrypts_binary_to_hex_dump() {
+        let mut processor = CryptographyProcessor::new();
+        processor.input = aes_input(CryptoOperation::Encrypt);
+        processor.input.input_encodings.plaintext = InputEncoding::Hex;
+        processor.input.input_text = "00ff7f80".to_string();
+        processor.input.input_text = processor.process().unwrap();
+
+        processor.input.operation = CryptoOperation::Decrypt;
+        processor.input.input_encodings.plaintext = InputEncoding::Utf8;
+        assert_eq!(
+            processor.process().unwrap(),
+            hex_dump(&[0x00, 0xff, 0x7f, 0x80])
+        );
+
+        let encrypted = {
+            processor.input.operation = CryptoOperation::Encrypt;
+            processor.process_bytes(&[0, 1, 2, 255]).unwrap()
+        };
+        processor.input.operation = CryptoOperation::Decrypt;
+        assert_eq!(processor.process_bytes(&encrypted).unwrap(), [0, 1, 2, 255]);
+    }
+
+    #[test]
+    fn test_process_bytes_aead_and_rsa() {
+        let mut processor = CryptographyProcessor::new();
+        processor.input.algorithm = CryptoAlgorithm::ChaCha20Poly1305;
+        processor.input.associated_data = Some("header".to_string());
+        processor.generate_random_key().unwrap();
+        processor.generate_random_iv().unwrap();
+
+        let data = binary_data(1000);
+        let encrypted = processor.process_bytes(&data).unwrap();
+        assert_eq!(encrypted.len(), data.len() + 16);
+        processor.input.operation = CryptoOperation::Decrypt;
+        assert_eq!(processor.process_bytes(&encrypted).unwrap(), data);
+
+        processor.input.associated_data = None;
+        assert!(processor.process_bytes(&encrypted).is_err());
+
+        processor.input.algorithm = CryptoAlgorithm::RSA;
+        assert!(processor.process_bytes(&data).is_err());
+    }
+
+    #[test]
+    fn test_file_round_trip_with_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.bin");
+        let encrypted = dir.path().join("plain.bin.enc");
+        let decrypted = dir.path().join("decrypted.bin");
+        let data = binary_data(2 * CHUNK_SIZE + 100);
+        std::fs::write(&plain, &data).unwrap();
+
+        let job = CipherJob::from_input(&aes_input(CryptoOperation::Encrypt)).unwrap();
+        let mut updates = Vec::new();
+        let written = job
+            .process_file(&plain, &encrypted, |processed, total| {
+                updates.push((processed, total))
+            })
+            .unwrap();
+        assert_eq!(written, data.len() as u64 + 12); // PKCS#7 pads to the next block
+        assert!(updates.len() >= 3);
+        assert_eq!(
+            updates.last(),
+            Some(&(data.len() as u64, data.len() as u64))
+        );
+        assert!(updates.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+
+        let job = CipherJob::from_input(&aes_input(CryptoOperation::Decrypt)).unwrap();
+        job.process_file(&encrypted, &decrypted, |_, _| {}).unwrap();
+        assert!(std::fs::read(&decrypted).unwrap() == data);
+
+        // A failed decryption leaves no partial output behind
+        let failed = dir.path().join("failed.bin");
+        let mut input = aes_input(CryptoOperation::Decrypt);
+        input.key = "00".repeat(32);
+        let job = CipherJob::from_input(&input).unwrap();
+        assert!(job.process_file(&encrypted, &failed, |_, _| {}).is_err());
+        assert!(!failed.exists());
+    }
+
+    #[test]
+    fn test_async_file_processing() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.bin");
+        let encrypted = dir.path().join("plain.bin.enc");
+        std::fs::write(&plain, binary_data(2 * CHUNK_SIZE)).unwrap();
+
+        let mut processor = CryptographyProcessor::new();
+        processor.input = aes_input(CryptoOperation::Encrypt);
+        processor.input.mode = Some(CipherMode::CTR);
+        processor
+            .start_file_processing(plain.clone(), encrypted.clone())
+            .unwrap();
+        assert!(processor.is_file_processing_in_progress());
+
+        let mut attempts = 0;
+        while processor.is_file_processing_in_progress() && attempts < 100 {
+            processor.check_file_processing_progress();
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            attempts += 1;
+        }
+        assert_eq!(
+            processor.file_processing_state,
+            FileProcessingState::Completed {
+                written: 2 * CHUNK_SIZE as u64
+            }
+        );
+        assert!(processor.file_processing_receiver.is_none());
+
+        // Invalid keys are reported before a thread is started
+        processor.input.key = "short".to_string();
+        assert!(processor.start_file_processing(plain, encrypted).is_err());
+        assert!(!processor.is_file_processing_in_progress());
+
+        // Missing files fail on the background thread
+        processor.input = aes_input(CryptoOperation::Encrypt);
+        processor
+            .start_file_processing(dir.path().join("missing"), dir.path().join("out"))
+            .unwrap();
+        while processor.is_file_processing_in_progress() {
+            processor.check_file_processing_progress();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(matches!(
+            processor.file_processing_state,
+            FileProcessingState::Failed(ref error) if error.contains("Failed to open")
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 use dev_tools_rs::core::crypto::{
     symmetric::aes::AesKeySize, CipherMode, CryptoAlgorithm, CryptoInput, CryptoOperation,
-    CryptographyProcessor, OutputEncoding,
+    CryptographyProcessor, InputEncoding, OutputEncoding,
 };
 
 #[cfg(test)]
@@ -38,9 +38,13 @@ mod tests {
     #[test]
     fn test_cipher_mode_variants() {
         let variants = CipherMode::variants();
-        assert_eq!(variants.len(), 2);
+        assert_eq!(variants.len(), 6);
         assert!(variants.contains(&CipherMode::ECB));
         assert!(variants.contains(&CipherMode::CBC));
+        assert!(variants.contains(&CipherMode::CTR));
+        assert!(variants.contains(&CipherMode::CFB));
+        assert!(variants.contains(&CipherMode::CFB8));
+        assert!(variants.contains(&CipherMode::OFB));
     }
 
     #[test]
@@ -120,11 +124,12 @@ mod tests {
         let mut processor = CryptographyProcessor::new();
         processor.input.algorithm = CryptoAlgorithm::AES;
         processor.input.aes_key_size = Some(AesKeySize::Aes128);
+        processor.input.input_encodings.key = InputEncoding::Hex;
 
         let result = processor.generate_random_key();
         assert!(result.is_ok());
         assert!(!processor.input.key.is_empty());
-        assert_eq!(processor.input.key.len(), 16); // 128 bits = 16 hex chars
+        assert_eq!(processor.input.key.len(), 32); // 128 bits = 32 hex chars
         assert!(processor.input.key.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
@@ -132,11 +137,12 @@ mod tests {
     fn test_generate_random_key_des() {
         let mut processor = CryptographyProcessor::new();
         processor.input.algorithm = CryptoAlgorithm::DES;
+        processor.input.input_encodings.key = InputEncoding::Hex;
 
         let result = processor.generate_random_key();
         assert!(result.is_ok());
         assert!(!processor.input.key.is_empty());
-        assert_eq!(processor.input.key.len(), 8); // 64 bits = 8 hex chars
+        assert_eq!(processor.input.key.len(), 16); // 64 bits = 16 hex chars
         assert!(processor.input.key.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
@@ -144,11 +150,12 @@ mod tests {
     fn test_generate_random_key_triple_des() {
         let mut processor = CryptographyProcessor::new();
         processor.input.algorithm = CryptoAlgorithm::TripleDES;
+        processor.input.input_encodings.key = InputEncoding::Hex;
 
         let result = processor.generate_random_key();
         assert!(result.is_ok());
         assert!(!processor.input.key.is_empty());
-        assert_eq!(processor.input.key.len(), 24); // 192 bits = 24 hex chars
+        assert_eq!(processor.input.key.len(), 48); // 192 bits = 48 hex chars
         assert!(processor.input.key.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
@@ -170,12 +177,13 @@ mod tests {
         let mut processor = CryptographyProcessor::new();
         processor.input.algorithm = CryptoAlgorithm::AES;
         processor.input.mode = Some(CipherMode::CBC);
+        processor.input.input_encodings.iv = InputEncoding::Hex;
 
         let result = processor.generate_random_iv();
         assert!(result.is_ok());
         assert!(processor.input.iv.is_some());
         assert!(!processor.input.iv.as_ref().unwrap().is_empty());
-        assert_eq!(processor.input.iv.as_ref().unwrap().len(), 16); // 128 bits = 16 hex chars
+        assert_eq!(processor.input.iv.as_ref().unwrap().len(), 32); // 128 bits = 32 hex chars
         assert!(processor
             .input
             .iv
@@ -190,12 +198,13 @@ mod tests {
         let mut processor = CryptographyProcessor::new();
         processor.input.algorithm = CryptoAlgorithm::DES;
         processor.input.mode = Some(CipherMode::CBC);
+        processor.input.input_encodings.iv = InputEncoding::Hex;
 
         let result = processor.generate_random_iv();
         assert!(result.is_ok());
         assert!(processor.input.iv.is_some());
         assert!(!processor.input.iv.as_ref().unwrap().is_empty());
-        assert_eq!(processor.input.iv.as_ref().unwrap().len(), 8); // 64 bits = 8 hex chars
+        assert_eq!(processor.input.iv.as_ref().unwrap().len(), 16); // 64 bits = 16 hex chars
         assert!(processor
             .input
             .iv
@@ -210,12 +219,13 @@ mod tests {
         let mut processor = CryptographyProcessor::new();
         processor.input.algorithm = CryptoAlgorithm::TripleDES;
         processor.input.mode = Some(CipherMode::CBC);
+        processor.input.input_encodings.iv = InputEncoding::Hex;
 
         let result = processor.generate_random_iv();
         assert!(result.is_ok());
         assert!(processor.input.iv.is_some());
         assert!(!processor.input.iv.as_ref().unwrap().is_empty());
-        assert_eq!(processor.input.iv.as_ref().unwrap().len(), 8); // 64 bits = 8 hex chars
+        assert_eq!(processor.input.iv.as_ref().unwrap().len(), 16); // 64 bits = 16 hex chars
         assert!(processor
             .input
             .iv
@@ -1,14 +1,15 @@
 use dev_tools_rs::core::crypto::{
     asymmetric::{
         ecdsa::{ecdsa_sign, ecdsa_verify},
-        rsa::{generate_rsa_keypair, rsa_decrypt, rsa_encrypt, rsa_sign, rsa_verify},
+        rsa::{generate_rsa_keypair, rsa_decrypt, rsa_encrypt, rsa_sign, rsa_verify, RsaOptions},
     },
     symmetric::{
         aes::{aes_decrypt, aes_encrypt, AesKeySize},
         des::{des_decrypt, des_encrypt},
         tdes::{triple_des_decrypt, triple_des_encrypt},
     },
-    CipherMode, CryptoAlgorithm, CryptoOperation, CryptographyProcessor,
+    CipherMode, CryptoAlgorithm, CryptoOperation, CryptographyProcessor, InputEncoding,
+    InputEncodings, Padding,
 };
 
 #[cfg(test)]
@@ -27,7 +28,9 @@ mod tests {
             invalid_key,
             AesKeySize::Aes128,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         );
         assert!(result.is_err());
     }
@@ -43,7 +46,12 @@ mod tests {
             invalid_key,
             AesKeySize::Aes128,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings {
+                key: InputEncoding::Hex,
+                ..Default::default()
+            },
         );
         assert!(result.is_err());
     }
@@ -59,7 +67,9 @@ mod tests {
             key,
             AesKeySize::Aes256,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(invalid_iv),
+            InputEncodings::default(),
         );
         assert!(result.is_err());
     }
@@ -75,7 +85,12 @@ mod tests {
             key,
             AesKeySize::Aes256,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(invalid_iv),
+            InputEncodings {
+                iv: InputEncoding::Hex,
+                ..Default::default()
+            },
         );
         assert!(result.is_err());
     }
@@ -91,7 +106,9 @@ mod tests {
             key,
             AesKeySize::Aes256,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         );
         assert!(result.is_err());
     }
@@ -109,7 +126,9 @@ mod tests {
             key,
             AesKeySize::Aes256,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         )
         .unwrap();
 
@@ -119,7 +138,9 @@ mod tests {
             key,
             AesKeySize::Aes256,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(invalid_iv),
+            InputEncodings::default(),
         );
         assert!(result.is_err());
     }
@@ -130,7 +151,14 @@ mod tests {
         let plaintext = "Hello, World!";
         let invalid_key = "short"; // Too short for DES
 
-        let result = des_encrypt(plaintext, invalid_key, CipherMode::ECB, None);
+        let result = des_encrypt(
+            plaintext,
+            invalid_key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -139,7 +167,17 @@ mod tests {
         let plaintext = "Hello, World!";
         let invalid_key = "ghijklmnopqrstuv"; // Invalid hex characters
 
-        let result = des_encrypt(plaintext, invalid_key, CipherMode::ECB, None);
+        let result = des_encrypt(
+            plaintext,
+            invalid_key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings {
+                key: InputEncoding::Hex,
+                ..Default::default()
+            },
+        );
         assert!(result.is_err());
     }
 
@@ -148,7 +186,14 @@ mod tests {
         let invalid_ciphertext = "invalid_hex"; // Invalid hex
         let key = "0123456789abcdef";
 
-        let result = des_decrypt(invalid_ciphertext, key, CipherMode::ECB, None);
+        let result = des_decrypt(
+            invalid_ciphertext,
+            key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -158,7 +203,14 @@ mod tests {
         let plaintext = "Hello, World!";
         let invalid_key = "short"; // Too short for Triple DES
 
-        let result = triple_des_encrypt(plaintext, invalid_key, CipherMode::ECB, None);
+        let result = triple_des_encrypt(
+            plaintext,
+            invalid_key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -167,7 +219,17 @@ mod tests {
         let plaintext = "Hello, World!";
         let invalid_key = "ghijklmnopqrstuvwxyz123456789012345678901234567890"; // Invalid hex characters
 
-        let result = triple_des_encrypt(plaintext, invalid_key, CipherMode::ECB, None);
+        let result = triple_des_encrypt(
+            plaintext,
+            invalid_key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings {
+                key: InputEncoding::Hex,
+                ..Default::default()
+            },
+        );
         assert!(result.is_err());
     }
 
@@ -176,7 +238,14 @@ mod tests {
         let invalid_ciphertext = "invalid_hex"; // Invalid hex
         let key = "0123456789abcdef0123456789abcdef0123456789abcdef";
 
-        let result = triple_des_decrypt(invalid_ciphertext, key, CipherMode::ECB, None);
+        let result = triple_des_decrypt(
+            invalid_ciphertext,
+            key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -186,7 +255,7 @@ mod tests {
         let plaintext = "Hello, World!";
         let invalid_key = "invalid_key";
 
-        let result = rsa_encrypt(plaintext, invalid_key);
+        let result = rsa_encrypt(plaintext, invalid_key, &RsaOptions::default());
         assert!(result.is_err());
     }
 
@@ -195,7 +264,7 @@ mod tests {
         let ciphertext = "encrypted_text";
         let invalid_key = "invalid_key";
 
-        let result = rsa_decrypt(ciphertext, invalid_key);
+        let result = rsa_decrypt(ciphertext, invalid_key, &RsaOptions::default());
         assert!(result.is_err());
     }
 
@@ -204,7 +273,7 @@ mod tests {
         let (_, private_key) = generate_rsa_keypair(2048).unwrap();
         let invalid_ciphertext = "invalid_ciphertext";
 
-        let result = rsa_decrypt(invalid_ciphertext, &private_key);
+        let result = rsa_decrypt(invalid_ciphertext, &private_key, &RsaOptions::default());
         assert!(result.is_err());
     }
 
@@ -213,7 +282,7 @@ mod tests {
         let message = "Hello, World!";
         let invalid_key = "invalid_key";
 
-        let result = rsa_sign(message, invalid_key);
+        let result = rsa_sign(message, invalid_key, &RsaOptions::default());
         assert!(result.is_err());
     }
 
@@ -223,7 +292,7 @@ mod tests {
         let signature = "signature";
         let invalid_key = "invalid_key";
 
-        let result = rsa_verify(message, signature, invalid_key);
+        let result = rsa_verify(message, signature, invalid_key, &RsaOptions::default());
         assert!(result.is_err());
     }
 
@@ -233,7 +302,12 @@ mod tests {
         let message = "Hello, World!";
         let invalid_signature = "invalid_signature";
 
-        let result = rsa_verify(message, invalid_signature, &public_key);
+        let result = rsa_verify(
+            message,
+            invalid_signature,
+            &public_key,
+            &RsaOptions::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -243,10 +317,10 @@ mod tests {
         let plaintext = "Hello, World!";
 
         // Encrypt with public key
-        let encrypted = rsa_encrypt(plaintext, &public_key).unwrap();
+        let encrypted = rsa_encrypt(plaintext, &public_key, &RsaOptions::default()).unwrap();
 
         // Try to decrypt with public key (should fail)
-        let result = rsa_decrypt(&hex::encode(encrypted), &public_key);
+        let result = rsa_decrypt(&hex::encode(encrypted), &public_key, &RsaOptions::default());
         assert!(result.is_err());
     }
 
@@ -256,7 +330,7 @@ mod tests {
         let plaintext = "Hello, World!";
 
         // Try to encrypt with private key (should fail)
-        let result = rsa_encrypt(plaintext, &private_key);
+        let result = rsa_encrypt(plaintext, &private_key, &RsaOptions::default());
         assert!(result.is_err());
     }
 
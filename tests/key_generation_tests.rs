@@ -2,7 +2,8 @@ use dev_tools_rs::core::crypto::{
     asymmetric::{
         generate_des_iv, generate_des_key, generate_triple_des_key, rsa::generate_rsa_keypair,
     },
-    symmetric::{aes::AesKeySize, generate_aes_iv, generate_aes_key},
+    symmetric::{aes::AesKeySize, generate_aes_iv, generate_aes_key, generate_random_bytes},
+    InputEncoding,
 };
 
 #[cfg(test)]
@@ -12,8 +13,8 @@ mod tests {
     // AES Key Generation Tests
     #[test]
     fn test_generate_aes_key_128() {
-        let key = generate_aes_key(AesKeySize::Aes128);
-        assert_eq!(key.len(), 16); // 128 bits = 16 hex chars
+        let key = generate_aes_key(AesKeySize::Aes128, InputEncoding::Hex);
+        assert_eq!(key.len(), 32); // 128 bits = 32 hex chars
         assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
         assert!(key
             .chars()
@@ -22,8 +23,8 @@ mod tests {
 
     #[test]
     fn test_generate_aes_key_192() {
-        let key = generate_aes_key(AesKeySize::Aes192);
-        assert_eq!(key.len(), 24); // 192 bits = 24 hex chars
+        let key = generate_aes_key(AesKeySize::Aes192, InputEncoding::Hex);
+        assert_eq!(key.len(), 48); // 192 bits = 48 hex chars
         assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
         assert!(key
             .chars()
@@ -32,8 +33,8 @@ mod tests {
 
     #[test]
     fn test_generate_aes_key_256() {
-        let key = generate_aes_key(AesKeySize::Aes256);
-        assert_eq!(key.len(), 32); // 256 bits = 32 hex chars
+        let key = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
+        assert_eq!(key.len(), 64); // 256 bits = 64 hex chars
         assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
         assert!(key
             .chars()
@@ -42,32 +43,32 @@ mod tests {
 
     #[test]
     fn test_generate_different_aes_keys() {
-        let key1 = generate_aes_key(AesKeySize::Aes256);
-        let key2 = generate_aes_key(AesKeySize::Aes256);
+        let key1 = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
+        let key2 = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
         assert_ne!(key1, key2); // Generated keys should be different
     }
 
     #[test]
     fn test_generate_multiple_aes_keys_different_sizes() {
-        let key128 = generate_aes_key(AesKeySize::Aes128);
-        let key192 = generate_aes_key(AesKeySize::Aes192);
-        let key256 = generate_aes_key(AesKeySize::Aes256);
+        let key128 = generate_aes_key(AesKeySize::Aes128, InputEncoding::Hex);
+        let key192 = generate_aes_key(AesKeySize::Aes192, InputEncoding::Hex);
+        let key256 = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
 
-        assert_eq!(key128.len(), 16);
-        assert_eq!(key192.len(), 24);
-        assert_eq!(key256.len(), 32);
+        assert_eq!(key128.len(), 32);
+        assert_eq!(key192.len(), 48);
+        assert_eq!(key256.len(), 64);
 
         // All should be different
-        assert_ne!(key128, key192[..16]);
-        assert_ne!(key128, key256[..16]);
-        assert_ne!(key192[..16], key256[..16]);
+        assert_ne!(key128, key192[..32]);
+        assert_ne!(key128, key256[..32]);
+        assert_ne!(key192[..32], key256[..32]);
     }
 
     // AES IV Generation Tests
     #[test]
     fn test_generate_aes_iv() {
-        let iv = generate_aes_iv();
-        assert_eq!(iv.len(), 16); // 128 bits = 16 hex chars
+        let iv = generate_aes_iv(InputEncoding::Hex);
+        assert_eq!(iv.len(), 32); // 128 bits = 32 hex chars
         assert!(iv.chars().all(|c| c.is_ascii_hexdigit()));
         assert!(iv
             .chars()
@@ -76,8 +77,8 @@ mod tests {
 
     #[test]
     fn test_generate_different_aes_ivs() {
-        let iv1 = generate_aes_iv();
-        let iv2 = generate_aes_iv();
+        let iv1 = generate_aes_iv(InputEncoding::Hex);
+        let iv2 = generate_aes_iv(InputEncoding::Hex);
         assert_ne!(iv1, iv2); // Generated IVs should be different
     }
 
@@ -85,7 +86,7 @@ mod tests {
     fn test_generate_multiple_aes_ivs() {
         let mut ivs = Vec::new();
         for _ in 0..10 {
-            ivs.push(generate_aes_iv());
+            ivs.push(generate_aes_iv(InputEncoding::Hex));
         }
 
         // Check all are different
@@ -97,7 +98,7 @@ mod tests {
 
         // Check all have correct format
         for iv in ivs {
-            assert_eq!(iv.len(), 16);
+            assert_eq!(iv.len(), 32);
             assert!(iv.chars().all(|c| c.is_ascii_hexdigit()));
         }
     }
@@ -105,8 +106,8 @@ mod tests {
     // DES Key Generation Tests
     #[test]
     fn test_generate_des_key() {
-        let key = generate_des_key();
-        assert_eq!(key.len(), 8); // 64 bits = 8 hex chars
+        let key = generate_des_key(InputEncoding::Hex);
+        assert_eq!(key.len(), 16); // 64 bits = 16 hex chars
         assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
         assert!(key
             .chars()
@@ -115,8 +116,8 @@ mod tests {
 
     #[test]
     fn test_generate_different_des_keys() {
-        let key1 = generate_des_key();
-        let key2 = generate_des_key();
+        let key1 = generate_des_key(InputEncoding::Hex);
+        let key2 = generate_des_key(InputEncoding::Hex);
         assert_ne!(key1, key2); // Generated keys should be different
     }
 
@@ -124,7 +125,7 @@ mod tests {
     fn test_generate_multiple_des_keys() {
         let mut keys = Vec::new();
         for _ in 0..10 {
-            keys.push(generate_des_key());
+            keys.push(generate_des_key(InputEncoding::Hex));
         }
 
         // Check all are different
@@ -136,7 +137,7 @@ mod tests {
 
         // Check all have correct format
         for key in keys {
-            assert_eq!(key.len(), 8);
+            assert_eq!(key.len(), 16);
             assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
         }
     }
@@ -144,8 +145,8 @@ mod tests {
     // Triple DES Key Generation Tests
     #[test]
     fn test_generate_triple_des_key() {
-        let key = generate_triple_des_key();
-        assert_eq!(key.len(), 24); // 192 bits = 24 hex chars
+        let key = generate_triple_des_key(InputEncoding::Hex);
+        assert_eq!(key.len(), 48); // 192 bits = 48 hex chars
         assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
         assert!(key
             .chars()
@@ -154,8 +155,8 @@ mod tests {
 
     #[test]
     fn test_generate_different_triple_des_keys() {
-        let key1 = generate_triple_des_key();
-        let key2 = generate_triple_des_key();
+        let key1 = generate_triple_des_key(InputEncoding::Hex);
+        let key2 = generate_triple_des_key(InputEncoding::Hex);
         assert_ne!(key1, key2); // Generated keys should be different
     }
 
@@ -163,7 +164,7 @@ mod tests {
     fn test_generate_multiple_triple_des_keys() {
         let mut keys = Vec::new();
         for _ in 0..10 {
-            keys.push(generate_triple_des_key());
+            keys.push(generate_triple_des_key(InputEncoding::Hex));
         }
 
         // Check all are different
@@ -175,7 +176,7 @@ mod tests {
 
         // Check all have correct format
         for key in keys {
-            assert_eq!(key.len(), 24);
+            assert_eq!(key.len(), 48);
             assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
         }
     }
@@ -183,8 +184,8 @@ mod tests {
     // DES IV Generation Tests
     #[test]
     fn test_generate_des_iv() {
-        let iv = generate_des_iv();
-        assert_eq!(iv.len(), 8); // 64 bits = 8 hex chars
+        let iv = generate_des_iv(InputEncoding::Hex);
+        assert_eq!(iv.len(), 16); // 64 bits = 16 hex chars
         assert!(iv.chars().all(|c| c.is_ascii_hexdigit()));
         assert!(iv
             .chars()
@@ -193,8 +194,8 @@ mod tests {
 
     #[test]
     fn test_generate_different_des_ivs() {
-        let iv1 = generate_des_iv();
-        let iv2 = generate_des_iv();
+        let iv1 = generate_des_iv(InputEncoding::Hex);
+        let iv2 = generate_des_iv(InputEncoding::Hex);
         assert_ne!(iv1, iv2); // Generated IVs should be different
     }
 
@@ -202,7 +203,7 @@ mod tests {
     fn test_generate_multiple_des_ivs() {
         let mut ivs = Vec::new();
         for _ in 0..10 {
-            ivs.push(generate_des_iv());
+            ivs.push(generate_des_iv(InputEncoding::Hex));
         }
 
         // Check all are different
@@ -214,7 +215,7 @@ mod tests {
 
         // Check all have correct format
         for iv in ivs {
-            assert_eq!(iv.len(), 8);
+            assert_eq!(iv.len(), 16);
             assert!(iv.chars().all(|c| c.is_ascii_hexdigit()));
         }
     }
@@ -280,11 +281,11 @@ mod tests {
     #[test]
     fn test_key_format_consistency() {
         // Test that keys are consistently formatted as lowercase hex
-        let aes_key = generate_aes_key(AesKeySize::Aes256);
-        let des_key = generate_des_key();
-        let triple_des_key = generate_triple_des_key();
-        let aes_iv = generate_aes_iv();
-        let des_iv = generate_des_iv();
+        let aes_key = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
+        let des_key = generate_des_key(InputEncoding::Hex);
+        let triple_des_key = generate_triple_des_key(InputEncoding::Hex);
+        let aes_iv = generate_aes_iv(InputEncoding::Hex);
+        let des_iv = generate_des_iv(InputEncoding::Hex);
 
         // All should be lowercase hex
         assert!(aes_key
@@ -304,12 +305,35 @@ mod tests {
             .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
     }
 
+    #[test]
+    fn test_generate_keys_in_each_encoding() {
+        // Keys and IVs hold the required number of random bytes in every encoding
+        for encoding in InputEncoding::variants() {
+            let aes_key = generate_aes_key(AesKeySize::Aes192, *encoding);
+            let des_key = generate_des_key(*encoding);
+            let triple_des_key = generate_triple_des_key(*encoding);
+            let aes_iv = generate_aes_iv(*encoding);
+            let des_iv = generate_des_iv(*encoding);
+
+            assert_eq!(encoding.decode(&aes_key, "key").unwrap().len(), 24);
+            assert_eq!(encoding.decode(&des_key, "key").unwrap().len(), 8);
+            assert_eq!(encoding.decode(&triple_des_key, "key").unwrap().len(), 24);
+            assert_eq!(encoding.decode(&aes_iv, "IV").unwrap().len(), 16);
+            assert_eq!(encoding.decode(&des_iv, "IV").unwrap().len(), 8);
+        }
+
+        assert_eq!(generate_random_bytes(12, InputEncoding::Base64).len(), 16);
+        assert!(generate_random_bytes(32, InputEncoding::Utf8)
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric()));
+    }
+
     #[test]
     fn test_key_entropy() {
         // Test that generated keys have good entropy (not all zeros or all same character)
-        let aes_key = generate_aes_key(AesKeySize::Aes256);
-        let des_key = generate_des_key();
-        let triple_des_key = generate_triple_des_key();
+        let aes_key = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
+        let des_key = generate_des_key(InputEncoding::Hex);
+        let triple_des_key = generate_triple_des_key(InputEncoding::Hex);
 
         // Check that keys are not all the same character
         assert!(!aes_key
@@ -323,24 +347,24 @@ mod tests {
             .all(|c| c == triple_des_key.chars().next().unwrap()));
 
         // Check that keys are not all zeros
-        assert_ne!(aes_key, "0".repeat(32));
-        assert_ne!(des_key, "0".repeat(8));
-        assert_ne!(triple_des_key, "0".repeat(24));
+        assert_ne!(aes_key, "0".repeat(64));
+        assert_ne!(des_key, "0".repeat(16));
+        assert_ne!(triple_des_key, "0".repeat(48));
     }
 
     #[test]
     fn test_iv_entropy() {
         // Test that generated IVs have good entropy
-        let aes_iv = generate_aes_iv();
-        let des_iv = generate_des_iv();
+        let aes_iv = generate_aes_iv(InputEncoding::Hex);
+        let des_iv = generate_des_iv(InputEncoding::Hex);
 
         // Check that IVs are not all the same character
         assert!(!aes_iv.chars().all(|c| c == aes_iv.chars().next().unwrap()));
         assert!(!des_iv.chars().all(|c| c == des_iv.chars().next().unwrap()));
 
         // Check that IVs are not all zeros
-        assert_ne!(aes_iv, "0".repeat(16));
-        assert_ne!(des_iv, "0".repeat(8));
+        assert_ne!(aes_iv, "0".repeat(32));
+        assert_ne!(des_iv, "0".repeat(16));
     }
 
     #[test]
@@ -350,11 +374,11 @@ mod tests {
 
         let start = Instant::now();
         for _ in 0..100 {
-            let _ = generate_aes_key(AesKeySize::Aes256);
-            let _ = generate_des_key();
-            let _ = generate_triple_des_key();
-            let _ = generate_aes_iv();
-            let _ = generate_des_iv();
+            let _ = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
+            let _ = generate_des_key(InputEncoding::Hex);
+            let _ = generate_triple_des_key(InputEncoding::Hex);
+            let _ = generate_aes_iv(InputEncoding::Hex);
+            let _ = generate_des_iv(InputEncoding::Hex);
         }
         let duration = start.elapsed();
 
@@ -3,15 +3,19 @@ use dev_tools_rs::core::crypto::{
         aes::{aes_decrypt, aes_encrypt, AesKeySize},
         des::{des_decrypt, des_encrypt},
         generate_aes_iv, generate_aes_key,
+        modes::{decrypt, encrypt, pad, unpad},
         tdes::{triple_des_decrypt, triple_des_encrypt},
     },
-    CipherMode, CryptoAlgorithm, CryptoOperation, CryptographyProcessor, OutputEncoding,
+    CipherMode, CryptoAlgorithm, CryptoOperation, CryptographyProcessor, InputEncoding,
+    InputEncodings, OutputEncoding, Padding,
 };
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aes::Aes128;
     use base64::Engine;
+    use ctr::flavors::Ctr128BE;
 
     // AES Tests
     #[test]
@@ -25,7 +29,9 @@ mod tests {
             key,
             AesKeySize::Aes256,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         )
         .expect("Encryption should succeed");
 
@@ -34,7 +40,9 @@ mod tests {
             key,
             AesKeySize::Aes256,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         )
         .expect("Decryption should succeed");
 
@@ -46,15 +54,25 @@ mod tests {
         let plaintext = "Hello, World!";
         let key = "0123456789abcdef0123456789abcdef"; // 32 hex chars for AES-256
 
-        let encrypted = aes_encrypt(plaintext, key, AesKeySize::Aes256, CipherMode::ECB, None)
-            .expect("Encryption should succeed");
+        let encrypted = aes_encrypt(
+            plaintext,
+            key,
+            AesKeySize::Aes256,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        )
+        .expect("Encryption should succeed");
 
         let decrypted = aes_decrypt(
             &hex::encode(encrypted),
             key,
             AesKeySize::Aes256,
             CipherMode::ECB,
+            Padding::Pkcs7,
             None,
+            InputEncodings::default(),
         )
         .expect("Decryption should succeed");
 
@@ -72,7 +90,9 @@ mod tests {
             key,
             AesKeySize::Aes128,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         )
         .expect("Encryption should succeed");
 
@@ -81,7 +101,9 @@ mod tests {
             key,
             AesKeySize::Aes128,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         )
         .expect("Decryption should succeed");
 
@@ -99,7 +121,9 @@ mod tests {
             key,
             AesKeySize::Aes192,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         )
         .expect("Encryption should succeed");
 
@@ -108,7 +132,9 @@ mod tests {
             key,
             AesKeySize::Aes192,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         )
         .expect("Decryption should succeed");
 
@@ -126,7 +152,9 @@ mod tests {
             key,
             AesKeySize::Aes256,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         );
         assert!(result.is_err());
     }
@@ -142,7 +170,9 @@ mod tests {
             key,
             AesKeySize::Aes256,
             CipherMode::CBC,
+            Padding::Pkcs7,
             Some(iv),
+            InputEncodings::default(),
         );
         assert!(result.is_err());
     }
@@ -154,11 +184,25 @@ mod tests {
         let key = "01234567";
         let iv = "01234567";
 
-        let encrypted = des_encrypt(plaintext, key, CipherMode::CBC, Some(iv))
-            .expect("Encryption should succeed");
+        let encrypted = des_encrypt(
+            plaintext,
+            key,
+            CipherMode::CBC,
+            Padding::Pkcs7,
+            Some(iv),
+            InputEncodings::default(),
+        )
+        .expect("Encryption should succeed");
 
-        let decrypted = des_decrypt(&hex::encode(encrypted), key, CipherMode::CBC, Some(iv))
-            .expect("Decryption should succeed");
+        let decrypted = des_decrypt(
+            &hex::encode(encrypted),
+            key,
+            CipherMode::CBC,
+            Padding::Pkcs7,
+            Some(iv),
+            InputEncodings::default(),
+        )
+        .expect("Decryption should succeed");
 
         assert_eq!(decrypted, plaintext);
     }
@@ -168,11 +212,25 @@ mod tests {
         let plaintext = "Hello, World!";
         let key = "01234567";
 
-        let encrypted =
-            des_encrypt(plaintext, key, CipherMode::ECB, None).expect("Encryption should succeed");
+        let encrypted = des_encrypt(
+            plaintext,
+            key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        )
+        .expect("Encryption should succeed");
 
-        let decrypted = des_decrypt(&hex::encode(encrypted), key, CipherMode::ECB, None)
-            .expect("Decryption should succeed");
+        let decrypted = des_decrypt(
+            &hex::encode(encrypted),
+            key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        )
+        .expect("Decryption should succeed");
 
         assert_eq!(decrypted, plaintext);
     }
@@ -182,7 +240,14 @@ mod tests {
         let plaintext = "Hello, World!";
         let key = "short"; // Too short for DES
 
-        let result = des_encrypt(plaintext, key, CipherMode::ECB, None);
+        let result = des_encrypt(
+            plaintext,
+            key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        );
         assert!(result.is_err());
     }
 
@@ -193,11 +258,25 @@ mod tests {
         let key = "382614643025737a9cceeb79";
         let iv = "060ded27";
 
-        let encrypted = triple_des_encrypt(plaintext, key, CipherMode::CBC, Some(iv))
-            .expect("Encryption should succeed");
+        let encrypted = triple_des_encrypt(
+            plaintext,
+            key,
+            CipherMode::CBC,
+            Padding::Pkcs7,
+            Some(iv),
+            InputEncodings::default(),
+        )
+        .expect("Encryption should succeed");
 
-        let decrypted = triple_des_decrypt(&hex::encode(encrypted), key, CipherMode::CBC, Some(iv))
-            .expect("Decryption should succeed");
+        let decrypted = triple_des_decrypt(
+            &hex::encode(encrypted),
+            key,
+            CipherMode::CBC,
+            Padding::Pkcs7,
+            Some(iv),
+            InputEncodings::default(),
+        )
+        .expect("Decryption should succeed");
 
         assert_eq!(decrypted, plaintext);
     }
@@ -207,11 +286,25 @@ mod tests {
         let plaintext = "Hello, World!";
         let key = "382614643025737a9cceeb79";
 
-        let encrypted = triple_des_encrypt(plaintext, key, CipherMode::ECB, None)
-            .expect("Encryption should succeed");
+        let encrypted = triple_des_encrypt(
+            plaintext,
+            key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        )
+        .expect("Encryption should succeed");
 
-        let decrypted = triple_des_decrypt(&hex::encode(encrypted), key, CipherMode::ECB, None)
-            .expect("Decryption should succeed");
+        let decrypted = triple_des_decrypt(
+            &hex::encode(encrypted),
+            key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        )
+        .expect("Decryption should succeed");
 
         assert_eq!(decrypted, plaintext);
     }
@@ -221,53 +314,141 @@ mod tests {
         let plaintext = "Hello, World!";
         let key = "short"; // Too short for 3DES
 
-        let result = triple_des_encrypt(plaintext, key, CipherMode::ECB, None);
+        let result = triple_des_encrypt(
+            plaintext,
+            key,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        );
         assert!(result.is_err());
     }
 
     // Key and IV Generation Tests
     #[test]
     fn test_generate_aes_key_128() {
-        let key = generate_aes_key(AesKeySize::Aes128);
-        assert_eq!(key.len(), 16); // 128 bits = 16 hex chars
+        let key = generate_aes_key(AesKeySize::Aes128, InputEncoding::Hex);
+        assert_eq!(key.len(), 32); // 128 bits = 32 hex chars
         assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
     #[test]
     fn test_generate_aes_key_192() {
-        let key = generate_aes_key(AesKeySize::Aes192);
-        assert_eq!(key.len(), 24); // 192 bits = 24 hex chars
+        let key = generate_aes_key(AesKeySize::Aes192, InputEncoding::Hex);
+        assert_eq!(key.len(), 48); // 192 bits = 48 hex chars
         assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
     #[test]
     fn test_generate_aes_key_256() {
-        let key = generate_aes_key(AesKeySize::Aes256);
-        assert_eq!(key.len(), 32); // 256 bits = 32 hex chars
+        let key = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
+        assert_eq!(key.len(), 64); // 256 bits = 64 hex chars
         assert!(key.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
     #[test]
     fn test_generate_aes_iv() {
-        let iv = generate_aes_iv();
-        assert_eq!(iv.len(), 16); // 128 bits = 16 hex chars
+        let iv = generate_aes_iv(InputEncoding::Hex);
+        assert_eq!(iv.len(), 32); // 128 bits = 32 hex chars
         assert!(iv.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
     #[test]
     fn test_generate_different_keys() {
-        let key1 = generate_aes_key(AesKeySize::Aes256);
-        let key2 = generate_aes_key(AesKeySize::Aes256);
+        let key1 = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
+        let key2 = generate_aes_key(AesKeySize::Aes256, InputEncoding::Hex);
         assert_ne!(key1, key2); // Generated keys should be different
     }
 
     #[test]
     fn test_generate_different_ivs() {
-        let iv1 = generate_aes_iv();
-        let iv2 = generate_aes_iv();
+        let iv1 = generate_aes_iv(InputEncoding::Hex);
+        let iv2 = generate_aes_iv(InputEncoding::Hex);
         assert_ne!(iv1, iv2); // Generated IVs should be different
     }
 
+    // NIST SP 800-38A, F.2.1 CBC-AES128 with the key, IV and text given as hex and Base64
+    #[test]
+    fn test_aes_input_encodings() {
+        let hex = InputEncodings {
+            key: InputEncoding::Hex,
+            iv: InputEncoding::Hex,
+            plaintext: InputEncoding::Hex,
+            ciphertext: InputEncoding::Hex,
+        };
+        let encrypted = aes_encrypt(
+            "6bc1bee22e409f96e93d7e117393172a",
+            "2b7e151628aed2a6abf7158809cf4f3c",
+            AesKeySize::Aes128,
+            CipherMode::CBC,
+            Padding::NoPadding,
+            Some("000102030405060708090a0b0c0d0e0f"),
+            hex,
+        )
+        .unwrap();
+        assert_eq!(hex::encode(&encrypted), "7649abac8119b246cee98e9b12e9197d");
+
+        let base64 = InputEncodings {
+            key: InputEncoding::Base64,
+            iv: InputEncoding::Base64,
+            plaintext: InputEncoding::Hex,
+            ciphertext: InputEncoding::Base64,
+        };
+        let decrypted = aes_decrypt(
+            &base64::engine::general_purpose::STANDARD.encode(&encrypted),
+            "K34VFiiu0qar9xWICc9PPA==",
+            AesKeySize::Aes128,
+            CipherMode::CBC,
+            Padding::NoPadding,
+            Some("AAECAwQFBgcICQoLDA0ODw=="),
+            base64,
+        )
+        .unwrap();
+        assert_eq!(hex::encode(decrypted), "6bc1bee22e409f96e93d7e117393172a");
+
+        // a hex key is 32 characters but only 16 bytes
+        let result = aes_encrypt(
+            "Hello, World!",
+            "2b7e151628aed2a6abf7158809cf4f3c",
+            AesKeySize::Aes256,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            hex,
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "AES-256 requires a 32-byte key, got 16 bytes"
+        );
+    }
+
+    #[test]
+    fn test_crypto_processor_input_encodings() {
+        let mut processor = CryptographyProcessor::new();
+        processor.input.algorithm = CryptoAlgorithm::TripleDES;
+        processor.input.operation = CryptoOperation::Encrypt;
+        processor.input.mode = Some(CipherMode::CBC);
+        processor.input.input_encodings.key = InputEncoding::Base64;
+        processor.input.input_encodings.iv = InputEncoding::Hex;
+        processor.input.input_encodings.plaintext = InputEncoding::Hex;
+        processor.input.input_encodings.ciphertext = InputEncoding::Base64;
+        processor.input.encoding = OutputEncoding::Base64;
+        processor.generate_random_key().unwrap();
+        processor.generate_random_iv().unwrap();
+        assert_eq!(processor.input.key.len(), 32);
+        assert_eq!(processor.input.iv.as_ref().unwrap().len(), 16);
+
+        processor.input.input_text = "00ff10ee".to_string();
+        processor.input.input_text = processor.process().unwrap();
+        processor.input.operation = CryptoOperation::Decrypt;
+        assert_eq!(processor.process().unwrap(), "00ff10ee");
+
+        processor.input.input_encodings.plaintext = InputEncoding::Utf8;
+        let result = processor.process().unwrap();
+        assert!(result.starts_with("00000000  00 ff 10 ee"), "{result}");
+    }
+
     // Integration Tests with CryptographyProcessor
     #[test]
     fn test_crypto_processor_aes_encrypt_hex() {
@@ -374,4 +555,190 @@ mod tests {
         assert!(result.is_err());
         assert!(processor.error.is_some());
     }
+
+    // NIST SP 800-38A, appendix F with AES-128
+    #[test]
+    fn test_aes_mode_vectors() {
+        let key = hex::decode("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        let iv = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let counter = hex::decode("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff").unwrap();
+        let plaintext =
+            hex::decode("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51")
+                .unwrap();
+
+        for (mode, iv, plaintext, expected) in [
+            (
+                CipherMode::ECB,
+                &iv,
+                &plaintext[..16],
+                "3ad77bb40d7a3660a89ecaf32466ef97",
+            ),
+            (
+                CipherMode::CBC,
+                &iv,
+                &plaintext[..16],
+                "7649abac8119b246cee98e9b12e9197d",
+            ),
+            (
+                CipherMode::CFB,
+                &iv,
+                &plaintext[..],
+                "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b",
+            ),
+            (
+                CipherMode::CFB8,
+                &iv,
+                &plaintext[..18],
+                "3b79424c9c0dd436bace9e0ed4586a4f32b9",
+            ),
+            (
+                CipherMode::OFB,
+                &iv,
+                &plaintext[..],
+                "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed825",
+            ),
+            (
+                CipherMode::CTR,
+                &counter,
+                &plaintext[..],
+                "874d6191b620e3261bef6864990db6ce9806f66b7970fdff8617187bb9fffdff",
+            ),
+        ] {
+            let encrypted =
+                encrypt::<Aes128, Ctr128BE>(mode, Padding::NoPadding, &key, iv, plaintext).unwrap();
+            assert_eq!(hex::encode(&encrypted), expected, "{mode}");
+            let decrypted =
+                decrypt::<Aes128, Ctr128BE>(mode, Padding::NoPadding, &key, iv, &encrypted)
+                    .unwrap();
+            assert_eq!(decrypted, plaintext, "{mode}");
+        }
+    }
+
+    #[test]
+    fn test_padding_schemes() {
+        let data = b"test";
+        for (padding, expected) in [
+            (Padding::Pkcs7, &b"test\x04\x04\x04\x04"[..]),
+            (Padding::Zero, b"test\x00\x00\x00\x00"),
+            (Padding::AnsiX923, b"test\x00\x00\x00\x04"),
+        ] {
+            let padded = pad(padding, data, 8).unwrap();
+            assert_eq!(padded, expected, "{padding}");
+            assert_eq!(unpad(padding, padded, 8).unwrap(), data, "{padding}");
+        }
+
+        let padded = pad(Padding::Iso10126, data, 8).unwrap();
+        assert_eq!((padded.len(), padded[7]), (8, 4));
+        assert_eq!(unpad(Padding::Iso10126, padded, 8).unwrap(), data);
+
+        // Aligned input gets a whole block of padding except with zero and no padding
+        assert_eq!(pad(Padding::Pkcs7, b"12345678", 8).unwrap().len(), 16);
+        assert_eq!(pad(Padding::Zero, b"12345678", 8).unwrap().len(), 8);
+        assert_eq!(pad(Padding::NoPadding, b"12345678", 8).unwrap().len(), 8);
+        assert!(pad(Padding::NoPadding, data, 8).is_err());
+
+        assert!(unpad(Padding::Pkcs7, b"test\x04\x04\x03\x04".to_vec(), 8).is_err());
+        assert!(unpad(Padding::AnsiX923, b"test\x00\x01\x00\x04".to_vec(), 8).is_err());
+        assert!(unpad(Padding::Pkcs7, b"testtest".to_vec(), 8).is_err());
+        assert!(unpad(Padding::Pkcs7, vec![], 8).is_err());
+    }
+
+    #[test]
+    fn test_crypto_processor_modes_and_paddings() {
+        let plaintext = "Hello, World!";
+        for (algorithm, key, iv) in [
+            (CryptoAlgorithm::AES, "0123456789abcdef", "fedcba9876543210"),
+            (CryptoAlgorithm::DES, "20c5f825", "6a5da673"),
+            (
+                CryptoAlgorithm::TripleDES,
+                "0123456789abcdef01234567",
+                "6a5da673",
+            ),
+        ] {
+            for mode in CipherMode::variants() {
+                for padding in Padding::variants() {
+                    let mut processor = CryptographyProcessor::new();
+                    processor.input.algorithm = algorithm;
+                    processor.input.mode = Some(*mode);
+                    processor.input.padding = *padding;
+                    processor.input.key = key.to_string();
+                    processor.input.iv = Some(iv.to_string());
+                    processor.input.input_text = plaintext.to_string();
+
+                    let result = processor.process();
+                    if mode.uses_padding() && *padding == Padding::NoPadding {
+                        assert!(result.is_err(), "{algorithm} {mode} {padding}");
+                        continue;
+                    }
+                    let encrypted = result.unwrap();
+                    if !mode.uses_padding() {
+                        assert_eq!(encrypted.len(), plaintext.len() * 2, "{algorithm} {mode}");
+                    }
+
+                    processor.input.operation = CryptoOperation::Decrypt;
+                    processor.input.input_text = encrypted;
+                    assert_eq!(
+                        processor.process().unwrap(),
+                        plaintext,
+                        "{algorithm} {mode} {padding}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stream_modes_require_iv() {
+        for (algorithm, key) in [
+            (CryptoAlgorithm::AES, "0123456789abcdef"),
+            (CryptoAlgorithm::DES, "20c5f825"),
+            (CryptoAlgorithm::TripleDES, "0123456789abcdef01234567"),
+        ] {
+            for mode in CipherMode::variants() {
+                let mut processor = CryptographyProcessor::new();
+                processor.input.algorithm = algorithm;
+                processor.input.mode = Some(*mode);
+                processor.input.key = key.to_string();
+                processor.input.iv = None;
+                processor.input.input_text = "Hello, World!".to_string();
+
+                let result = processor.process();
+                if mode.requires_iv() {
+                    let error = result.unwrap_err().to_string();
+                    assert!(
+                        error.contains("requires an IV"),
+                        "{algorithm} {mode}: {error}"
+                    );
+                } else {
+                    assert!(result.is_ok(), "{algorithm} {mode}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_padding() {
+        let key = "0123456789abcdef";
+        let encrypted = aes_encrypt(
+            "Hello, World!",
+            key,
+            AesKeySize::Aes128,
+            CipherMode::ECB,
+            Padding::Zero,
+            None,
+            InputEncodings::default(),
+        )
+        .unwrap();
+
+        let result = aes_decrypt(
+            &hex::encode(encrypted),
+            key,
+            AesKeySize::Aes128,
+            CipherMode::ECB,
+            Padding::Pkcs7,
+            None,
+            InputEncodings::default(),
+        );
+        assert!(result.unwrap_err().to_string().contains("padding"));
+    }
 }